@@ -1,9 +1,9 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
     layout::{Constraint, Layout, Rect},
     style::{
-        Color, Stylize,
+        Stylize,
     },
     symbols,
     text::Line,
@@ -16,21 +16,32 @@ use ratatui::{
 };
 use std::error::Error;
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    ALT_ROW_BG_COLOR,
-    SELECTED_STYLE,
-    TEXT_FG_COLOR,
-    FOOTER_STYLE
-};
+use crate::styles::theme;
 
 use crate::Autoloop;
+use crate::tr;
+use crate::mouse::{
+    Click,
+    Footer,
+    Targets,
+    is_click
+};
+use crate::keymap::{
+    Action,
+    Keymap
+};
+use crate::wizard::{
+    Nav,
+    Step
+};
 
 pub struct AutoloopWidget {
     should_exit: bool,
+    nav: Nav,
     selected_type: Autoloop,
-    list_element_entries: AutoloopList
+    list_element_entries: AutoloopList,
+    targets: Targets,
+    help: bool
 }
 
 struct AutoloopList {
@@ -56,7 +67,11 @@ struct AutoloopEntry {
 
 impl From<&AutoloopEntry> for ListItem<'_> {
     fn from(value: &AutoloopEntry) -> Self {
-        let line = Line::styled(value.list_element.to_string(), TEXT_FG_COLOR);
+        let label = match value.list_element {
+            Autoloop::Yes => tr!("common.yes"),
+            Autoloop::No => tr!("common.no")
+        };
+        let line = Line::styled(label, theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -74,11 +89,14 @@ impl Default for AutoloopWidget {
     fn default() -> Self {
         Self {
             should_exit: false,
+            nav: Nav::Next,
             selected_type: Autoloop::No,
             list_element_entries: AutoloopList::from_iter([
-                (Autoloop::Yes, "Auto loop this media file. Press ENTER to select."),
-                (Autoloop::No, "Do not auto loop this media file. Press ENTER to select.")
+                (Autoloop::Yes, tr!("autoloop.yes_info")),
+                (Autoloop::No, tr!("autoloop.no_info"))
             ]),
+            targets: Targets::default(),
+            help: false
         }
     }
 }
@@ -87,15 +105,18 @@ impl AutoloopWidget {
     pub fn new(autoloop_preset: Autoloop) -> Self {
         Self {
             should_exit: false,
+            nav: Nav::Next,
             selected_type: autoloop_preset,
             list_element_entries: AutoloopList::from_iter([
-                (Autoloop::Yes, "Auto loop this media file. Press ENTER to select."),
-                (Autoloop::No, "Do not auto loop this media file. Press ENTER to select.")
+                (Autoloop::Yes, tr!("autoloop.yes_info")),
+                (Autoloop::No, tr!("autoloop.no_info"))
             ]),
+            targets: Targets::default(),
+            help: false,
 
         }
     }
-    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Autoloop, Box< dyn Error>> {
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<Autoloop>, Box< dyn Error>> {
         // set the preset
         if let Some(index) = self.list_element_entries.list.iter().position(|i| i.list_element == self.selected_type) {
             self.list_element_entries.state.select(Some(index));
@@ -103,25 +124,42 @@ impl AutoloopWidget {
 
         while !self.should_exit {
             terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
-            if let Event::Key(key) = event::read()? {
-                self.handle_key(key);
-            };
+            match event::read()? {
+                Event::Key(key) => self.handle_key(key),
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                _ => {}
+            }
         }
-        Ok(self.selected_type)
+        Ok(self.nav.with(self.selected_type))
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::list()
+            .bind(&[KeyCode::Char('h'), KeyCode::Left], Action::Unselect)
+            .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose)
+            .bind(&[KeyCode::Esc], Action::Back)
+            .bind(&[KeyCode::Char('q')], Action::Quit)
     }
 
     fn handle_key(&mut self, key: KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
         }
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => self.should_exit = true,
-            KeyCode::Char('h') | KeyCode::Left => self.select_none(),
-            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-            KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-            KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-            KeyCode::Char('G') | KeyCode::End => self.select_last(),
-            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match AutoloopWidget::keymap().action(key.code) {
+            Some(Action::Help) => self.help = true,
+            Some(Action::Back) => self.leave(Nav::Back),
+            Some(Action::Quit) => self.leave(Nav::Cancel),
+            Some(Action::Unselect) => self.select_none(),
+            Some(Action::Next) => self.select_next(),
+            Some(Action::Previous) => self.select_previous(),
+            Some(Action::First) => self.select_first(),
+            Some(Action::Last) => self.select_last(),
+            Some(Action::Choose) => {
                 // add code to select the list item
                 self.set_current_type();
                 self.should_exit = true;
@@ -129,7 +167,26 @@ impl AutoloopWidget {
             _ => {}
         }
     }
-    
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.help {
+            self.help = !is_click(&mouse);
+            return;
+        }
+        let footer = Footer::list(&Self::keymap());
+        let rows = self.list_element_entries.list.len();
+        match self.targets.click(mouse, &self.list_element_entries.state, rows, Some(&footer)) {
+            Some(Click::Key(code)) => self.handle_key(KeyEvent::from(code)),
+            Some(Click::Select(i)) => self.list_element_entries.state.select(Some(i)),
+            None => {}
+        }
+    }
+
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
     fn set_current_type(&mut self) {
         if let Some(i) = self.list_element_entries.state.selected() {
             match self.list_element_entries.list[i].list_element {
@@ -158,26 +215,28 @@ impl AutoloopWidget {
 
     // rendering logic
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Auto Loop")
+        Paragraph::new(tr!("autoloop.header"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
     fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .style(FOOTER_STYLE)
+        Paragraph::new(Footer::list(&Self::keymap()).line())
+            .style(theme().footer)
             .centered()
             .render(area, buf);
     }
 
     fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw("Do you want your file to automatically loop?").centered())
+            .title(Line::raw(tr!("autoloop.question")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        self.targets.list = block.inner(area);
 
         // Iterate through all the elements in the 'items' and stylise them
         let items: Vec<ListItem> = self 
@@ -186,7 +245,7 @@ impl AutoloopWidget {
             .iter()
             .enumerate()
             .map(|(i, list_item)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(list_item).bg(color)
             })
             .collect();
@@ -194,7 +253,7 @@ impl AutoloopWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -206,38 +265,31 @@ impl AutoloopWidget {
         let info = if let Some(i) = self.list_element_entries.state.selected() {
             self.list_element_entries.list[i].info.clone()
         } else {
-            "Nothing selected...".to_string()
+            tr!("common.nothing_selected").to_string()
         };
 
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INFORMATION").centered())
+            .title(Line::raw(tr!("common.information")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(info)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
 
 }
 
-const fn alternate_colors(i: usize) -> Color {
-    if i.is_multiple_of(2) {
-        NORMAL_ROW_BG
-    } else {
-        ALT_ROW_BG_COLOR
-    }
-}
-
 impl Widget for &mut AutoloopWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        self.targets.screen = area;
         let [header_area, main_area, footer_area] = Layout::vertical([
             Constraint::Length(2),
             Constraint::Fill(1),
@@ -255,6 +307,10 @@ impl Widget for &mut AutoloopWidget {
         AutoloopWidget::render_footer(footer_area, buf);
         self.render_list(list_area, buf);
         self.render_selected_item(item_area, buf);
+
+        if self.help {
+            AutoloopWidget::keymap().render_help(tr!("help.autoloop"), area, buf);
+        }
     }
 
 }
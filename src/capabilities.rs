@@ -0,0 +1,322 @@
+use crate::{
+    Model,
+    ProcType
+};
+use crate::probe::{
+    MediaInfo,
+    VideoStream,
+    format_bit_rate
+};
+use crate::tr;
+
+/// A video codec a model can decode, with the profiles and bit depth its decoder handles. An
+/// empty profile list accepts any profile.
+pub struct VideoCodec {
+    pub codec: &'static str,
+    pub profiles: &'static [&'static str],
+    pub max_bit_depth: u8
+}
+
+/// What each model can play smoothly. Rules are checked in order and every failure is reported,
+/// so the operator can fix them all in one export.
+pub struct Capabilities {
+    pub max_width: u32,
+    pub max_height: u32,
+    // (largest picture height, highest frame rate) pairs, in increasing height
+    pub frame_rates: &'static [(u32, f64)],
+    // bits per second
+    pub max_bit_rate: u64,
+    pub video_codecs: &'static [VideoCodec],
+    pub audio_codecs: &'static [&'static str],
+    pub max_audio_channels: u32,
+    pub image_codecs: &'static [&'static str],
+    pub max_image_pixels: u64
+}
+
+const AUDIO_CODECS: [&str; 9] = [
+    "aac", "mp3", "mp2", "opus", "vorbis", "flac", "ac3", "pcm_s16le", "pcm_s24le"
+];
+
+const IMAGE_CODECS: [&str; 6] = [
+    "mjpeg", "png", "webp", "gif", "bmp", "tiff"
+];
+
+const ECO: Capabilities = Capabilities {
+    max_width: 1920,
+    max_height: 1080,
+    frame_rates: &[(720, 60.0), (1080, 30.0)],
+    max_bit_rate: 20_000_000,
+    video_codecs: &[
+        VideoCodec { codec: "h264", profiles: &["Constrained Baseline", "Baseline", "Main", "High"], max_bit_depth: 8 },
+        VideoCodec { codec: "hevc", profiles: &["Main"], max_bit_depth: 8 },
+        VideoCodec { codec: "vp8", profiles: &[], max_bit_depth: 8 }
+    ],
+    audio_codecs: &AUDIO_CODECS,
+    max_audio_channels: 2,
+    image_codecs: &IMAGE_CODECS,
+    max_image_pixels: 24_000_000
+};
+
+const STANDARD: Capabilities = Capabilities {
+    max_width: 2560,
+    max_height: 1440,
+    frame_rates: &[(1080, 60.0), (1440, 30.0)],
+    max_bit_rate: 40_000_000,
+    video_codecs: &[
+        VideoCodec { codec: "h264", profiles: &["Constrained Baseline", "Baseline", "Main", "High"], max_bit_depth: 8 },
+        VideoCodec { codec: "hevc", profiles: &["Main", "Main 10"], max_bit_depth: 10 },
+        VideoCodec { codec: "vp8", profiles: &[], max_bit_depth: 8 },
+        VideoCodec { codec: "vp9", profiles: &["Profile 0"], max_bit_depth: 8 }
+    ],
+    audio_codecs: &AUDIO_CODECS,
+    max_audio_channels: 6,
+    image_codecs: &IMAGE_CODECS,
+    max_image_pixels: 50_000_000
+};
+
+const PRO: Capabilities = Capabilities {
+    max_width: 3840,
+    max_height: 2160,
+    frame_rates: &[(2160, 60.0)],
+    max_bit_rate: 80_000_000,
+    video_codecs: &[
+        VideoCodec { codec: "h264", profiles: &["Constrained Baseline", "Baseline", "Main", "High"], max_bit_depth: 8 },
+        VideoCodec { codec: "hevc", profiles: &["Main", "Main 10"], max_bit_depth: 10 },
+        VideoCodec { codec: "vp8", profiles: &[], max_bit_depth: 8 },
+        VideoCodec { codec: "vp9", profiles: &["Profile 0", "Profile 2"], max_bit_depth: 10 },
+        VideoCodec { codec: "av1", profiles: &["Main"], max_bit_depth: 10 }
+    ],
+    audio_codecs: &AUDIO_CODECS,
+    max_audio_channels: 8,
+    image_codecs: &IMAGE_CODECS,
+    max_image_pixels: 100_000_000
+};
+
+impl Model {
+    pub fn capabilities(&self) -> &'static Capabilities {
+        match self {
+            Model::Eco => &ECO,
+            Model::Standard => &STANDARD,
+            Model::Pro => &PRO
+        }
+    }
+
+    /// The largest picture, as width and height, the model's display output is built for.
+    pub fn max_resolution(&self) -> (u32, u32) {
+        let capabilities = self.capabilities();
+        (capabilities.max_width, capabilities.max_height)
+    }
+}
+
+// the number of bits per colour channel, from the suffix of names such as yuv420p10le, p010le
+// or gray12be. Digits elsewhere in the name, as in nv12 or yuv410p, describe the layout instead.
+pub fn bit_depth(pix_fmt: &str) -> u8 {
+    let name = pix_fmt.strip_suffix("le").or_else(|| pix_fmt.strip_suffix("be")).unwrap_or(pix_fmt);
+    let digits = name.len() - name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let (prefix, depth) = name.split_at(name.len() - digits);
+    // the semi-planar p010, p210 and p416 put the chroma layout first
+    let depth = if prefix == "p" && depth.len() == 3 { &depth[1..] } else { depth };
+    match depth.parse() {
+        Ok(depth) if prefix.ends_with('p') || prefix == "gray" => depth,
+        _ => 8
+    }
+}
+
+fn codec_names(codecs: &[&str]) -> String {
+    codecs.join(", ")
+}
+
+/// The picture size as shown, with width and height swapped for rotated video.
+pub fn display_size(video: &VideoStream) -> (u32, u32) {
+    if video.rotation % 180 == 90 {
+        (video.height, video.width)
+    } else {
+        (video.width, video.height)
+    }
+}
+
+/// Returns every reason the file cannot be played on the model, or nothing if it can.
+pub fn check_media(info: &MediaInfo, proc_type: &ProcType, model: &Model) -> Vec<String> {
+    let capabilities = model.capabilities();
+    let mut reasons = Vec::new();
+    match proc_type {
+        ProcType::Video => {
+            match info.video.as_ref() {
+                Some(video) => check_video(video, capabilities, model, &mut reasons),
+                None => reasons.push(tr!("capabilities.no_video").to_string())
+            }
+            if let Some(bit_rate) = info.bit_rate && bit_rate > capabilities.max_bit_rate {
+                reasons.push(tr!("capabilities.bit_rate", rate = format_bit_rate(bit_rate), max = format_bit_rate(capabilities.max_bit_rate)));
+            }
+            check_audio(info, capabilities, model, &mut reasons);
+        },
+        ProcType::Audio => {
+            if info.audio.is_none() {
+                reasons.push(tr!("capabilities.no_audio").to_string());
+            }
+            check_audio(info, capabilities, model, &mut reasons);
+        },
+        ProcType::Image | ProcType::Slideshow => {
+            match info.video.as_ref() {
+                Some(image) => {
+                    if !capabilities.image_codecs.contains(&image.codec.as_str()) {
+                        reasons.push(tr!("capabilities.image_format", codec = image.codec));
+                    }
+                    let pixels = image.width as u64 * image.height as u64;
+                    if pixels > capabilities.max_image_pixels {
+                        reasons.push(tr!("capabilities.image_size", width = image.width, height = image.height, max = capabilities.max_image_pixels / 1_000_000, model = model));
+                    }
+                },
+                None => reasons.push(tr!("capabilities.no_image").to_string())
+            }
+        },
+        _ => {}
+    }
+    reasons
+}
+
+fn check_video(video: &VideoStream, capabilities: &Capabilities, model: &Model, reasons: &mut Vec<String>) {
+    match capabilities.video_codecs.iter().find(|c| c.codec == video.codec) {
+        Some(codec) => {
+            if let Some(profile) = video.profile.as_ref()
+                && !codec.profiles.is_empty()
+                && !codec.profiles.contains(&profile.as_str()) {
+                reasons.push(tr!("capabilities.profile", codec = video.codec, profile = profile, model = model, supported = codec.profiles.join(", ")));
+            }
+            let depth = video.pix_fmt.as_deref().map_or(8, bit_depth);
+            if depth > codec.max_bit_depth {
+                reasons.push(tr!("capabilities.bit_depth", depth = depth, codec = video.codec, model = model, max = codec.max_bit_depth));
+            }
+        },
+        None => {
+            let supported: Vec<&str> = capabilities.video_codecs.iter().map(|c| c.codec).collect();
+            reasons.push(tr!("capabilities.video_codec", codec = video.codec, model = model, supported = codec_names(&supported)));
+        }
+    }
+
+    let (width, height) = display_size(video);
+    // compare the long and short sides so portrait video is judged fairly
+    if width.max(height) > capabilities.max_width || width.min(height) > capabilities.max_height {
+        reasons.push(tr!("capabilities.resolution", width = width, height = height, max = capabilities.max_height));
+    } else if let Some(frame_rate) = video.frame_rate {
+        let short_side = width.min(height);
+        let limit = capabilities.frame_rates.iter()
+            .find(|(max_height, _)| short_side <= *max_height)
+            .map(|(_, max_fps)| *max_fps);
+        if let Some(max_fps) = limit && frame_rate > max_fps + 0.01 {
+            reasons.push(tr!("capabilities.frame_rate", rate = format!("{:.2}", frame_rate), height = short_side, model = model, max = max_fps));
+        }
+    }
+}
+
+fn check_audio(info: &MediaInfo, capabilities: &Capabilities, model: &Model, reasons: &mut Vec<String>) {
+    if let Some(audio) = info.audio.as_ref() {
+        if !capabilities.audio_codecs.contains(&audio.codec.as_str()) {
+            reasons.push(tr!("capabilities.audio_codec", codec = audio.codec, supported = codec_names(capabilities.audio_codecs)));
+        }
+        if audio.channels > capabilities.max_audio_channels {
+            reasons.push(tr!("capabilities.channels", channels = audio.channels, model = model, max = capabilities.max_audio_channels));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::AudioStream;
+
+    fn video(codec: &str, profile: &str, pix_fmt: &str, width: u32, height: u32, frame_rate: f64) -> MediaInfo {
+        MediaInfo {
+            container: String::from("mov,mp4,m4a,3gp,3g2,mj2"),
+            duration: Some(10.0),
+            bit_rate: Some(8_000_000),
+            video: Some(VideoStream {
+                codec: String::from(codec),
+                profile: Some(String::from(profile)),
+                pix_fmt: Some(String::from(pix_fmt)),
+                width,
+                height,
+                frame_rate: Some(frame_rate),
+                rotation: 0
+            }),
+            audio: Some(AudioStream {
+                codec: String::from("aac"),
+                channels: 2,
+                sample_rate: Some(48000)
+            })
+        }
+    }
+
+    #[test]
+    fn check_bit_depth() {
+        assert_eq!(bit_depth("yuv420p"), 8);
+        assert_eq!(bit_depth("yuv420p10le"), 10);
+        assert_eq!(bit_depth("p010le"), 10);
+        assert_eq!(bit_depth("yuv444p12le"), 12);
+        assert_eq!(bit_depth("p016be"), 16);
+        assert_eq!(bit_depth("gray10le"), 10);
+        // 8-bit formats with digits in their names
+        assert_eq!(bit_depth("nv12"), 8);
+        assert_eq!(bit_depth("nv16"), 8);
+        assert_eq!(bit_depth("yuv410p"), 8);
+        assert_eq!(bit_depth("yuvj420p"), 8);
+    }
+
+    #[test]
+    fn check_nv12_plays() {
+        let info = video("h264", "High", "nv12", 1920, 1080, 25.0);
+        assert!(check_media(&info, &ProcType::Video, &Model::Eco).is_empty());
+    }
+
+    #[test]
+    fn check_hevc_10_bit() {
+        let info = video("hevc", "Main 10", "yuv420p10le", 1920, 1080, 25.0);
+        let reasons = check_media(&info, &ProcType::Video, &Model::Eco);
+        assert_eq!(reasons.len(), 2);
+        assert!(reasons[1].starts_with("10-bit hevc"));
+        assert!(check_media(&info, &ProcType::Video, &Model::Standard).is_empty());
+    }
+
+    #[test]
+    fn check_resolution_and_frame_rate() {
+        let info = video("h264", "High", "yuv420p", 3840, 2160, 60.0);
+        assert!(check_media(&info, &ProcType::Video, &Model::Pro).is_empty());
+        let reasons = check_media(&info, &ProcType::Video, &Model::Eco);
+        assert_eq!(reasons, vec![String::from("Video resolution is too high (3840x2160). Export video as 1080p maximum.")]);
+
+        let info = video("h264", "High", "yuv420p", 1920, 1080, 59.94);
+        let reasons = check_media(&info, &ProcType::Video, &Model::Eco);
+        assert_eq!(reasons, vec![String::from("Frame rate is too high (59.94 fps) for 1080p on the Eco model. Export at 30 fps maximum.")]);
+        assert!(check_media(&video("h264", "High", "yuv420p", 1280, 720, 59.94), &ProcType::Video, &Model::Eco).is_empty());
+
+        // portrait video uses the short side
+        let mut info = video("h264", "High", "yuv420p", 1080, 1920, 25.0);
+        assert!(check_media(&info, &ProcType::Video, &Model::Eco).is_empty());
+        info.video.as_mut().unwrap().rotation = 90;
+        assert!(check_media(&info, &ProcType::Video, &Model::Eco).is_empty());
+    }
+
+    #[test]
+    fn check_audio_rules() {
+        let mut info = video("h264", "High", "yuv420p", 1920, 1080, 25.0);
+        info.video = None;
+        info.audio.as_mut().unwrap().channels = 6;
+        assert!(check_media(&info, &ProcType::Audio, &Model::Standard).is_empty());
+        let reasons = check_media(&info, &ProcType::Audio, &Model::Eco);
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].contains("too many channels"));
+
+        info.audio = None;
+        assert_eq!(check_media(&info, &ProcType::Audio, &Model::Pro).len(), 1);
+    }
+
+    #[test]
+    fn check_image_rules() {
+        let mut info = video("png", "", "rgba", 8000, 6000, 25.0);
+        info.audio = None;
+        assert_eq!(check_media(&info, &ProcType::Image, &Model::Eco).len(), 1);
+        assert!(check_media(&info, &ProcType::Image, &Model::Pro).is_empty());
+        info.video.as_mut().unwrap().codec = String::from("jpegxl");
+        assert_eq!(check_media(&info, &ProcType::Image, &Model::Pro).len(), 1);
+    }
+}
@@ -0,0 +1,711 @@
+use std::{
+    fs,
+    io::{
+        self,
+        BufRead
+    },
+    path::{
+        Path,
+        PathBuf
+    },
+    process::Command
+};
+use serde_json::{
+    json,
+    Value
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::{
+    Task,
+    Model,
+    ProcType,
+    MediaPlayer,
+    Autoloop,
+    AdvancedSchedule,
+    Weekday,
+    Schedule,
+    Timings,
+    env_file_path,
+    fresh_uuid,
+    load_task,
+    media_root,
+    read_model,
+    write_task
+};
+use crate::mount::{
+    Drive,
+    Resolution,
+    identify_mounted_drives,
+    resolve_media
+};
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
+};
+use crate::probe::probe;
+use crate::capabilities::check_media;
+use crate::slideshow::{
+    MIN_SLIDE_DELAY,
+    MAX_SLIDE_DELAY
+};
+use crate::schedule::active::day_schedule;
+use crate::schedule::import::import_schedule;
+use crate::schedule::timings::{
+    timing_format_correct,
+    timing_no_clash
+};
+use crate::web::url_format_correct;
+use crate::review::summary;
+use crate::lock::{
+    Attempt,
+    PinStore,
+    now,
+    recovery_message,
+    valid_pin,
+    write_recovery
+};
+use crate::provision::{
+    key_path,
+    read_key
+};
+
+/// The subcommands handled here rather than by the setup screens.
+pub const COMMANDS: [&str; 6] = ["show", "set", "schedule", "validate", "apply", "pin"];
+
+// exit codes, so scripts can tell a typo from a configuration that will not play
+const EXIT_OK: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_INVALID: i32 = 3;
+const EXIT_SERVICE: i32 = 4;
+
+const USAGE: &str = "Usage:
+  mediatimer show [--json]
+  mediatimer set [--proctype TYPE] [--file PATH] [--url URL] [--autoloop | --no-autoloop]
+                 [--schedule | --no-schedule] [--slide-delay SECONDS] [--player PLAYER] [--json]
+  mediatimer schedule add DAY HH:MM-HH:MM [--json]
+  mediatimer schedule import FILE [--json]
+  mediatimer validate [--json]
+  mediatimer apply [--json]
+  mediatimer pin set|clear|status [--json]
+  mediatimer pin recovery FOLDER [PIN_FILE] [--json]
+
+With a PIN set, set, schedule add, schedule import, apply, pin set and pin clear need the
+current PIN, given with --pin PIN or on the first line of stdin.
+
+A recovery file removes one PIN once: this device's, or the one in PIN_FILE, a copy of another
+device's pin file. Setting a PIN again needs a new recovery file.";
+
+const DAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+/// Why a command failed.
+#[derive(Debug, PartialEq)]
+enum Failure {
+    Usage(String),
+    Invalid(Vec<String>),
+    Error(String),
+    Service(String)
+}
+
+impl Failure {
+    fn code(&self) -> i32 {
+        match self {
+            Failure::Usage(_) => EXIT_USAGE,
+            Failure::Invalid(_) => EXIT_INVALID,
+            Failure::Error(_) => EXIT_ERROR,
+            Failure::Service(_) => EXIT_SERVICE
+        }
+    }
+
+    fn messages(&self) -> Vec<String> {
+        match self {
+            Failure::Usage(message) => vec![message.clone(), String::from(USAGE)],
+            Failure::Invalid(problems) => problems.clone(),
+            Failure::Error(message) | Failure::Service(message) => vec![message.clone()]
+        }
+    }
+}
+
+/// What a command printed: plain text lines, or the same as JSON with `--json`.
+struct Report {
+    lines: Vec<String>,
+    json: Value
+}
+
+/// Runs `mediatimer <command> ...` and returns the exit code. `args` starts with the command.
+pub fn run(args: &[String]) -> i32 {
+    let json = args.iter().any(|arg| arg == "--json");
+    let args: Vec<&str> = args.iter().map(String::as_str).filter(|arg| *arg != "--json").collect();
+    let mut input = io::stdin().lock();
+
+    let result = take_pin(args).and_then(|(pin, args)| {
+        if changes_config(&args) {
+            unlock(&PinStore::default(), pin, &mut input)?;
+        }
+        match args.as_slice() {
+            ["show"] => show(),
+            ["set", options @ ..] => set(options),
+            ["schedule", "add", day, times] => schedule_add(day, times),
+            ["schedule", "import", file] => schedule_import(Path::new(file)),
+            ["validate"] => validate_saved(),
+            ["apply"] => apply(),
+            ["pin", "set"] => pin_set(&mut input),
+            ["pin", "clear"] => pin_clear(),
+            ["pin", "status"] => pin_status(),
+            ["pin", "recovery", folder] => pin_recovery(Path::new(folder), None),
+            ["pin", "recovery", folder, pin_file] => pin_recovery(Path::new(folder), Some(Path::new(pin_file))),
+            _ => Err(Failure::Usage(format!("Unknown command: mediatimer {}", args.join(" "))))
+        }
+    });
+
+    match result {
+        Ok(report) => {
+            if json {
+                println!("{}", report.json);
+            } else {
+                for line in report.lines {
+                    println!("{}", line);
+                }
+            }
+            EXIT_OK
+        },
+        Err(failure) => {
+            if json {
+                println!("{}", json!({ "ok": false, "errors": failure.messages() }));
+            } else {
+                for message in failure.messages() {
+                    eprintln!("{}", message);
+                }
+            }
+            failure.code()
+        }
+    }
+}
+
+/// The task as JSON, using the names of the MT_* keys.
+fn task_json(task: &Task) -> Value {
+    let timings: serde_json::Map<String, Value> = DAYS.iter().enumerate()
+        .map(|(day, name)| {
+            let times: Vec<String> = day_schedule(&task.timings, day)
+                .map(|schedule| schedule.iter().map(|(start, end)| format!("{}-{}", start, end)).collect())
+                .unwrap_or_default();
+            (name.to_string(), json!(times))
+        })
+        .collect();
+    json!({
+        "proctype": task.proc_type.to_string().to_lowercase(),
+        "file": task.file.display().to_string(),
+        "uuid": task.uuid,
+        "url": task.url,
+        "autoloop": task.auto_loop == Autoloop::Yes,
+        "schedule": task.advanced_schedule == AdvancedSchedule::Yes,
+        "timings": timings,
+        "slide_delay": task.slide_delay,
+        "slide_order": task.slideshow.order.to_string().to_lowercase(),
+        "slide_recursive": task.slideshow.recursive,
+        "slide_skip": task.slideshow.skip_non_images,
+        "player": task.media_player.to_string().to_lowercase()
+    })
+}
+
+fn summary_lines(task: &Task) -> Vec<String> {
+    let rows = summary(task);
+    let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    rows.iter().map(|(label, value)| format!("{:width$}  {}", label, value, width = width)).collect()
+}
+
+fn load() -> Result<Task, Failure> {
+    load_task(&env_file_path()).map_err(|e| Failure::Error(format!("Could not read the configuration: {}", e)))
+}
+
+/// Checks the task and saves it.
+fn save(task: Task) -> Result<Report, Failure> {
+    let problems = validate(&task, &read_model(), &SystemRunner, &mounted_drives());
+    if !problems.is_empty() {
+        return Err(Failure::Invalid(problems));
+    }
+    let report = Report {
+        lines: std::iter::once(String::from("Saved"))
+            .chain(summary_lines(&task))
+            .collect(),
+        json: json!({ "ok": true, "config": task_json(&task) })
+    };
+    write_task(task).map_err(|e| Failure::Error(format!("Could not save the configuration: {}", e)))?;
+    logi!("Configuration saved from the command line");
+    Ok(report)
+}
+
+fn mounted_drives() -> Vec<Drive> {
+    identify_mounted_drives(&SystemRunner).unwrap_or_else(|e| {
+        logw!("No storage devices identified, Error: {}", e);
+        Vec::new()
+    })
+}
+
+fn show() -> Result<Report, Failure> {
+    let task = load()?;
+    Ok(Report {
+        lines: summary_lines(&task),
+        json: json!({ "ok": true, "config": task_json(&task) })
+    })
+}
+
+fn parse_proc_type(value: &str) -> Option<ProcType> {
+    match value.to_lowercase().as_str() {
+        "video" => Some(ProcType::Video),
+        "audio" => Some(ProcType::Audio),
+        "image" => Some(ProcType::Image),
+        "slideshow" => Some(ProcType::Slideshow),
+        "web" => Some(ProcType::Web),
+        "browser" => Some(ProcType::Browser),
+        "executable" => Some(ProcType::Executable),
+        _ => None
+    }
+}
+
+fn parse_player(value: &str) -> Option<MediaPlayer> {
+    match value.to_lowercase().as_str() {
+        "ffplay" => Some(MediaPlayer::Ffplay),
+        "mpv" => Some(MediaPlayer::Mpv),
+        "vlc" => Some(MediaPlayer::Vlc),
+        _ => None
+    }
+}
+
+/// Changes the task as the `set` options ask.
+fn apply_options(task: &mut Task, options: &[&str]) -> Result<(), Failure> {
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = |name: &str| options.next().copied()
+            .ok_or_else(|| Failure::Usage(format!("{} needs a value", name)));
+        match *option {
+            "--proctype" => {
+                let proc_type = value(option)?;
+                task.proc_type = parse_proc_type(proc_type)
+                    .ok_or_else(|| Failure::Usage(format!("Unknown type: {}", proc_type)))?;
+            },
+            "--file" => {
+                let file = value(option)?;
+                task.file = fs::canonicalize(file)
+                    .map_err(|e| Failure::Invalid(vec![format!("{} could not be found: {}", file, e)]))?;
+                task.uuid = fresh_uuid(std::mem::take(&mut task.uuid), &whoami::username(), &task.file)
+                    .map_err(|e| Failure::Error(format!("Could not identify the drive holding {}: {}", file, e)))?;
+            },
+            "--url" => task.url = value(option)?.trim().to_string(),
+            "--autoloop" => task.auto_loop = Autoloop::Yes,
+            "--no-autoloop" => task.auto_loop = Autoloop::No,
+            "--schedule" => task.advanced_schedule = AdvancedSchedule::Yes,
+            "--no-schedule" => task.advanced_schedule = AdvancedSchedule::No,
+            "--slide-delay" => {
+                let delay = value(option)?;
+                task.slide_delay = delay.parse::<u32>()
+                    .map_err(|_| Failure::Usage(format!("The slide delay must be a number of seconds: {}", delay)))?;
+            },
+            "--player" => {
+                let player = value(option)?;
+                task.media_player = parse_player(player)
+                    .ok_or_else(|| Failure::Usage(format!("Unknown player: {}", player)))?;
+            },
+            _ => return Err(Failure::Usage(format!("Unknown option: {}", option)))
+        }
+    }
+    Ok(())
+}
+
+fn set(options: &[&str]) -> Result<Report, Failure> {
+    if options.is_empty() {
+        return Err(Failure::Usage(String::from("Nothing to set")));
+    }
+    let mut task = load()?;
+    apply_options(&mut task, options)?;
+    save(task)
+}
+
+/// Accepts 10:00-12:00 as well as 10:00:00-12:00:00, the format the setup screens use.
+fn full_timing(times: &str) -> String {
+    times.trim()
+        .split('-')
+        .map(|time| if time.matches(':').count() == 1 { format!("{}:00", time) } else { time.to_string() })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Every problem with the timings, checked the same way as on the Timings screen.
+fn timing_problems(timings: &Timings) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (day, name) in DAYS.iter().enumerate() {
+        let Some(schedule) = day_schedule(timings, day) else {
+            continue;
+        };
+        let mut checked: Vec<(String, String)> = Vec::new();
+        for (start, end) in schedule {
+            let timing = full_timing(&format!("{}-{}", start, end));
+            if !timing_format_correct(&timing).unwrap_or(false) {
+                problems.push(format!("{} {}-{} must use the 24 hour clock, e.g. 09:00-17:00", name, start, end));
+                continue;
+            }
+            if !timing_no_clash(&timing, &checked).unwrap_or(false) {
+                problems.push(format!("{} {}-{} clashes with another timing", name, start, end));
+            }
+            if let Some((start, end)) = timing.split_once('-') {
+                checked.push((start.to_string(), end.to_string()));
+            }
+        }
+    }
+    problems
+}
+
+/// Every reason the task would not play, using the checks the setup screens make.
+pub(crate) fn validate(task: &Task, model: &Model, runner: &dyn CommandRunner, drives: &[Drive]) -> Vec<String> {
+    let mut problems = Vec::new();
+    if task.proc_type == ProcType::Web {
+        if !url_format_correct(&task.url).unwrap_or(false) {
+            problems.push(format!("\"{}\" is not a valid web address", task.url));
+        }
+    } else if task.file.as_os_str().is_empty() {
+        problems.push(String::from("No file has been chosen"));
+    } else {
+        match resolve_media(&task.file, &task.uuid, &media_root(), drives) {
+            Resolution::Missing(path) => problems.push(format!("{} could not be found on any connected drive", path.display())),
+            resolution => {
+                let path = resolution.path();
+                match task.proc_type {
+                    ProcType::Slideshow if !path.is_dir() => problems.push(format!("{} is not a folder", path.display())),
+                    ProcType::Video | ProcType::Audio | ProcType::Image => match probe(runner, path) {
+                        Ok(info) => problems.extend(check_media(&info, &task.proc_type, model)),
+                        Err(_) => problems.push(format!("{} could not be read. Please check file is a supported {} format.", path.display(), task.proc_type.as_ref().to_lowercase()))
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    if task.proc_type == ProcType::Slideshow && !(MIN_SLIDE_DELAY..=MAX_SLIDE_DELAY).contains(&task.slide_delay) {
+        problems.push(format!("The slide delay must be between {} and {} seconds", MIN_SLIDE_DELAY, MAX_SLIDE_DELAY));
+    }
+    if task.advanced_schedule == AdvancedSchedule::Yes {
+        problems.extend(timing_problems(&task.timings));
+    }
+    problems
+}
+
+/// A week with nothing scheduled.
+fn empty_week() -> Timings {
+    vec![
+        Weekday::Monday(Vec::new()),
+        Weekday::Tuesday(Vec::new()),
+        Weekday::Wednesday(Vec::new()),
+        Weekday::Thursday(Vec::new()),
+        Weekday::Friday(Vec::new()),
+        Weekday::Saturday(Vec::new()),
+        Weekday::Sunday(Vec::new()),
+    ]
+}
+
+fn day_schedule_mut(timings: &mut Timings, day: usize) -> Option<&mut Schedule> {
+    timings.iter_mut().find_map(|weekday| match (weekday, day) {
+        (Weekday::Monday(schedule), 0) => Some(schedule),
+        (Weekday::Tuesday(schedule), 1) => Some(schedule),
+        (Weekday::Wednesday(schedule), 2) => Some(schedule),
+        (Weekday::Thursday(schedule), 3) => Some(schedule),
+        (Weekday::Friday(schedule), 4) => Some(schedule),
+        (Weekday::Saturday(schedule), 5) => Some(schedule),
+        (Weekday::Sunday(schedule), 6) => Some(schedule),
+        _ => None
+    })
+}
+
+/// Adds a timing to a day and turns the schedule on.
+fn add_timing(task: &mut Task, day: &str, times: &str) -> Result<(), Failure> {
+    let index = DAYS.iter().position(|name| name.eq_ignore_ascii_case(day))
+        .ok_or_else(|| Failure::Usage(format!("Unknown day: {}", day)))?;
+    let timing = full_timing(times);
+    if !timing_format_correct(&timing).unwrap_or(false) {
+        return Err(Failure::Invalid(vec![format!("{} must use the 24 hour clock, e.g. 09:00-17:00", times)]));
+    }
+    if task.timings.is_empty() {
+        task.timings = empty_week();
+    }
+    let schedule = day_schedule_mut(&mut task.timings, index)
+        .ok_or_else(|| Failure::Error(format!("The configuration has no {} schedule", DAYS[index])))?;
+    let existing: Vec<(String, String)> = schedule.iter()
+        .filter_map(|(start, end)| full_timing(&format!("{}-{}", start, end))
+            .split_once('-')
+            .map(|(start, end)| (start.to_string(), end.to_string())))
+        .collect();
+    if !timing_no_clash(&timing, &existing).unwrap_or(false) {
+        return Err(Failure::Invalid(vec![format!("{} clashes with another timing on {}", times, DAYS[index])]));
+    }
+    let (start, end) = timing.split_once('-').expect("checked by timing_format_correct");
+    schedule.push((start.to_string(), end.to_string()));
+    task.advanced_schedule = AdvancedSchedule::Yes;
+    Ok(())
+}
+
+fn schedule_add(day: &str, times: &str) -> Result<Report, Failure> {
+    let mut task = load()?;
+    add_timing(&mut task, day, times)?;
+    save(task)
+}
+
+fn schedule_import(file: &Path) -> Result<Report, Failure> {
+    if !file.is_file() {
+        return Err(Failure::Error(format!("{} could not be found", file.display())));
+    }
+    let timings = import_schedule(PathBuf::from(file))
+        .map_err(|e| Failure::Invalid(vec![format!("{} could not be read: {}", file.display(), e)]))?;
+    let mut task = load()?;
+    task.timings = timings;
+    task.advanced_schedule = AdvancedSchedule::Yes;
+    save(task)
+}
+
+fn validate_saved() -> Result<Report, Failure> {
+    let task = load()?;
+    let problems = validate(&task, &read_model(), &SystemRunner, &mounted_drives());
+    if !problems.is_empty() {
+        return Err(Failure::Invalid(problems));
+    }
+    Ok(Report {
+        lines: vec![String::from("The configuration is valid")],
+        json: json!({ "ok": true, "errors": [] })
+    })
+}
+
+/// Checks the saved configuration and restarts playback with it.
+fn apply() -> Result<Report, Failure> {
+    validate_saved()?;
+    let output = SystemRunner.output(Command::new("systemctl")
+        .arg("--user")
+        .arg("restart")
+        .arg("mediatimer_init.service"))
+        .map_err(|e| Failure::Service(format!("Could not run systemctl: {}", e)))?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        loge!("Could not restart mediatimer_init: {}", reason);
+        return Err(Failure::Service(format!("Could not restart mediatimer_init: {}", reason)));
+    }
+    logi!("Configuration applied from the command line");
+    Ok(Report {
+        lines: vec![String::from("Applied")],
+        json: json!({ "ok": true })
+    })
+}
+
+// the PIN given with --pin, and the other arguments
+fn take_pin(args: Vec<&str>) -> Result<(Option<&str>, Vec<&str>), Failure> {
+    let Some(i) = args.iter().position(|arg| *arg == "--pin") else {
+        return Ok((None, args));
+    };
+    let pin = *args.get(i + 1).ok_or_else(|| Failure::Usage(String::from("--pin needs a value")))?;
+    let rest = args[..i].iter().chain(&args[i + 2..]).copied().collect();
+    Ok((Some(pin), rest))
+}
+
+// whether the command changes the configuration or the PIN, so needs the current PIN
+fn changes_config(args: &[&str]) -> bool {
+    matches!(args, ["set", ..] | ["schedule", "add" | "import", ..] | ["apply"] | ["pin", "set" | "clear"])
+}
+
+fn read_pin(input: &mut impl BufRead) -> Result<String, Failure> {
+    let mut pin = String::new();
+    input.read_line(&mut pin)
+        .map_err(|e| Failure::Error(format!("Could not read the PIN: {}", e)))?;
+    Ok(pin.trim().to_string())
+}
+
+/// Checks the current PIN, if one is set, from `pin` or else the next line of `input`. Wrong PINs
+/// count towards the same lockout as the lock screen.
+fn unlock(store: &PinStore, pin: Option<&str>, input: &mut impl BufRead) -> Result<(), Failure> {
+    if !store.is_set() {
+        return Ok(());
+    }
+    let pin = match pin {
+        Some(pin) => pin.to_string(),
+        None => read_pin(input)?
+    };
+    if pin.is_empty() {
+        return Err(Failure::Error(String::from("A PIN is set: give the current PIN with --pin PIN or on the first line of stdin")));
+    }
+    match store.check(&pin, now()) {
+        Ok(Attempt::Unlocked) => Ok(()),
+        Ok(Attempt::Wrong { left }) => {
+            logw!("Wrong PIN given on the command line, {} attempts left", left);
+            Err(Failure::Error(format!("Wrong PIN, {} attempts left", left)))
+        },
+        Ok(Attempt::LockedOut { seconds }) => {
+            logw!("Command line refused, setup is locked for {} seconds", seconds);
+            Err(Failure::Error(format!("Locked for {} more seconds after too many wrong PINs", seconds)))
+        },
+        Err(e) => Err(Failure::Error(format!("Could not check the PIN: {}", e)))
+    }
+}
+
+/// Sets the PIN of the setup screens, read from the next line of stdin so that it is not left
+/// in the shell history. The current PIN, if needed and not given with --pin, comes first.
+fn pin_set(input: &mut impl BufRead) -> Result<Report, Failure> {
+    let pin = read_pin(input)?;
+    let pin = pin.as_str();
+    if !valid_pin(pin) {
+        return Err(Failure::Invalid(vec![String::from("A PIN must be 4 to 12 digits")]));
+    }
+    PinStore::default().set(pin)
+        .map_err(|e| Failure::Error(format!("Could not save the PIN: {}", e)))?;
+    logi!("PIN set from the command line");
+    Ok(Report {
+        lines: vec![String::from("PIN set")],
+        json: json!({ "ok": true })
+    })
+}
+
+fn pin_clear() -> Result<Report, Failure> {
+    PinStore::default().clear()
+        .map_err(|e| Failure::Error(format!("Could not remove the PIN: {}", e)))?;
+    logi!("PIN removed from the command line");
+    Ok(Report {
+        lines: vec![String::from("PIN removed")],
+        json: json!({ "ok": true })
+    })
+}
+
+fn pin_status() -> Result<Report, Failure> {
+    let store = PinStore::default();
+    let locked_for = store.locked_for(now());
+    let mut lines = vec![String::from(if store.is_set() { "A PIN is set" } else { "No PIN is set" })];
+    if let Some(seconds) = locked_for {
+        lines.push(format!("Locked for {} more seconds after too many wrong PINs", seconds));
+    }
+    Ok(Report {
+        lines,
+        json: json!({ "ok": true, "set": store.is_set(), "locked_for": locked_for.unwrap_or(0) })
+    })
+}
+
+/// Writes a file that removes the PIN when its drive is plugged in and R is pressed on the lock
+/// screen. It is signed with the deployment key, like a provisioning file, and made for one PIN:
+/// this device's, or the one stored in `pin_file`.
+fn pin_recovery(folder: &Path, pin_file: Option<&Path>) -> Result<Report, Failure> {
+    let key = read_key()
+        .ok_or_else(|| Failure::Error(format!("No deployment key found at {}", key_path().display())))?;
+    let message = match pin_file {
+        Some(pin_file) => fs::read_to_string(pin_file)
+            .map(|stored| recovery_message(&stored))
+            .map_err(|e| Failure::Error(format!("Could not read {}: {}", pin_file.display(), e)))?,
+        None => PinStore::default().recovery_message()
+            .ok_or_else(|| Failure::Error(String::from("No PIN is set, so there is nothing to recover")))?
+    };
+    let file = write_recovery(folder, &key, &message)
+        .map_err(|e| Failure::Error(format!("Could not write the recovery file: {}", e)))?;
+    Ok(Report {
+        lines: vec![format!("Recovery file written to {}. It removes the current PIN once.", file.display())],
+        json: json!({ "ok": true, "file": file.display().to_string() })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::task;
+    use crate::parse_task;
+    use crate::runner::fake::FakeRunner;
+    use crate::lock::MAX_ATTEMPTS;
+
+    #[test]
+    fn check_apply_options() {
+        let mut web = task(&[("MT_PROCTYPE", "video")]);
+        apply_options(&mut web, &["--proctype", "Web", "--url", " example.com ", "--no-autoloop", "--player", "mpv"]).unwrap();
+        assert_eq!(web.proc_type, ProcType::Web);
+        assert_eq!(web.url, "example.com");
+        assert_eq!(web.auto_loop, Autoloop::No);
+        assert_eq!(web.media_player, MediaPlayer::Mpv);
+
+        assert!(matches!(apply_options(&mut web, &["--proctype", "film"]), Err(Failure::Usage(_))));
+        assert!(matches!(apply_options(&mut web, &["--slide-delay"]), Err(Failure::Usage(_))));
+        assert!(matches!(apply_options(&mut web, &["--loop"]), Err(Failure::Usage(_))));
+        assert_eq!(Failure::Usage(String::new()).code(), EXIT_USAGE);
+    }
+
+    #[test]
+    fn check_add_timing() {
+        let mut unscheduled = task(&[]);
+        add_timing(&mut unscheduled, "Monday", "10:00-12:00").unwrap();
+        assert_eq!(unscheduled.advanced_schedule, AdvancedSchedule::Yes);
+        assert_eq!(day_schedule(&unscheduled.timings, 0), Some(&vec![(String::from("10:00:00"), String::from("12:00:00"))]));
+
+        assert!(matches!(add_timing(&mut unscheduled, "monday", "11:00-13:00"), Err(Failure::Invalid(_))));
+        assert!(matches!(add_timing(&mut unscheduled, "monday", "25:00-26:00"), Err(Failure::Invalid(_))));
+        assert!(matches!(add_timing(&mut unscheduled, "someday", "10:00-12:00"), Err(Failure::Usage(_))));
+        add_timing(&mut unscheduled, "monday", "13:00:00-14:00:00").unwrap();
+        assert_eq!(day_schedule(&unscheduled.timings, 0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn check_validate() {
+        let web = task(&[("MT_PROCTYPE", "web"), ("MT_URL", "not a url")]);
+        assert_eq!(validate(&web, &Model::Pro, &FakeRunner::new(), &[]), vec!["\"not a url\" is not a valid web address"]);
+
+        let web = task(&[
+            ("MT_PROCTYPE", "web"),
+            ("MT_URL", "https://example.com"),
+            ("MT_SCHEDULE", "true"),
+            ("MT_MONDAY", "09:00-12:00,11:00-13:00"),
+            ("MT_TUESDAY", "9am-5pm"),
+        ]);
+        assert_eq!(validate(&web, &Model::Pro, &FakeRunner::new(), &[]), vec![
+            "monday 11:00-13:00 clashes with another timing",
+            "tuesday 9am-5pm must use the 24 hour clock, e.g. 09:00-17:00"
+        ]);
+
+        let video = task(&[("MT_PROCTYPE", "video"), ("MT_FILE", "/nonexistent/intro.mp4")]);
+        assert_eq!(validate(&video, &Model::Pro, &FakeRunner::new(), &[]), vec!["/nonexistent/intro.mp4 could not be found on any connected drive"]);
+    }
+
+    #[test]
+    fn check_task_json() {
+        let json = task_json(&task(&[("MT_PROCTYPE", "audio"), ("MT_FILE", "/home/pi/media/a.mp3"), ("MT_SCHEDULE", "true"), ("MT_FRIDAY", "18:00:00-19:30:00")]));
+        assert_eq!(json["proctype"], "audio");
+        assert_eq!(json["schedule"], true);
+        assert_eq!(json["timings"]["friday"], json!(["18:00:00-19:30:00"]));
+        assert_eq!(json["timings"]["monday"], json!([]));
+    }
+
+    #[test]
+    fn check_take_pin() {
+        assert_eq!(take_pin(vec!["apply"]), Ok((None, vec!["apply"])));
+        assert_eq!(take_pin(vec!["set", "--pin", "2468", "--url", "example.com"]), Ok((Some("2468"), vec!["set", "--url", "example.com"])));
+        assert!(matches!(take_pin(vec!["apply", "--pin"]), Err(Failure::Usage(_))));
+        assert!(changes_config(&["pin", "clear"]));
+        assert!(changes_config(&["schedule", "import", "times.csv"]));
+        assert!(!changes_config(&["pin", "status"]));
+        assert!(!changes_config(&["show"]));
+    }
+
+    #[test]
+    fn check_unlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PinStore::in_dir(dir.path());
+        // nothing is asked for without a PIN
+        assert_eq!(unlock(&store, None, &mut io::empty()), Ok(()));
+
+        store.set("2468").unwrap();
+        assert!(matches!(unlock(&store, None, &mut io::empty()), Err(Failure::Error(_))));
+        assert_eq!(unlock(&store, Some("2468"), &mut io::empty()), Ok(()));
+        assert_eq!(unlock(&store, None, &mut "2468\n1357\n".as_bytes()), Ok(()));
+        assert_eq!(unlock(&store, Some("1357"), &mut io::empty()), Err(Failure::Error(format!("Wrong PIN, {} attempts left", MAX_ATTEMPTS - 1))));
+
+        // the current PIN comes before the new one
+        let mut input = "2468\n1357\n".as_bytes();
+        unlock(&store, None, &mut input).unwrap();
+        assert_eq!(read_pin(&mut input), Ok(String::from("1357")));
+    }
+}
@@ -0,0 +1,411 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, Padding, Paragraph,
+        Widget, Wrap
+    },
+    DefaultTerminal,
+};
+use std::{
+    error::Error,
+    process::Command,
+    time::{
+        Duration,
+        Instant
+    }
+};
+use log::error;
+use crate::loge;
+use chrono::{
+    DateTime,
+    Datelike,
+    Local,
+    Timelike
+};
+
+use crate::styles::{
+    theme,
+    theme_name,
+    save_theme
+};
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::i18n::{
+    weekday_name,
+    yes_no
+};
+
+use crate::{
+    Task,
+    Model,
+    Timings,
+    AdvancedSchedule
+};
+use crate::mount::{
+    Drive,
+    Resolution
+};
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
+};
+use crate::review::summary;
+use crate::schedule::active::{
+    active_window,
+    next_change
+};
+use crate::status::{
+    PlaybackState,
+    Status,
+    read_status
+};
+
+const SERVICE: &str = "mediatimer_init.service";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+// the supervisor writes a heartbeat every five seconds
+const STALE_AFTER: i64 = 15;
+
+/// The state systemd reports for the playback service, e.g. "active" or "inactive".
+pub fn service_state(runner: &dyn CommandRunner) -> String {
+    // is-active exits non-zero for anything but active, but still prints the state
+    match runner.output(Command::new("systemctl").args(["--user", "is-active", SERVICE])) {
+        Ok(output) => {
+            let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if state.is_empty() { String::from("unknown") } else { state }
+        },
+        Err(_) => String::from("unknown")
+    }
+}
+
+fn format_time(seconds: u32) -> String {
+    format!("{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)
+}
+
+/// Whether content should be playing on `day` (0 = Monday) at `seconds` since midnight, and when
+/// that next changes. `timings` is None when there is no advanced schedule.
+pub(crate) fn describe_schedule(timings: Option<&Timings>, day: usize, seconds: u32) -> (bool, String) {
+    let Some(timings) = timings else {
+        return (true, tr!("review.always").to_string());
+    };
+    let playing = active_window(timings, day, seconds).is_some();
+    let next = match (next_change(timings, day, seconds), playing) {
+        (None, _) => tr!("dashboard.nothing_scheduled").to_string(),
+        (Some((next_day, at)), true) if next_day == day && at > seconds => tr!("dashboard.stops_today", time = format_time(at)),
+        (Some((next_day, at)), false) if next_day == day && at > seconds => tr!("dashboard.starts_today", time = format_time(at)),
+        (Some((next_day, at)), true) if next_day == (day + 1) % 7 => tr!("dashboard.stops_tomorrow", time = format_time(at)),
+        (Some((next_day, at)), false) if next_day == (day + 1) % 7 => tr!("dashboard.starts_tomorrow", time = format_time(at)),
+        (Some((next_day, at)), true) if next_day == day => tr!("dashboard.stops_next", day = weekday_name(next_day), time = format_time(at)),
+        (Some((next_day, at)), false) if next_day == day => tr!("dashboard.starts_next", day = weekday_name(next_day), time = format_time(at)),
+        (Some((next_day, at)), true) => tr!("dashboard.stops_on", day = weekday_name(next_day), time = format_time(at)),
+        (Some((next_day, at)), false) => tr!("dashboard.starts_on", day = weekday_name(next_day), time = format_time(at))
+    };
+    (playing, next)
+}
+
+/// What `mediatimer run` last reported, or that it has gone quiet.
+pub(crate) fn describe_playback(status: Option<&Status>, now: DateTime<Local>) -> String {
+    let Some(status) = status else {
+        return tr!("dashboard.never_played").to_string();
+    };
+    let updated = DateTime::parse_from_rfc3339(&status.updated).ok();
+    if updated.is_none_or(|updated| (now.fixed_offset() - updated).num_seconds() > STALE_AFTER) {
+        return tr!("dashboard.not_running").to_string();
+    }
+    let mut text = match status.state {
+        PlaybackState::Playing => tr!("dashboard.playing", source = status.source, player = status.player),
+        PlaybackState::Idle => tr!("dashboard.idle").to_string(),
+        PlaybackState::Finished => tr!("dashboard.finished").to_string(),
+        PlaybackState::Restarting => tr!("dashboard.restarting").to_string()
+    };
+    if status.restarts > 0 {
+        text.push_str(&tr!("dashboard.restarts", count = status.restarts));
+    }
+    if let Some(problem) = status.last_problem.as_ref() {
+        text.push_str(&tr!("dashboard.last_problem", problem = problem));
+    }
+    text
+}
+
+/// Shows what is playing and what is configured without changing anything. Playback carries on
+/// until the user chooses to edit the configuration.
+pub struct DashboardWidget {
+    should_exit: bool,
+    edit: bool,
+    model: Model,
+    // None when nothing has been set up yet
+    summary: Option<Vec<(String, String)>>,
+    timings: Option<Timings>,
+    resolution: Option<Resolution>,
+    drives: Vec<Drive>,
+    service: String,
+    status: Option<Status>,
+    refreshed: Option<Instant>,
+    help: bool
+}
+
+impl DashboardWidget {
+    pub(crate) fn new(model: Model, saved: Option<&Task>, resolution: Option<Resolution>, drives: Vec<Drive>) -> Self {
+        Self {
+            should_exit: false,
+            edit: false,
+            model,
+            summary: saved.map(summary),
+            timings: saved
+                .filter(|task| task.advanced_schedule == AdvancedSchedule::Yes)
+                .map(|task| task.timings.clone()),
+            resolution,
+            drives,
+            service: String::new(),
+            status: None,
+            refreshed: None,
+            help: false
+        }
+    }
+
+    /// Returns true when the user chose to edit the configuration.
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<bool, Box< dyn Error>> {
+        while !self.should_exit {
+            if self.refreshed.is_none_or(|refreshed| refreshed.elapsed() >= REFRESH_INTERVAL) {
+                self.service = service_state(&SystemRunner);
+                self.status = read_status();
+                self.refreshed = Some(Instant::now());
+            }
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            // wake up now and then to keep the clock and status current
+            if event::poll(Duration::from_millis(250))? && let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            }
+        }
+        Ok(self.edit)
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::default()
+            .bind(&[KeyCode::Enter, KeyCode::Char('e')], Action::Edit)
+            .bind(&[KeyCode::Char('t')], Action::Theme)
+            .bind(&[KeyCode::Char('q'), KeyCode::Esc], Action::Leave)
+            .bind(HELP_KEYS, Action::Help)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match DashboardWidget::keymap().action(key.code) {
+            Some(Action::Leave) => self.should_exit = true,
+            Some(Action::Edit) => {
+                self.edit = true;
+                self.should_exit = true;
+            },
+            Some(Action::Help) => self.help = true,
+            // cycles through the built in themes, e.g. high contrast for operators with low vision
+            Some(Action::Theme) => {
+                if let Err(e) = save_theme(theme_name().next()) {
+                    loge!("Could not save the theme: {}", e);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // rendering logic
+    fn render_header(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(vec![
+            "Media Timer ".into(),
+            self.model.as_ref().into(),
+        ]);
+        Paragraph::new(title)
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("dashboard.footer"))
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn block(title: &str) -> Block<'_> {
+        Block::new()
+            .title(Line::raw(title).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1))
+    }
+
+    fn render_now(&self, area: Rect, buf: &mut Buffer) {
+        let now = Local::now();
+        let day = now.weekday().num_days_from_monday() as usize;
+        let mut rows = vec![
+            (tr!("dashboard.time"), format!("{} {}", weekday_name(day), now.format("%H:%M"))),
+            (tr!("dashboard.service"), self.service.clone()),
+            (tr!("dashboard.playback"), describe_playback(self.status.as_ref(), now)),
+        ];
+        if self.summary.is_some() {
+            let (playing, next) = describe_schedule(self.timings.as_ref(), day, now.num_seconds_from_midnight());
+            rows.push((tr!("dashboard.should_play"), yes_no(playing).to_string()));
+            rows.push((tr!("dashboard.next"), next));
+        }
+        let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        let text: Vec<Line> = rows.iter()
+            .map(|(label, value)| Line::from(format!("{:width$}  {}", label, value, width = width)))
+            .collect();
+
+        Paragraph::new(text)
+            .block(DashboardWidget::block(tr!("dashboard.now_title")))
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn render_config(&self, area: Rect, buf: &mut Buffer) {
+        let text: Vec<Line> = match &self.summary {
+            None => vec![Line::from(tr!("dashboard.not_set_up"))],
+            Some(rows) => {
+                let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+                rows.iter()
+                    .map(|(label, value)| Line::from(format!("{:width$}  {}", label, value, width = width)))
+                    .collect()
+            }
+        };
+
+        Paragraph::new(text)
+            .block(DashboardWidget::block(tr!("dashboard.config_title")))
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn render_drives(&self, area: Rect, buf: &mut Buffer) {
+        let mut text = match &self.resolution {
+            Some(Resolution::Found(_)) => vec![Line::from(tr!("dashboard.media_found"))],
+            Some(Resolution::Relocated { to, .. }) => vec![Line::from(tr!("dashboard.media_relocated", path = to.display()))],
+            Some(Resolution::Missing(path)) => vec![Line::from(tr!("dashboard.media_missing", path = path.display())).bold()],
+            None => Vec::new()
+        };
+        if self.drives.is_empty() {
+            text.push(Line::from(tr!("dashboard.no_drives")));
+        }
+        text.extend(self.drives.iter().map(|drive| Line::from(drive.summary())));
+
+        Paragraph::new(text)
+            .block(DashboardWidget::block(tr!("dashboard.drives_title")))
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut DashboardWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [top_area, drives_area] = Layout::vertical([
+            Constraint::Fill(2),
+            Constraint::Fill(1)
+        ])
+        .areas(main_area);
+
+        let [now_area, config_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1)
+        ])
+        .areas(top_area);
+
+        self.render_header(header_area, buf);
+        DashboardWidget::render_footer(footer_area, buf);
+        self.render_now(now_area, buf);
+        self.render_config(config_area, buf);
+        self.render_drives(drives_area, buf);
+
+        if self.help {
+            DashboardWidget::keymap().render_help(tr!("help.dashboard"), area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Weekday;
+    use crate::runner::fake::FakeRunner;
+
+    fn status(updated: DateTime<Local>, state: PlaybackState) -> Status {
+        Status {
+            updated: updated.to_rfc3339(),
+            state,
+            proc_type: String::from("Video"),
+            player: String::from("ffplay"),
+            source: String::from("/home/pi/media/intro.mp4"),
+            pid: Some(1234),
+            running_secs: Some(60),
+            restarts: 0,
+            last_problem: None
+        }
+    }
+
+    #[test]
+    fn check_service_state() {
+        let runner = FakeRunner::new().respond_with("systemctl", 3, "inactive\n", "");
+        assert_eq!(service_state(&runner), "inactive");
+        assert_eq!(runner.calls(), vec!["systemctl --user is-active mediatimer_init.service"]);
+        // systemctl is not installed
+        assert_eq!(service_state(&FakeRunner::new()), "unknown");
+    }
+
+    #[test]
+    fn check_describe_schedule() {
+        assert_eq!(describe_schedule(None, 0, 0), (true, String::from("Plays all the time")));
+
+        let timings = vec![
+            Weekday::Monday(vec![(String::from("09:00"), String::from("17:00"))]),
+            Weekday::Wednesday(vec![(String::from("09:00:00"), String::from("12:30:00"))]),
+        ];
+        assert_eq!(describe_schedule(Some(&timings), 0, 8 * 3600), (false, String::from("Starts today at 09:00")));
+        assert_eq!(describe_schedule(Some(&timings), 0, 10 * 3600), (true, String::from("Stops today at 17:00")));
+        assert_eq!(describe_schedule(Some(&timings), 0, 18 * 3600), (false, String::from("Starts Wednesday at 09:00")));
+        assert_eq!(describe_schedule(Some(&timings), 2, 10 * 3600), (true, String::from("Stops today at 12:30")));
+        assert_eq!(describe_schedule(Some(&timings), 6, 0), (false, String::from("Starts tomorrow at 09:00")));
+
+        let monday_only = vec![Weekday::Monday(vec![(String::from("09:00"), String::from("17:00"))])];
+        assert_eq!(describe_schedule(Some(&monday_only), 0, 18 * 3600), (false, String::from("Starts next Monday at 09:00")));
+    }
+
+    #[test]
+    fn check_describe_playback() {
+        let now = Local::now();
+        assert_eq!(describe_playback(None, now), "Nothing has been played yet");
+        assert_eq!(describe_playback(Some(&status(now, PlaybackState::Playing)), now), "Playing /home/pi/media/intro.mp4 with ffplay");
+        // the supervisor stopped writing its heartbeat
+        let stale = status(now - chrono::Duration::seconds(60), PlaybackState::Playing);
+        assert_eq!(describe_playback(Some(&stale), now), "Not running");
+
+        let mut restarting = status(now, PlaybackState::Restarting);
+        restarting.restarts = 2;
+        restarting.last_problem = Some(String::from("exited with code 1"));
+        assert_eq!(describe_playback(Some(&restarting), now), "Restarting the player, restarted 2 times, last problem: exited with code 1");
+    }
+}
@@ -0,0 +1,239 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{
+        Stylize,
+    },
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, ListState, Padding, Paragraph,
+        StatefulWidget, Widget, Wrap, ListItem, List,
+        HighlightSpacing
+    },
+    DefaultTerminal,
+};
+use std::{
+    error::Error,
+    path::PathBuf
+};
+
+use crate::styles::theme;
+
+use crate::env_file_path;
+use crate::mount::Drive;
+use crate::eject::{
+    Ejected,
+    eject
+};
+use crate::runner::SystemRunner;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap
+};
+
+/// The media the saved configuration plays, if any.
+fn configured_media() -> Option<PathBuf> {
+    dotenvy::from_path_iter(env_file_path()).ok()?
+        .filter_map(Result::ok)
+        .find(|(key, _)| key == "MT_FILE")
+        .map(|(_, value)| PathBuf::from(value))
+        .filter(|file| !file.as_os_str().is_empty())
+}
+
+/// Lists the mounted drives so they can be ejected before they are pulled out.
+pub struct DrivesWidget {
+    should_exit: bool,
+    drives: Vec<Drive>,
+    state: ListState,
+    configured: Option<PathBuf>,
+    // the drive the saved configuration uses, waiting for a second E to confirm
+    confirm: Option<usize>,
+    message: String,
+    help: bool
+}
+
+impl DrivesWidget {
+    /// `selected` is the drive being browsed when the screen was opened.
+    pub fn new(drives: Vec<Drive>, selected: Option<usize>) -> Self {
+        let mut state = ListState::default();
+        state.select(selected.or(if drives.is_empty() { None } else { Some(0) }));
+        Self {
+            should_exit: false,
+            drives,
+            state,
+            configured: configured_media(),
+            confirm: None,
+            message: String::new(),
+            help: false
+        }
+    }
+
+    /// Returns the drives that are still mounted.
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Vec<Drive>, Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            };
+        }
+        Ok(self.drives)
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::list()
+            .bind(&[KeyCode::Char('e'), KeyCode::Enter], Action::Eject)
+            .bind(&[KeyCode::Char('q'), KeyCode::Esc], Action::Back)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match DrivesWidget::keymap().action(key.code) {
+            Some(Action::Back) => self.should_exit = true,
+            Some(Action::Next) => self.select(|state| state.select_next()),
+            Some(Action::Previous) => self.select(|state| state.select_previous()),
+            Some(Action::First) => self.select(|state| state.select_first()),
+            Some(Action::Last) => self.select(|state| state.select_last()),
+            Some(Action::Eject) => self.eject_selected(),
+            Some(Action::Help) => self.help = true,
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, change: impl FnOnce(&mut ListState)) {
+        change(&mut self.state);
+        self.confirm = None;
+        self.message.clear();
+    }
+
+    fn eject_selected(&mut self) {
+        let Some(index) = self.state.selected().filter(|i| *i < self.drives.len()) else {
+            return;
+        };
+        let drive = &self.drives[index];
+        let name = drive.label.clone().unwrap_or_else(|| drive.name.clone());
+
+        let used_by_config = self.configured.as_ref().is_some_and(|file| drive.contains(file));
+        if used_by_config && self.confirm != Some(index) {
+            self.confirm = Some(index);
+            self.message = tr!("drives.confirm", name = name);
+            return;
+        }
+        self.confirm = None;
+
+        match eject(&SystemRunner, drive) {
+            Ok(Ejected::PoweredOff) => self.message = tr!("drives.removable", name = name),
+            Ok(Ejected::Unmounted) => self.message = tr!("drives.unmounted", name = name),
+            Err(e) => {
+                self.message = e.to_string();
+                return;
+            }
+        }
+        self.drives.remove(index);
+        if self.drives.is_empty() {
+            self.state.select(None);
+        } else if index >= self.drives.len() {
+            self.state.select_last();
+        }
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("drives.header"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("drives.footer"))
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(tr!("drives.question")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        let items: Vec<ListItem> = self
+            .drives
+            .iter()
+            .enumerate()
+            .map(|(i, drive)| {
+                let color = theme().row_bg(i);
+                ListItem::new(Line::styled(drive.summary(), theme().text_fg)).bg(color)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme().selected)
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+
+    fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
+        let info = if !self.message.is_empty() {
+            self.message.clone()
+        } else if self.drives.is_empty() {
+            String::from(tr!("drives.none"))
+        } else {
+            String::from(tr!("drives.info"))
+        };
+
+        let block = Block::new()
+            .title(Line::raw(tr!("common.information")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(info)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut DrivesWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [list_area, item_area] = Layout::vertical([
+            Constraint::Fill(2),
+            Constraint::Fill(1)
+        ])
+        .areas(main_area);
+
+        DrivesWidget::render_header(header_area, buf);
+        DrivesWidget::render_footer(footer_area, buf);
+        self.render_list(list_area, buf);
+        self.render_selected_item(item_area, buf);
+
+        if self.help {
+            DrivesWidget::keymap().render_help(tr!("help.drives"), area, buf);
+        }
+    }
+}
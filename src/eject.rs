@@ -0,0 +1,139 @@
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    process::Command
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::mount::Drive;
+use crate::runner::CommandRunner;
+use crate::tr;
+
+/// How far ejecting a drive got.
+#[derive(Debug, PartialEq)]
+pub enum Ejected {
+    /// The drive is unmounted and switched off, so it can be pulled out.
+    PoweredOff,
+    /// The drive is unmounted but could not be switched off. Some card readers do not support it.
+    Unmounted
+}
+
+/// The programs with files open on the drive, e.g. "ffplay (1234)".
+pub fn drive_users(runner: &dyn CommandRunner, mountpoint: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    // fuser prints the pids on stdout and exits with 1 when nothing uses the mount
+    let output = runner.output(Command::new("fuser").arg("-m").arg(mountpoint))?;
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .filter_map(|pid| pid.trim_end_matches(|c: char| !c.is_ascii_digit()).parse::<u32>().ok())
+        .map(|pid| match fs::read_to_string(format!("/proc/{}/comm", pid)) {
+            Ok(name) => format!("{} ({})", name.trim(), pid),
+            Err(_) => tr!("eject.process", pid = pid)
+        })
+        .collect())
+}
+
+/// Flushes, unmounts and powers off the drive. Refuses while any program has files open on it.
+pub fn eject(runner: &dyn CommandRunner, drive: &Drive) -> Result<Ejected, Box<dyn Error>> {
+    let name = drive.label.as_deref().unwrap_or(&drive.name);
+    let mountpoint = drive.mountpoint.as_ref()
+        .ok_or_else(|| tr!("eject.not_mounted", name = name))?;
+
+    let users = drive_users(runner, mountpoint)?;
+    if !users.is_empty() {
+        logw!("Not ejecting {}, in use by {}", name, users.join(", "));
+        return Err(tr!("eject.in_use", name = name, users = users.join(", ")).into());
+    }
+
+    runner.output(&mut Command::new("sync"))?;
+
+    let output = runner.output(Command::new("udisksctl").arg("unmount").arg("-b").arg(&drive.path))?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        loge!("Could not unmount {}: {}", drive.path.display(), reason);
+        return Err(tr!("eject.unmount_failed", name = name, reason = reason).into());
+    }
+
+    let output = runner.output(Command::new("udisksctl").arg("power-off").arg("-b").arg(&drive.path))?;
+    if output.status.success() {
+        logi!("Ejected {}", drive.path.display());
+        Ok(Ejected::PoweredOff)
+    } else {
+        logw!("Unmounted {} but could not power it off", drive.path.display());
+        Ok(Ejected::Unmounted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use crate::runner::fake::FakeRunner;
+    use crate::mount::tests::drive;
+
+    fn stick() -> Drive {
+        drive("1234-ABCD", Some("MY STICK"), Path::new("/media/pi/MY STICK"))
+    }
+
+    #[test]
+    fn check_eject() {
+        let runner = FakeRunner::new()
+            .respond_with("fuser", 1, "", "")
+            .respond("sync", "")
+            .respond("udisksctl", "Unmounted /dev/sdb1.\n")
+            .respond("udisksctl", "");
+
+        assert_eq!(eject(&runner, &stick()).unwrap(), Ejected::PoweredOff);
+        assert_eq!(runner.calls(), vec![
+            "fuser -m /media/pi/MY STICK",
+            "sync",
+            "udisksctl unmount -b /dev/sdb1",
+            "udisksctl power-off -b /dev/sdb1"
+        ]);
+    }
+
+    #[test]
+    fn check_eject_power_off_fails() {
+        let runner = FakeRunner::new()
+            .respond_with("fuser", 1, "", "")
+            .respond("sync", "")
+            .respond("udisksctl", "Unmounted /dev/sdb1.\n")
+            .respond_with("udisksctl", 1, "", "Error powering off drive");
+
+        assert_eq!(eject(&runner, &stick()).unwrap(), Ejected::Unmounted);
+    }
+
+    #[test]
+    fn check_eject_in_use() {
+        // pids that are not running on this machine are still reported
+        let runner = FakeRunner::new()
+            .respond("fuser", " 4194301c 4194302");
+
+        let error = eject(&runner, &stick()).unwrap_err().to_string();
+        assert_eq!(error, "MY STICK is still in use by process 4194301, process 4194302. Close it and try again.");
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn check_eject_unmount_fails() {
+        let runner = FakeRunner::new()
+            .respond_with("fuser", 1, "", "")
+            .respond("sync", "")
+            .respond_with("udisksctl", 1, "", "Error unmounting /dev/sdb1: target is busy");
+
+        let error = eject(&runner, &stick()).unwrap_err().to_string();
+        assert!(error.ends_with("target is busy"));
+    }
+}
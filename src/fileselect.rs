@@ -1,6 +1,6 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
     layout::{Constraint, Layout, Rect},
     style::{
         Modifier,
@@ -10,7 +10,7 @@ use ratatui::{
     text::Line,
     widgets::{
         Block, Borders, HighlightSpacing, Padding, Paragraph,
-        Widget, Wrap, Clear
+        Widget, Wrap, Clear, Gauge
     },
     DefaultTerminal,
 };
@@ -20,38 +20,95 @@ use std::{
     PathBuf,
     Path
     },
-    process::Command
+    time::Duration
 };
 
 use ratatui_explorer::{FileExplorer, Theme};
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    SELECTED_STYLE,
-    TEXT_FG_COLOR,
-    TEXT_DIR_COLOR,
-    FOOTER_STYLE
-};
+use crate::styles::theme;
 
 use crate::ProcType;
 use crate::areas;
-
-use regex::Regex;
+use crate::tr;
+use crate::mouse::{
+    Click,
+    Footer,
+    Targets,
+    explorer_rows,
+    explorer_state,
+    is_click
+};
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::proctype::proc_type_label;
+use crate::slideshow::{
+    ImageInventory,
+    InventoryJob,
+    InventoryState
+};
+use crate::probe::{
+    MediaInfo,
+    probe
+};
+use crate::capabilities::check_media;
+use crate::library::{
+    ImportJob,
+    ImportState
+};
+use crate::transcode::{
+    TranscodeJob,
+    TranscodeState,
+    output_path,
+    plan
+};
 
 use crate::Model;
+use crate::mount::Drive;
+use crate::hotplug::{
+    DriveMonitor,
+    Toast,
+    browsed_drive,
+    update_explorer
+};
+use crate::drives::DrivesWidget;
+use crate::runner::SystemRunner;
+use crate::wizard::{
+    Nav,
+    Step
+};
 type FileSelect = PathBuf;
 
 pub struct FileSelectWidget {
     model: Model,
     should_exit: bool,
+    nav: Nav,
     file_explorer: FileExplorer,
     selected_file: FileSelect,
     can_be_dir: bool,
     proc_type: ProcType,
-    mounted_drives: Vec<(PathBuf, String)>,
+    mounted_drives: Vec<Drive>,
     error: bool,
-    error_message: String
+    error_message: String,
+    // the folder waiting to be confirmed and what it contains
+    inventory: Option<(PathBuf, ImageInventory)>,
+    scan: Option<InventoryJob>,
+    // probe results for the highlighted file, kept until the highlight moves
+    details: Option<(PathBuf, Result<MediaInfo, String>)>,
+    // a rejected file that ffmpeg can convert for this model
+    convertible: Option<(PathBuf, MediaInfo)>,
+    transcode: Option<TranscodeJob>,
+    // set when the selection should be copied onto the device
+    import_requested: bool,
+    import: Option<ImportJob>,
+    progress: Option<f64>,
+    monitor: Option<DriveMonitor>,
+    toast: Option<Toast>,
+    drives_requested: bool,
+    targets: Targets,
+    help: bool
 }
 
 
@@ -61,79 +118,231 @@ impl Default for FileSelectWidget {
         Self {
             model: Model::Pro,
             should_exit: false,
+            nav: Nav::Next,
             file_explorer: FileExplorer::new().unwrap(),
             selected_file: Path::new("./").to_path_buf(),
             can_be_dir: false,
             proc_type: ProcType::Video,
             mounted_drives: Vec::new(),
             error: false,
-            error_message: String::from("")
+            error_message: String::from(""),
+            inventory: None,
+            scan: None,
+            details: None,
+            convertible: None,
+            transcode: None,
+            import_requested: false,
+            import: None,
+            progress: None,
+            monitor: None,
+            toast: None,
+            drives_requested: false,
+            targets: Targets::default(),
+            help: false
         }
     }
 }
 
 impl FileSelectWidget {
-    pub fn new(model: Model, file_path: PathBuf, can_be_dir: bool, proc_type: ProcType, mounted_drives: Vec<(PathBuf, String)>) -> Result<Self, Box<dyn Error>> {
+    pub fn new(model: Model, file_path: PathBuf, can_be_dir: bool, proc_type: ProcType, mounted_drives: Vec<Drive>) -> Result<Self, Box<dyn Error>> {
         let file_explorer = FileExplorer::new()?;
         Ok(
             Self {
                 model,
                 should_exit: false,
+                nav: Nav::Next,
                 file_explorer,
                 selected_file: file_path,
                 can_be_dir,
                 proc_type,
                 mounted_drives,
                 error: false,
-                error_message: String::from("")
+                error_message: String::from(""),
+                inventory: None,
+                scan: None,
+                details: None,
+                convertible: None,
+                transcode: None,
+                import_requested: false,
+                import: None,
+                progress: None,
+                monitor: Some(DriveMonitor::start()),
+                toast: None,
+                drives_requested: false,
+                targets: Targets::default(),
+                help: false
             }
         )
     }
 
-    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<FileSelect, Box< dyn Error>> {
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<FileSelect>, Box< dyn Error>> {
         let _file_explorer_init = self.setup_file_explorer()?;
         let _file_explorer_style_init = self.style_file_explorer();
 
         while !self.should_exit {
             terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
-            let event = event::read()?;
-            
-            self.file_explorer.handle(&event)?;
 
-            if let Event::Key(key) = event {
-                let _handle_result = self.handle_key(key)?;
+            if self.is_busy() {
+                // keep the progress bar moving while waiting for a key
+                if event::poll(Duration::from_millis(250))? && let Event::Key(key) = event::read()? {
+                    self.handle_key(key)?;
+                }
+                self.update_scan();
+                self.update_transcode();
+                self.update_import();
+                continue;
+            }
+
+            self.update_drives()?;
+            // wake up now and then to notice drives being plugged in
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let event = match event::read()? {
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                event => Some(event)
             };
 
+            if let Some(event) = event {
+                if !self.help {
+                    self.file_explorer.handle(&event)?;
+                }
+
+                if let Event::Key(key) = event {
+                    let _handle_result = self.handle_key(key)?;
+                };
+            }
+            if self.drives_requested {
+                self.drives_requested = false;
+                self.show_drives(terminal)?;
+            }
+            self.update_details();
+
+        }
+        Ok(self.nav.with(self.selected_file))
+    }
+
+    // the keys of whatever is showing: the progress, an error, the folder summary or the files
+    fn keymap(&self) -> Keymap {
+        if self.is_busy() {
+            Keymap::default()
+                .bind(&[KeyCode::Esc, KeyCode::Char('q')], Action::Cancel)
+                .bind(HELP_KEYS, Action::Help)
+        } else if self.inventory.is_some() {
+            Keymap::default()
+                .bind(&[KeyCode::Enter], Action::Choose)
+                .bind(&[KeyCode::Char('i')], Action::Import)
+                .bind(&[KeyCode::Esc], Action::Close)
+                .bind(HELP_KEYS, Action::Help)
+        } else if self.error {
+            let keymap = Keymap::default()
+                .bind(&[KeyCode::Enter], Action::Close)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::Char('q')], Action::Quit)
+                .bind(HELP_KEYS, Action::Help);
+            if self.convertible.is_some() {
+                keymap.bind(&[KeyCode::Char('c')], Action::Convert)
+            } else {
+                keymap
+            }
+        } else {
+            // the list keys include the help
+            let keymap = Keymap::list()
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace], Action::Parent)
+                .bind(&[KeyCode::Char('l'), KeyCode::Right], Action::Open)
+                .bind(&[KeyCode::Enter], Action::OpenOrChoose)
+                .bind(&[KeyCode::Char('e')], Action::Drives)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::Char('q')], Action::Quit);
+            if self.can_import() {
+                keymap.bind(&[KeyCode::Char('i')], Action::Import)
+            } else {
+                keymap
+            }
         }
-        Ok(self.selected_file)
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
         if key.kind != KeyEventKind::Press {
             return Ok(());
         }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return Ok(());
+        }
+        let action = self.keymap().action(key.code);
+        if action == Some(Action::Help) {
+            self.help = true;
+            return Ok(());
+        }
         let is_dir = self.file_explorer.current().is_dir();
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                self.should_exit = true;
+
+        // only cancelling is possible while a folder is checked or a file is converted or copied
+        if self.is_busy() {
+            if action == Some(Action::Cancel) {
+                if let Some(job) = self.scan.take() {
+                    job.cancel();
+                }
+                if let Some(job) = self.transcode.take() {
+                    job.cancel();
+                }
+                if let Some(job) = self.import.take() {
+                    job.cancel();
+                }
             }
-            KeyCode::Enter if !is_dir => {
-                if self.error {
+            return Ok(());
+        }
+
+        // the folder summary is open, ENTER uses the folder, I copies it and anything else goes back
+        if let Some((folder, _)) = self.inventory.take() {
+            match action {
+                Some(Action::Choose) => {
+                    self.import_requested = false;
+                    self.accept(folder);
+                },
+                Some(Action::Import) => {
+                    self.import_requested = true;
+                    self.accept(folder);
+                },
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match action {
+            Some(Action::Back) => self.leave(Nav::Back),
+            Some(Action::Quit) => self.leave(Nav::Cancel),
+            Some(Action::Drives) => self.drives_requested = true,
+            Some(Action::Convert) => {
+                if let Some((source, info)) = self.convertible.take() {
                     self.error = false;
-                } else if self.can_be_dir {
+                    self.start_transcode(source, info);
+                }
+            },
+            Some(Action::Close) => {
+                self.error = false;
+                self.convertible = None;
+            },
+            // the file explorer moves the highlight and opens folders, these are only missing from it
+            Some(Action::First) => self.file_explorer.set_selected_idx(0),
+            Some(Action::Last) => {
+                let last = self.file_explorer.files().len().saturating_sub(1);
+                self.file_explorer.set_selected_idx(last);
+            },
+            Some(Action::OpenOrChoose | Action::Import) if !is_dir => {
+                self.import_requested = action == Some(Action::Import);
+                if self.can_be_dir {
                     let mut current_path_buf = self.file_explorer.current().path().to_path_buf();
                     current_path_buf.pop();
-                    self.selected_file = current_path_buf;
-                    self.should_exit = true;
-                } else if self.proc_type == ProcType::Video {
-                    let video_compatible = self.video_compatible()?;
-                        if video_compatible {
-                            self.selected_file = self.file_explorer.current().path().to_path_buf();
-                            self.should_exit = true;
+                    self.check_folder(current_path_buf);
+                } else if self.shows_details() {
+                    let media_compatible = self.media_compatible();
+                        if media_compatible {
+                            self.accept(self.file_explorer.current().path().to_path_buf());
                         }
                     } else {
-                        self.selected_file = self.file_explorer.current().path().to_path_buf();
-                        self.should_exit = true;
+                        self.accept(self.file_explorer.current().path().to_path_buf());
                     }
             }
             _ => {}
@@ -141,68 +350,224 @@ impl FileSelectWidget {
         Ok(())
     }
         
-    // check for video size
-    fn video_compatible(&mut self) -> Result<bool, Box<dyn Error>> {
-        let file_path = self.file_explorer.current().path();
-        let vid_height = Command::new("ffprobe")
-            .arg("-loglevel")
-            .arg("error")
-            .arg("-show_streams")
-            .arg(&file_path)
-            .output()?;
-
-        let height_string = String::from_utf8_lossy(&vid_height.stdout);
-        let height_re = Regex::new(r"height=(?<height>\d+)")?;
-        if let Some(height_info) = height_re.captures(&height_string) {
-            let height_collected = height_info[1].to_string();
-            let height_int: u32 = height_collected.parse::<u32>()?;
-            let _result = match self.model {
-                Model::Eco => {
-                    if height_int > 1080 {
-                        self.error_message = format!("Video resolution is too high ({}). Export video as 1080p (HD) maximum.", height_int);
-                        self.error = true;
-                        return Ok(false);
-                    } else {
-                        return Ok(true);
-                    }
-                },
-                Model::Standard => {
-                    if height_int > 1440 {
-                        self.error_message = format!("Video resolution is too high ({}). Export video as 1440p (QHD) maximum.", height_int);
-                        self.error = true;
-                        return Ok(false);
-                    } else {
-                        return Ok(true);
-                    }
-                },
-                Model::Pro => {
-                    if height_int > 2160 {
-                        self.error_message = format!("Video resolution is too high ({}). Export video as 2160p (4K) maximum.", height_int);
-                        self.error = true;
-                        return Ok(false);
-                    } else {
-                        return Ok(true);
-                    }
+    // turns a click into the key it stands for, or highlights the file that was clicked
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Event> {
+        if self.help {
+            self.help = !is_click(&mouse);
+            return None;
+        }
+        // a click anywhere closes the error or uses the folder
+        if self.error || self.inventory.is_some() {
+            return is_click(&mouse).then(|| Event::Key(KeyEvent::from(KeyCode::Enter)));
+        }
+        let state = explorer_state(&self.file_explorer, self.targets.list.height);
+        let rows = self.file_explorer.files().len();
+        match self.targets.click(mouse, &state, rows, self.footer().as_ref())? {
+            Click::Key(code) => Some(Event::Key(KeyEvent::from(code))),
+            Click::Select(i) => {
+                self.file_explorer.set_selected_idx(i);
+                None
+            }
+        }
+    }
+
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
+    fn is_busy(&self) -> bool {
+        self.scan.is_some() || self.transcode.is_some() || self.import.is_some()
+    }
+
+    // executables and web pages may depend on files around them, so only media is copied
+    fn can_import(&self) -> bool {
+        matches!(self.proc_type, ProcType::Video | ProcType::Audio | ProcType::Image | ProcType::Slideshow)
+    }
+
+    /// Uses the selection, copying it onto the device first when that was asked for.
+    fn accept(&mut self, path: PathBuf) {
+        if !self.import_requested {
+            self.selected_file = path;
+            self.should_exit = true;
+            return;
+        }
+        match ImportJob::start(&path) {
+            Ok(job) => {
+                self.import = Some(job);
+                self.progress = Some(0.0);
+            },
+            Err(e) => {
+                self.error_message = tr!("fileselect.copy_failed", reason = e);
+                self.error = true;
+            }
+        }
+    }
+
+    fn update_import(&mut self) {
+        let Some(job) = self.import.as_mut() else {
+            return;
+        };
+        match job.poll() {
+            ImportState::Running(progress) => self.progress = Some(progress),
+            ImportState::Finished(target) => {
+                self.import = None;
+                self.selected_file = target;
+                self.should_exit = true;
+            },
+            ImportState::Failed(reason) => {
+                self.import = None;
+                self.error_message = tr!("fileselect.copy_failed", reason = reason);
+                self.error = true;
+            }
+        }
+    }
+
+    fn shows_details(&self) -> bool {
+        matches!(self.proc_type, ProcType::Video | ProcType::Audio | ProcType::Image)
+    }
+
+    fn update_details(&mut self) {
+        if !self.shows_details() {
+            return;
+        }
+        let current = self.file_explorer.current();
+        if current.is_dir() {
+            self.details = None;
+            return;
+        }
+        let path = current.path().to_path_buf();
+        if self.details.as_ref().is_some_and(|(probed, _)| *probed == path) {
+            return;
+        }
+        let info = probe(&SystemRunner, &path).map_err(|e| e.to_string());
+        self.details = Some((path, info));
+    }
+
+    // scan a slideshow folder and show what is in it before it is used
+    fn check_folder(&mut self, folder: PathBuf) {
+        self.scan = Some(InventoryJob::start(folder, &self.model));
+        self.progress = Some(0.0);
+    }
+
+    fn update_scan(&mut self) {
+        let Some(job) = self.scan.as_mut() else {
+            return;
+        };
+        match job.poll() {
+            InventoryState::Running(progress) => self.progress = Some(progress),
+            InventoryState::Finished(inventory) => {
+                let folder = job.folder().to_path_buf();
+                self.scan = None;
+                if inventory.images == 0 && inventory.subfolder_images == 0 {
+                    self.error_message = String::from(tr!("fileselect.no_images"));
+                    self.error = true;
+                } else {
+                    self.inventory = Some((folder, inventory));
                 }
-            };
-        } else {
-            self.error_message = format!("Video resolution could not be identified. Please check file is a supported video format.");
-            self.error = true;
+            },
+            InventoryState::Failed(reason) => {
+                self.scan = None;
+                self.error_message = tr!("fileselect.scan_failed", reason = reason);
+                self.error = true;
+            }
+        }
+    }
+
+    // check the file against what the model can play
+    fn media_compatible(&mut self) -> bool {
+        let path = self.file_explorer.current().path().to_path_buf();
+        let info = match self.details.as_ref() {
+            Some((probed, info)) if *probed == path => info.clone(),
+            _ => probe(&SystemRunner, &path).map_err(|e| e.to_string())
+        };
+        let mut reasons = match info.as_ref() {
+            Ok(info) => check_media(info, &self.proc_type, &self.model),
+            Err(_) => vec![tr!("fileselect.unreadable", path = path.display(), kind = proc_type_label(&self.proc_type).to_lowercase())]
+        };
+        if reasons.is_empty() {
+            return true;
+        }
+        if let Ok(info) = info && matches!(self.proc_type, ProcType::Video | ProcType::Audio) {
+            reasons.push(String::new());
+            reasons.push(tr!("fileselect.convert_hint", model = self.model));
+            self.convertible = Some((path, info));
+        }
+        self.error_message = reasons.join("\n");
+        self.error = true;
+        false
+    }
+
+    fn start_transcode(&mut self, source: PathBuf, info: MediaInfo) {
+        let extension = if self.proc_type == ProcType::Audio { "m4a" } else { "mp4" };
+        let output = output_path(&source, &self.model, extension);
+        let plan = plan(&source, output, &info, &self.proc_type, &self.model);
+        match TranscodeJob::start(plan) {
+            Ok(job) => {
+                self.transcode = Some(job);
+                self.progress = None;
+            },
+            Err(e) => {
+                self.error_message = tr!("fileselect.convert_failed", reason = e);
+                self.error = true;
+            }
+        }
+    }
+
+    fn update_transcode(&mut self) {
+        let Some(job) = self.transcode.as_mut() else {
+            return;
+        };
+        match job.poll() {
+            TranscodeState::Running(progress) => self.progress = progress,
+            TranscodeState::Finished(output) => {
+                self.transcode = None;
+                // the converted file is used in place of the original
+                self.accept(output);
+            },
+            TranscodeState::Failed(reason) => {
+                self.transcode = None;
+                self.error_message = tr!("fileselect.convert_failed", reason = reason);
+                self.error = true;
+            }
         }
-        return Ok(false);
     }
 
     // rendering logic
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("File")
+        Paragraph::new(tr!("fileselect.header"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
-    fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .style(FOOTER_STYLE)
+    // the key hints for the files; the progress, errors and folder summary show their own
+    fn footer(&self) -> Option<Footer> {
+        if self.is_busy() || self.error || self.inventory.is_some() {
+            return None;
+        }
+        let keymap = self.keymap();
+        let footer = Footer::default()
+            .hint(tr!("footer.move"))
+            .bound(&keymap, tr!("footer.parent"), KeyCode::Left, Action::Parent)
+            .bound(&keymap, tr!("footer.open"), KeyCode::Right, Action::Open)
+            .hint(tr!("footer.top_bottom"))
+            .bound(&keymap, tr!("footer.drives"), KeyCode::Char('e'), Action::Drives);
+        let footer = if self.can_import() {
+            footer.bound(&keymap, tr!("footer.import"), KeyCode::Char('i'), Action::Import)
+        } else {
+            footer
+        };
+        Some(footer
+            .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+            .bound(&keymap, tr!("footer.quit"), KeyCode::Char('q'), Action::Quit)
+            .bound(&keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help))
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let text = self.footer().map(|footer| footer.line()).unwrap_or_default();
+        Paragraph::new(text)
+            .style(theme().footer)
             .centered()
             .render(area, buf);
     }
@@ -211,20 +576,99 @@ impl FileSelectWidget {
         // set the current input as the entry selected.
         let popup_area: Rect = areas::popup_area(area);
         Paragraph::new(Line::raw(""))
-            .bg(NORMAL_ROW_BG)
+            .bg(theme().normal_row_bg)
             .block(
                 Block::new()
             )
             .render(area, buf);
 
-        Paragraph::new(Line::raw(&self.error_message)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+        Paragraph::new(self.error_message.as_str())
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("ERROR").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("common.error")).centered())
+           )
+           .render(popup_area, buf);
+    }
+
+    fn render_progress(&self, area: Rect, buf: &mut Buffer) {
+        let popup_area: Rect = areas::popup_area(area);
+        Paragraph::new(Line::raw(""))
+            .bg(theme().normal_row_bg)
+            .block(
+                Block::new()
+            )
+            .render(area, buf);
+
+        let block = Block::bordered()
+            .style(theme().item_header)
+            .title(Line::raw(if self.scan.is_some() {
+                tr!("fileselect.checking")
+            } else if self.import.is_some() {
+                tr!("fileselect.copying")
+            } else {
+                tr!("fileselect.converting")
+            }).centered())
+            .title_bottom(Line::raw(tr!("fileselect.cancel_hint")).centered())
+            .padding(Padding::uniform(1));
+        let label = match self.progress {
+            Some(progress) => format!("{:.0}%", progress * 100.0),
+            None => String::from(tr!("fileselect.starting"))
+        };
+        Gauge::default()
+            .block(block)
+            .gauge_style(theme().selected)
+            .ratio(self.progress.unwrap_or(0.0))
+            .label(label)
+            .render(popup_area, buf);
+    }
+
+    fn render_inventory(&self, area: Rect, buf: &mut Buffer) {
+        let Some((folder, inventory)) = self.inventory.as_ref() else {
+            return;
+        };
+        let popup_area: Rect = areas::popup_area(area);
+        Paragraph::new(Line::raw(""))
+            .bg(theme().normal_row_bg)
+            .block(
+                Block::new()
+            )
+            .render(area, buf);
+
+        let mut text = vec![
+            Line::from(format!("{}", folder.display())),
+            Line::from(tr!("fileselect.images_ready", count = inventory.images))
+        ];
+        if inventory.subfolder_images > 0 {
+            text.push(Line::from(tr!("fileselect.images_subfolders", count = inventory.subfolder_images)));
+        }
+        if inventory.unsupported > 0 {
+            text.push(Line::from(tr!("fileselect.not_images", count = inventory.unsupported)));
+        }
+        if inventory.empty + inventory.unreadable > 0 {
+            text.push(Line::from(tr!("fileselect.damaged_images", count = inventory.empty + inventory.unreadable)));
+        }
+        if let Some((width, height)) = inventory.largest {
+            text.push(Line::from(tr!("fileselect.largest_image", width = width, height = height)));
+        }
+        if inventory.oversized > 0 {
+            let (max_width, max_height) = self.model.max_resolution();
+            text.push(Line::from(tr!("fileselect.oversized", count = inventory.oversized, width = max_width, height = max_height)).bold());
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(tr!("fileselect.folder_confirm")));
+
+        Paragraph::new(text)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
+           .wrap(Wrap {trim:false})
+           .block(
+               Block::bordered()
+               .style(theme().item_header)
+               .title(Line::raw(tr!("fileselect.folder_title")).centered())
            )
            .render(popup_area, buf);
     }
@@ -233,23 +677,49 @@ impl FileSelectWidget {
         let theme = Theme::default()
             .add_default_title()
             .with_block(Block::default()
-                .title(Line::raw("Select File").centered())
+                .title(Line::raw(tr!("fileselect.explorer_title")).centered())
                 .borders(Borders::TOP)
                 .border_set(symbols::border::EMPTY)
-                .border_style(ITEM_HEADER_STYLE)
-                .bg(NORMAL_ROW_BG)
+                .border_style(theme().item_header)
+                .bg(theme().normal_row_bg)
                 .padding(Padding::horizontal(1))
                 )
-            .with_highlight_item_style(SELECTED_STYLE)
+            .with_highlight_item_style(theme().selected)
             .with_highlight_symbol("> ")
             .with_highlight_spacing(HighlightSpacing::Always)
-            .with_dir_style(Style::default().fg(TEXT_DIR_COLOR).add_modifier(Modifier::BOLD))
-            .with_highlight_dir_style(SELECTED_STYLE)
-            .with_item_style(Style::default().fg(TEXT_FG_COLOR));
+            .with_dir_style(Style::default().fg(theme().text_dir).add_modifier(Modifier::BOLD))
+            .with_highlight_dir_style(theme().selected)
+            .with_item_style(Style::default().fg(theme().text_fg));
         
         self.file_explorer.set_theme(theme)
     }
 
+    // pick up drives plugged in or pulled out since the last check
+    fn update_drives(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.monitor.as_ref().and_then(DriveMonitor::poll) {
+            Some(drives) => self.apply_drives(drives),
+            None => Ok(())
+        }
+    }
+
+    // open the drives screen on the drive being browsed
+    fn show_drives(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        let browsed = browsed_drive(&self.file_explorer, &self.mounted_drives);
+        let drives = DrivesWidget::new(self.mounted_drives.clone(), browsed).run(terminal)?;
+        self.apply_drives(drives)
+    }
+
+    fn apply_drives(&mut self, drives: Vec<Drive>) -> Result<(), Box<dyn Error>> {
+        let update = update_explorer(&mut self.file_explorer, &mut self.mounted_drives, drives)?;
+        if update.folder_gone {
+            self.setup_file_explorer()?;
+        }
+        if update.toast.is_some() {
+            self.toast = update.toast;
+        }
+        Ok(())
+    }
+
     fn setup_file_explorer(&mut self) -> Result<(), Box<dyn Error>> {
         let username = whoami::username();
 
@@ -269,8 +739,8 @@ impl FileSelectWidget {
         } else if self.mounted_drives.len() > 1 && !username.is_empty() {
             let path_buf: PathBuf = ["/media/", &username].iter().collect();
             self.file_explorer.set_cwd(&path_buf)?;
-        } else if self.mounted_drives.len() == 1 {
-            self.file_explorer.set_cwd(self.mounted_drives[0].0.clone())?;
+        } else if let [drive] = self.mounted_drives.as_slice() && let Some(mountpoint) = &drive.mountpoint {
+            self.file_explorer.set_cwd(mountpoint)?;
         } else if !username.is_empty() {
             let username = whoami::username();
             let path_buf: PathBuf = ["/home/", &username].iter().collect();
@@ -282,45 +752,75 @@ impl FileSelectWidget {
     }
     
     fn render_file_explorer(&mut self, area: Rect, buf: &mut Buffer) {
+        self.targets.list = explorer_rows(area);
         self.file_explorer.widget().render(area, buf);
     }
 
+    fn render_details(&self, area: Rect, buf: &mut Buffer) {
+        let text: Vec<Line> = match self.details.as_ref() {
+            Some((_, Ok(info))) => info.summary().into_iter().map(Line::from).collect(),
+            Some((_, Err(_))) => vec![Line::from(tr!("fileselect.not_media"))],
+            None => vec![Line::from(tr!("fileselect.details_hint"))]
+        };
+
+        let block = Block::new()
+            .title(Line::raw(tr!("fileselect.details_title")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(text)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
     fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
 
         let mut text = vec![ 
-                Line::from("Select a file using our file explorer."),
-                Line::from("Use the arrow keys ⇅ to find the file you want to use."),
-                Line::from("Press ENTER to select the file."),
-                Line::from("To ascend a directory navigate to \"↑ Parent Folder ↑\" and press Enter"),
-                Line::from("USB sticks will show up automatically. Manually find them in the directory 'media'."),
+                Line::from(tr!("fileselect.file_instructions_1")),
+                Line::from(tr!("fileselect.file_instructions_2")),
+                Line::from(tr!("fileselect.file_instructions_3")),
+                Line::from(tr!("fileselect.parent_instructions")),
+                Line::from(tr!("fileselect.usb_instructions")),
 
             ];
 
     
         if self.can_be_dir {
             text = vec![ 
-                Line::from("Select a folder using our explorer."),
-                Line::from("The target folder must not contain other folders."),
-                Line::from("Use the arrow keys ⇅ to find the folder you want to use."),
-                Line::from("Press ENTER to open the folder, navigate to one of your images and then press ENTER again."),
-                Line::from("To ascend a directory navigate to \"↑ Parent Folder ↑\" and press Enter"),
-                Line::from("USB sticks will show up automatically. Manually find them in the directory 'media'."),
+                Line::from(tr!("fileselect.folder_instructions_1")),
+                Line::from(tr!("fileselect.folder_instructions_2")),
+                Line::from(tr!("fileselect.folder_instructions_3")),
+                Line::from(tr!("fileselect.folder_instructions_4")),
+                Line::from(tr!("fileselect.parent_instructions")),
+                Line::from(tr!("fileselect.usb_instructions")),
 
             ];
         }
+        text.extend(self.mounted_drives.iter().map(|drive| Line::from(drive.summary())));
+        if !self.mounted_drives.is_empty() {
+            text.push(Line::from(tr!("fileselect.eject_hint")));
+        }
+        if self.can_import() {
+            text.push(Line::from(tr!("fileselect.import_hint")));
+        }
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INSTRUCTIONS").centered())
+            .title(Line::raw(tr!("common.instructions")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(text)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
@@ -330,7 +830,7 @@ impl FileSelectWidget {
 
 impl Widget for &mut FileSelectWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        
+        self.targets.screen = area;
         let [header_area, main_area, footer_area] = Layout::vertical([
             Constraint::Length(2),
             Constraint::Fill(1),
@@ -345,15 +845,43 @@ impl Widget for &mut FileSelectWidget {
         .areas(main_area);
 
         FileSelectWidget::render_header(header_area, buf);
-        FileSelectWidget::render_footer(footer_area, buf);
+        self.render_footer(footer_area, buf);
 
-        if self.error {
+        if self.is_busy() {
+            let popup_area: Rect = areas::popup_area(area);
+            Clear.render(popup_area, buf);
+            self.render_progress(main_area, buf);
+        } else if self.error {
             let popup_area: Rect = areas::popup_area(area);
             Clear.render(popup_area, buf);
             self.render_error(main_area, buf);
+        } else if self.inventory.is_some() {
+            let popup_area: Rect = areas::popup_area(area);
+            Clear.render(popup_area, buf);
+            self.render_inventory(main_area, buf);
         } else {
             self.render_file_explorer(file_area, buf);
-            self.render_selected_item(item_area, buf);
+            if self.shows_details() {
+                let [instructions_area, details_area] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Fill(1)
+                ])
+                .areas(item_area);
+                self.render_selected_item(instructions_area, buf);
+                self.render_details(details_area, buf);
+            } else {
+                self.render_selected_item(item_area, buf);
+            }
+        }
+
+        if let Some(toast) = self.toast.as_ref().filter(|toast| !toast.expired()) {
+            let toast_area = Toast::area(area);
+            Clear.render(toast_area, buf);
+            toast.render(toast_area, buf);
+        }
+
+        if self.help {
+            self.keymap().render_help(tr!("help.fileselect"), area, buf);
         }
 
 
@@ -361,3 +889,23 @@ impl Widget for &mut FileSelectWidget {
 
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_footer() {
+        let mut widget = FileSelectWidget::default();
+        for proc_type in [ProcType::Video, ProcType::Executable] {
+            widget.proc_type = proc_type;
+            // building the footer checks each hint against the keymap
+            let line = widget.footer().unwrap().line().to_string();
+            assert!(line.contains(tr!("footer.parent")));
+            assert!(!line.contains(tr!("footer.unselect")));
+            assert_eq!(line.contains(tr!("footer.import")), widget.can_import());
+        }
+        widget.error = true;
+        assert!(widget.footer().is_none());
+        assert_eq!(widget.keymap().action(KeyCode::Char('?')), Some(Action::Help));
+    }
+}
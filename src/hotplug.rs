@@ -0,0 +1,293 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    widgets::{
+        Block, Borders, Paragraph,
+        Widget, Wrap
+    },
+};
+use ratatui_explorer::FileExplorer;
+use std::{
+    collections::BTreeSet,
+    fs,
+    io,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            Ordering
+        },
+        mpsc::{
+            self,
+            Receiver
+        }
+    },
+    thread,
+    time::{
+        Duration,
+        Instant
+    }
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::styles::theme;
+use crate::mount::{
+    Drive,
+    identify_mounted_drives
+};
+use crate::runner::SystemRunner;
+use crate::media_root;
+use crate::tr;
+
+// every disk and partition the kernel knows about has an entry here
+const BLOCK_DIR: &str = "/sys/class/block";
+const SCAN_INTERVAL: Duration = Duration::from_secs(1);
+// give the kernel time to read the partition table before asking lsblk about it
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// A drive that was plugged in or pulled out.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriveEvent {
+    Added(Drive),
+    Removed(Drive)
+}
+
+impl DriveEvent {
+    pub fn message(&self) -> String {
+        match self {
+            DriveEvent::Added(drive) => tr!("hotplug.connected", drive = drive.summary()),
+            DriveEvent::Removed(drive) => tr!("hotplug.removed", drive = drive.label.as_deref().unwrap_or(&drive.name))
+        }
+    }
+}
+
+/// Compares two lists of mounted drives by UUID.
+pub fn drive_changes(old: &[Drive], new: &[Drive]) -> Vec<DriveEvent> {
+    let removed = old.iter()
+        .filter(|drive| !new.iter().any(|n| n.uuid == drive.uuid))
+        .map(|drive| DriveEvent::Removed(drive.clone()));
+    let added = new.iter()
+        .filter(|drive| !old.iter().any(|o| o.uuid == drive.uuid))
+        .map(|drive| DriveEvent::Added(drive.clone()));
+    removed.chain(added).collect()
+}
+
+/// The names of the block devices in `dir`, e.g. sda, sda1, mmcblk0p1.
+pub fn block_devices(dir: &Path) -> io::Result<BTreeSet<String>> {
+    Ok(fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Watches for drives being plugged in or removed while the setup screens are open.
+/// A change to the block devices triggers a fresh `identify_mounted_drives`, which also mounts
+/// any new drive, and the widget picks up the result with `poll`.
+pub struct DriveMonitor {
+    updates: Receiver<Vec<Drive>>,
+    stop: Arc<AtomicBool>
+}
+
+impl DriveMonitor {
+    pub fn start() -> Self {
+        let (sender, updates) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut known = block_devices(Path::new(BLOCK_DIR)).unwrap_or_default();
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(SCAN_INTERVAL);
+                let current = match block_devices(Path::new(BLOCK_DIR)) {
+                    Ok(current) => current,
+                    Err(e) => {
+                        loge!("Could not read {}: {}", BLOCK_DIR, e);
+                        return;
+                    }
+                };
+                if current == known {
+                    continue;
+                }
+                logi!("Block devices changed, looking for drives");
+                known = current;
+                thread::sleep(SETTLE_TIME);
+                let drives = identify_mounted_drives(&SystemRunner).unwrap_or_else(|e| {
+                    logw!("No storage devices identified, Error: {}", e);
+                    Vec::new()
+                });
+                if sender.send(drives).is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            updates,
+            stop
+        }
+    }
+
+    /// The drives now mounted, if they changed since the last poll.
+    pub fn poll(&self) -> Option<Vec<Drive>> {
+        self.updates.try_iter().last()
+    }
+}
+
+impl Drop for DriveMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// What a screen with a file browser has to do once the mounted drives have changed.
+pub struct ExplorerUpdate {
+    /// The folder being browsed has gone, so the browser should start again from somewhere that
+    /// still exists.
+    pub folder_gone: bool,
+    /// Says what was plugged in or pulled out.
+    pub toast: Option<Toast>
+}
+
+/// Replaces `mounted` with `drives` on a screen browsing with `explorer`. When the drive folders
+/// themselves are being browsed they are listed again, so a new drive shows up straight away.
+pub fn update_explorer(explorer: &mut FileExplorer, mounted: &mut Vec<Drive>, drives: Vec<Drive>) -> io::Result<ExplorerUpdate> {
+    let changes = drive_changes(mounted, &drives);
+    *mounted = drives;
+    let cwd = explorer.cwd().clone();
+    let browsing_removed = changes.iter()
+        .any(|change| matches!(change, DriveEvent::Removed(drive) if drive.contains(&cwd)));
+    let folder_gone = browsing_removed || !cwd.exists();
+    if !folder_gone && cwd == media_root() {
+        // list the new drive folders
+        explorer.set_cwd(cwd)?;
+    }
+    Ok(ExplorerUpdate {
+        folder_gone,
+        toast: changes.last().map(|change| Toast::new(change.message()))
+    })
+}
+
+/// The drive holding the folder or file highlighted in `explorer`, so the drives screen opens on it.
+pub fn browsed_drive(explorer: &FileExplorer, drives: &[Drive]) -> Option<usize> {
+    let cwd = explorer.cwd();
+    let current = explorer.current().path();
+    drives.iter().position(|drive| drive.contains(cwd) || drive.contains(current))
+}
+
+/// A short message shown over the screen for a few seconds.
+pub struct Toast {
+    message: String,
+    shown: Instant
+}
+
+impl Toast {
+    pub fn new(message: String) -> Self {
+        Self {
+            message,
+            shown: Instant::now()
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.shown.elapsed() >= TOAST_DURATION
+    }
+
+    /// The bottom right corner of `area`, above the footer.
+    pub fn area(area: Rect) -> Rect {
+        let width = (area.width / 2).max(20).min(area.width);
+        let height = 4.min(area.height);
+        Rect {
+            x: area.x + area.width - width,
+            y: area.y + area.height.saturating_sub(height + 1),
+            width,
+            height
+        }
+    }
+}
+
+impl Widget for &Toast {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        Paragraph::new(self.message.as_str())
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+    use crate::mount::tests::drive;
+
+    fn plugged(uuid: &str, label: &str) -> Drive {
+        drive(uuid, Some(label), &Path::new("/media/pi").join(label))
+    }
+
+    #[test]
+    fn check_drive_changes() {
+        let stick = plugged("1234-ABCD", "MY STICK");
+        let card = plugged("5678-EFGH", "SD CARD");
+
+        assert!(drive_changes(&[stick.clone()], &[stick.clone()]).is_empty());
+        assert_eq!(drive_changes(&[stick.clone()], &[stick.clone(), card.clone()]), vec![DriveEvent::Added(card.clone())]);
+        assert_eq!(drive_changes(&[stick.clone(), card.clone()], &[card.clone()]), vec![DriveEvent::Removed(stick.clone())]);
+        assert_eq!(DriveEvent::Removed(stick).message(), "USB drive removed: MY STICK");
+    }
+
+    #[test]
+    fn check_update_explorer() {
+        let dir = tempdir().unwrap();
+        let mut stick = plugged("1234-ABCD", "MY STICK");
+        stick.mountpoint = Some(dir.path().to_path_buf());
+        let card = plugged("5678-EFGH", "SD CARD");
+        let mut explorer = FileExplorer::new().unwrap();
+        explorer.set_cwd(dir.path()).unwrap();
+        let mut mounted = vec![stick.clone()];
+
+        let update = update_explorer(&mut explorer, &mut mounted, vec![stick.clone(), card.clone()]).unwrap();
+        assert!(!update.folder_gone);
+        assert_eq!(update.toast.map(|toast| toast.message), Some(DriveEvent::Added(card.clone()).message()));
+        assert_eq!(browsed_drive(&explorer, &mounted), Some(0));
+
+        // the stick being browsed is pulled out
+        let update = update_explorer(&mut explorer, &mut mounted, vec![card.clone()]).unwrap();
+        assert!(update.folder_gone);
+        assert_eq!(mounted, vec![card]);
+        assert_eq!(browsed_drive(&explorer, &mounted), None);
+    }
+
+    #[test]
+    fn check_block_devices() {
+        let dir = tempdir().unwrap();
+        for name in ["sda", "sda1", "mmcblk0p1"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        let devices: Vec<_> = block_devices(dir.path()).unwrap().into_iter().collect();
+        assert_eq!(devices, vec!["mmcblk0p1", "sda", "sda1"]);
+    }
+
+    #[test]
+    fn check_toast_area() {
+        let area = Toast::area(Rect::new(0, 0, 80, 24));
+        assert_eq!(area, Rect::new(40, 19, 40, 4));
+        assert!(!Toast::new(String::from("USB drive removed")).expired());
+    }
+}
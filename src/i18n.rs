@@ -0,0 +1,229 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    path::PathBuf,
+    sync::{
+        OnceLock,
+        atomic::{
+            AtomicUsize,
+            Ordering
+        }
+    }
+};
+use strum::{
+    Display,
+    EnumString,
+    EnumIter,
+    IntoEnumIterator
+};
+use log::info;
+use crate::logi;
+
+use crate::env_file_path;
+
+/// The languages the screens can be shown in, as named in the locale file.
+#[derive(Debug, Display, EnumString, EnumIter, PartialEq, Clone, Copy, Default)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Locale {
+    #[default]
+    En,
+    /// Welsh
+    Cy
+}
+
+impl Locale {
+    /// The message catalogue, built into the program so nothing needs installing alongside it.
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.txt"),
+            Locale::Cy => include_str!("../locales/cy.txt")
+        }
+    }
+}
+
+type Catalogue = HashMap<&'static str, &'static str>;
+
+/// Reads `key = value` lines. Blank lines and lines starting with # are skipped.
+fn parse_catalogue(source: &'static str) -> Catalogue {
+    source.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+fn catalogue(locale: Locale) -> &'static Catalogue {
+    static CATALOGUES: OnceLock<Vec<Catalogue>> = OnceLock::new();
+    let catalogues = CATALOGUES.get_or_init(|| Locale::iter().map(|locale| parse_catalogue(locale.source())).collect());
+    &catalogues[locale as usize]
+}
+
+// the current locale, as its position in Locale::iter()
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn locale() -> Locale {
+    Locale::iter().nth(CURRENT.load(Ordering::Relaxed)).unwrap_or_default()
+}
+
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as usize, Ordering::Relaxed);
+}
+
+/// The text for `key` in the current language. Falls back to English, then to the key itself so
+/// a missing message is visible rather than blank.
+pub fn message(key: &'static str) -> &'static str {
+    catalogue(locale()).get(key)
+        .or_else(|| catalogue(Locale::En).get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// The text for `key` with each {name} placeholder replaced by its value.
+pub fn format_message(key: &'static str, args: &[(&str, String)]) -> String {
+    args.iter().fold(message(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Looks up a message in the current language, e.g. `tr!("landing.start")`. Placeholders are
+/// filled from named arguments, e.g. `tr!("drives.removable", name = label)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::message($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format_message($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+/// "Yes" or "No" in the current language.
+pub fn yes_no(yes: bool) -> &'static str {
+    if yes {
+        tr!("common.yes")
+    } else {
+        tr!("common.no")
+    }
+}
+
+/// The name of `day`, counting from 0 for Monday.
+pub fn weekday_name(day: usize) -> &'static str {
+    match day {
+        0 => tr!("day.monday"),
+        1 => tr!("day.tuesday"),
+        2 => tr!("day.wednesday"),
+        3 => tr!("day.thursday"),
+        4 => tr!("day.friday"),
+        5 => tr!("day.saturday"),
+        _ => tr!("day.sunday")
+    }
+}
+
+/// The short name of `day`, e.g. Mon, counting from 0 for Monday.
+pub fn weekday_short_name(day: usize) -> &'static str {
+    match day {
+        0 => tr!("day.mon"),
+        1 => tr!("day.tue"),
+        2 => tr!("day.wed"),
+        3 => tr!("day.thu"),
+        4 => tr!("day.fri"),
+        5 => tr!("day.sat"),
+        _ => tr!("day.sun")
+    }
+}
+
+/// The locale named by a setting such as "cy" or a POSIX locale such as "cy_GB.UTF-8".
+pub fn parse_locale(value: &str) -> Option<Locale> {
+    value.trim().split(['_', '.', '@', '-']).next()?.parse().ok()
+}
+
+/// The file holding the chosen language, next to the config.
+fn locale_file_path() -> PathBuf {
+    env_file_path().with_file_name("locale")
+}
+
+/// Picks the language from the config folder, or else from LC_ALL, LC_MESSAGES or LANG. English
+/// is kept when none of them names a language that is shipped.
+pub fn load_locale() {
+    let configured = fs::read_to_string(locale_file_path()).ok();
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter().filter_map(|name| env::var(name).ok());
+    if let Some(locale) = configured.into_iter().chain(from_env).find_map(|value| parse_locale(&value)) {
+        logi!("Language: {}", locale);
+        set_locale(locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::path::Path;
+
+    fn placeholders(text: &str) -> Vec<String> {
+        let re = Regex::new(r"\{(\w+)\}").unwrap();
+        let mut names: Vec<String> = re.captures_iter(text).map(|c| c[1].to_string()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn check_locales_complete() {
+        let english = catalogue(Locale::En);
+        for locale in Locale::iter() {
+            let other = catalogue(locale);
+            let mut missing: Vec<&str> = english.keys().filter(|key| !other.contains_key(*key)).copied().collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{} is missing {:?}", locale, missing);
+
+            let mut unknown: Vec<&str> = other.keys().filter(|key| !english.contains_key(*key)).copied().collect();
+            unknown.sort();
+            assert!(unknown.is_empty(), "{} has keys that English does not: {:?}", locale, unknown);
+
+            for (key, text) in other {
+                assert_eq!(placeholders(text), placeholders(english[key]), "{} {} has different placeholders", locale, key);
+            }
+        }
+    }
+
+    fn sources(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                sources(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn check_keys_used_exist() {
+        let re = Regex::new(r#"\btr!\(\s*"([^"]+)""#).unwrap();
+        let mut files = Vec::new();
+        sources(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+        let english = catalogue(Locale::En);
+        for file in files {
+            let source = fs::read_to_string(&file).unwrap();
+            for key in re.captures_iter(&source).map(|c| c[1].to_string()) {
+                assert!(english.contains_key(key.as_str()), "{} uses {} which is not in en.txt", file.display(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn check_parse_locale() {
+        assert_eq!(parse_locale("cy_GB.UTF-8"), Some(Locale::Cy));
+        assert_eq!(parse_locale("en"), Some(Locale::En));
+        assert_eq!(parse_locale(" CY\n"), Some(Locale::Cy));
+        assert_eq!(parse_locale("C"), None);
+        assert_eq!(parse_locale("fr_FR.UTF-8"), None);
+    }
+
+    #[test]
+    fn check_format_message() {
+        assert_eq!(format_message("drives.removable", &[("name", String::from("STICK"))]), "STICK can now be removed safely.");
+        assert_eq!(message("no.such.key"), "no.such.key");
+    }
+}
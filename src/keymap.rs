@@ -0,0 +1,251 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{
+        Constraint,
+        Flex,
+        Layout,
+        Rect
+    },
+    style::Stylize,
+    text::{
+        Line,
+        Span
+    },
+    widgets::{
+        Block,
+        Clear,
+        Padding,
+        Paragraph,
+        Widget,
+        Wrap
+    }
+};
+
+use crate::styles::theme;
+use crate::tr;
+
+/// The keys that open the help, on screens where ? can be typed as text.
+pub const HELP_KEYS: &[KeyCode] = &[KeyCode::Char('?'), KeyCode::F(1)];
+
+/// What a key does on a screen. Each screen reads its keys through a `Keymap`, and the help
+/// overlay is drawn from the same keymap, so the help cannot disagree with the keys.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Action {
+    Help,
+    Next,
+    Previous,
+    First,
+    Last,
+    Unselect,
+    Choose,
+    Back,
+    Quit,
+    Finish,
+    Menu,
+    Drives,
+    Parent,
+    Open,
+    OpenOrChoose,
+    Import,
+    Convert,
+    Edit,
+    Leave,
+    Theme,
+    Eject,
+    Decrease,
+    Increase,
+    DecreaseMore,
+    IncreaseMore,
+    Close,
+    Continue,
+    Apply,
+    Skip,
+    Cancel,
+    CursorLeft,
+    CursorRight,
+    DeleteChar,
+    Save,
+    PreviousPart,
+    NextPart,
+    OtherTime,
+    Unlock,
+    Recover
+}
+
+impl Action {
+    /// What the action does, as shown in the help.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Action::Help => tr!("keys.help"),
+            Action::Next => tr!("keys.next"),
+            Action::Previous => tr!("keys.previous"),
+            Action::First => tr!("keys.first"),
+            Action::Last => tr!("keys.last"),
+            Action::Unselect => tr!("keys.unselect"),
+            Action::Choose => tr!("keys.choose"),
+            Action::Back => tr!("keys.back"),
+            Action::Quit => tr!("keys.quit"),
+            Action::Finish => tr!("keys.finish"),
+            Action::Menu => tr!("keys.menu"),
+            Action::Drives => tr!("keys.drives"),
+            Action::Parent => tr!("keys.parent"),
+            Action::Open => tr!("keys.open"),
+            Action::OpenOrChoose => tr!("keys.open_or_choose"),
+            Action::Import => tr!("keys.import"),
+            Action::Convert => tr!("keys.convert"),
+            Action::Edit => tr!("keys.edit"),
+            Action::Leave => tr!("keys.leave"),
+            Action::Theme => tr!("keys.theme"),
+            Action::Eject => tr!("keys.eject"),
+            Action::Decrease => tr!("keys.decrease"),
+            Action::Increase => tr!("keys.increase"),
+            Action::DecreaseMore => tr!("keys.decrease_more"),
+            Action::IncreaseMore => tr!("keys.increase_more"),
+            Action::Close => tr!("keys.close"),
+            Action::Continue => tr!("keys.continue"),
+            Action::Apply => tr!("keys.apply"),
+            Action::Skip => tr!("keys.skip"),
+            Action::Cancel => tr!("keys.cancel"),
+            Action::CursorLeft => tr!("keys.cursor_left"),
+            Action::CursorRight => tr!("keys.cursor_right"),
+            Action::DeleteChar => tr!("keys.delete_char"),
+            Action::Save => tr!("keys.save"),
+            Action::PreviousPart => tr!("keys.previous_part"),
+            Action::NextPart => tr!("keys.next_part"),
+            Action::OtherTime => tr!("keys.other_time"),
+            Action::Unlock => tr!("keys.unlock"),
+            Action::Recover => tr!("keys.recover")
+        }
+    }
+}
+
+/// How a key is written in the help, e.g. ENTER or ↑.
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Up => String::from("↑"),
+        KeyCode::Down => String::from("↓"),
+        KeyCode::Left => String::from("←"),
+        KeyCode::Right => String::from("→"),
+        KeyCode::Enter => String::from("ENTER"),
+        KeyCode::Esc => String::from("ESC"),
+        KeyCode::Backspace => String::from("BACKSPACE"),
+        KeyCode::Tab => String::from("TAB"),
+        KeyCode::BackTab => String::from("SHIFT+TAB"),
+        KeyCode::Home => String::from("HOME"),
+        KeyCode::End => String::from("END"),
+        KeyCode::PageUp => String::from("PAGE UP"),
+        KeyCode::PageDown => String::from("PAGE DOWN"),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Char(' ') => String::from("SPACE"),
+        KeyCode::Char(c) => c.to_string(),
+        other => other.to_string()
+    }
+}
+
+/// The keys of one screen and what each of them does.
+#[derive(Debug, Default)]
+pub struct Keymap {
+    bindings: Vec<(&'static [KeyCode], Action)>
+}
+
+impl Keymap {
+    /// The keys for moving through a list, and for opening the help.
+    pub fn list() -> Self {
+        Keymap::default()
+            .bind(&[KeyCode::Char('j'), KeyCode::Down], Action::Next)
+            .bind(&[KeyCode::Char('k'), KeyCode::Up], Action::Previous)
+            .bind(&[KeyCode::Char('g'), KeyCode::Home], Action::First)
+            .bind(&[KeyCode::Char('G'), KeyCode::End], Action::Last)
+            .bind(HELP_KEYS, Action::Help)
+    }
+
+    /// Makes `keys` do `action`. A key can only do one thing on a screen.
+    pub fn bind(mut self, keys: &'static [KeyCode], action: Action) -> Self {
+        debug_assert!(keys.iter().all(|key| self.action(*key).is_none()), "{:?} is bound twice", keys);
+        self.bindings.push((keys, action));
+        self
+    }
+
+    /// What pressing `key` does, if anything.
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        self.bindings.iter()
+            .find(|(keys, _)| keys.contains(&key))
+            .map(|(_, action)| *action)
+    }
+
+    /// The keys written out, e.g. "k/↑", next to what they do. The help key comes last.
+    pub fn lines(&self) -> Vec<(String, &'static str)> {
+        let mut bindings: Vec<&(&'static [KeyCode], Action)> = self.bindings.iter().collect();
+        bindings.sort_by_key(|(_, action)| *action == Action::Help);
+        bindings.into_iter()
+            .map(|(keys, action)| {
+                let names: Vec<String> = keys.iter().map(|key| key_name(*key)).collect();
+                (names.join("/"), action.describe())
+            })
+            .collect()
+    }
+
+    /// Draws the help over `area`: `about` explains the step, followed by the keys.
+    pub fn render_help(&self, about: &str, area: Rect, buf: &mut Buffer) {
+        let [popup_area] = Layout::vertical([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+
+        let lines = self.lines();
+        let width = lines.iter().map(|(keys, _)| keys.chars().count()).max().unwrap_or(0) + 2;
+        let mut text = vec![
+            Line::from(about.to_string()),
+            Line::default()
+        ];
+        text.extend(lines.into_iter().map(|(keys, description)| Line::from(vec![
+            Span::raw(format!("{:<width$}", keys)).bold(),
+            Span::raw(description)
+        ])));
+
+        let block = Block::bordered()
+            .title(Line::raw(tr!("help.title")).centered())
+            .title_bottom(Line::raw(tr!("help.close")).centered())
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_keymap() {
+        let keymap = Keymap::list()
+            .bind(&[KeyCode::Esc], Action::Back)
+            .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose);
+        assert_eq!(keymap.action(KeyCode::Down), Some(Action::Next));
+        assert_eq!(keymap.action(KeyCode::Enter), Some(Action::Choose));
+        assert_eq!(keymap.action(KeyCode::F(1)), Some(Action::Help));
+        assert_eq!(keymap.action(KeyCode::Char('x')), None);
+
+        let lines = keymap.lines();
+        assert_eq!(lines[0].0, "j/↓");
+        assert_eq!(lines[5].0, "l/→/ENTER");
+        // the help key is listed last, however the keymap was built
+        assert_eq!(lines.last().unwrap().0, "?/F1");
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_key_bound_twice() {
+        let _ = Keymap::list().bind(&[KeyCode::Char('g')], Action::Menu);
+    }
+}
@@ -16,24 +16,28 @@ use ratatui::{
 use ratatui::prelude::*;
 use std::error::Error;
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    TEXT_FG_COLOR,
-};
+use crate::styles::theme;
 
 use crate::Model;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
 
 pub struct LandingWidget {
     model: Model,
     should_exit: bool,
+    help: bool
 }
 
 impl LandingWidget {
     pub fn new (model: Model) -> Self {
         Self {
             model,
-            should_exit: false
+            should_exit: false,
+            help: false
         }
     }
     pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<(), Box< dyn Error>> {
@@ -46,16 +50,24 @@ impl LandingWidget {
         Ok(())
     }
 
+    fn keymap() -> Keymap {
+        Keymap::default()
+            .bind(&[KeyCode::Enter, KeyCode::Char('l'), KeyCode::Right, KeyCode::Esc, KeyCode::Char('q')], Action::Continue)
+            .bind(HELP_KEYS, Action::Help)
+    }
+
     fn handle_key(&mut self, key: KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
         }
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => self.should_exit = true,
-            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
-                // add code to select the list item
-                self.should_exit = true;
-            }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match LandingWidget::keymap().action(key.code) {
+            Some(Action::Continue) => self.should_exit = true,
+            Some(Action::Help) => self.help = true,
             _ => {}
         }
     }
@@ -64,11 +76,7 @@ impl LandingWidget {
 
     // rendering logic
     fn render_header(&self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from(vec![
-            "Media Timer ".into(), 
-            self.model.as_ref().into(),
-            " Setup".into()
-        ]);
+        let title = Line::from(tr!("landing.header", model = self.model.as_ref()));
 
         Paragraph::new(title)
             .bold()
@@ -77,7 +85,7 @@ impl LandingWidget {
     }
 
     fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Press Enter or ESC to continue.")
+        Paragraph::new(tr!("common.press_continue"))
             .centered()
             .render(area, buf);
     }
@@ -96,7 +104,7 @@ impl LandingWidget {
         ⣿⠀⠙⠋⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠙⠋⠀⣿
         ⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛⠛
         "};
-        let logo_text = Text::styled(logo, TEXT_FG_COLOR);
+        let logo_text = Text::styled(logo, theme().text_fg);
         let area = centered_rect(area, logo_text.width() as u16, logo_text.height() as u16);
 
         logo_text.render(area, buf);
@@ -107,16 +115,14 @@ impl LandingWidget {
         Paragraph::new(
             vec![
                 Line::from("Adaptables: Media Timer"),
-                Line::from("Run it like clockwork."),
+                Line::from(tr!("landing.tagline")),
                 Line::from(""),
-                Line::from(
-                        "Press ENTER to start."
-                ),
+                Line::from(tr!("landing.start")),
             ])
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
-            .bg(NORMAL_ROW_BG)
-            .fg(TEXT_FG_COLOR)
+            .bg(theme().normal_row_bg)
+            .fg(theme().text_fg)
             .render(
                 area,
                 buf
@@ -130,9 +136,9 @@ impl LandingWidget {
             .title(title.clone())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
+            .border_style(theme().item_header)
             .padding(Padding::uniform(4))
-            .bg(NORMAL_ROW_BG);
+            .bg(theme().normal_row_bg);
 
         // render block
         block.render(area, buf);
@@ -173,6 +179,10 @@ impl Widget for &mut LandingWidget {
         LandingWidget::render_header(self, header_area, buf);
         LandingWidget::render_footer(footer_area, buf);
         LandingWidget::render_center(main_area, buf);
+
+        if self.help {
+            LandingWidget::keymap().render_help(tr!("help.landing"), area, buf);
+        }
         /*
         LandingWidget::render_logo(logo_area, buf);
         LandingWidget::render_text(text_area, buf);
@@ -0,0 +1,372 @@
+use std::{
+    error::Error,
+    fs::{
+        self,
+        File
+    },
+    io::{
+        self,
+        Read,
+        Write
+    },
+    path::{
+        Path,
+        PathBuf
+    },
+    process::Command,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            Ordering
+        },
+        mpsc::{
+            self,
+            Receiver,
+            Sender,
+            TryRecvError
+        }
+    },
+    thread
+};
+use sha2::{
+    Digest,
+    Sha256
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+use crate::tr;
+
+use crate::local_media_dir;
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
+};
+use crate::slideshow::slideshow_files;
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+// keep some space free for logs and the system
+const SPARE_BYTES: u64 = 200 * 1024 * 1024;
+
+/// The files to copy into the library and where each one goes.
+#[derive(Debug, PartialEq)]
+pub struct ImportPlan {
+    // the file or folder in the library that replaces the source
+    pub target: PathBuf,
+    // where the files are copied to, which is renamed to the target once every file is checked
+    pub partial: PathBuf,
+    pub files: Vec<(PathBuf, PathBuf)>,
+    pub total_bytes: u64
+}
+
+/// A name in `library` that nothing has yet, e.g. "clip (2).mp4" when "clip.mp4" is already there.
+pub(crate) fn unused_name(library: &Path, name: &str, is_dir: bool) -> String {
+    let (stem, extension) = match Path::new(name).extension().and_then(|e| e.to_str()) {
+        Some(extension) if !is_dir => (&name[..name.len() - extension.len() - 1], format!(".{}", extension)),
+        _ => (name, String::new())
+    };
+    let mut candidate = name.to_string();
+    let mut count = 2;
+    while library.join(&candidate).exists() {
+        candidate = format!("{} ({}){}", stem, count, extension);
+        count += 1;
+    }
+    candidate
+}
+
+/// Plans copying a file, or a folder and its subfolders, into `library`. Nothing already in the
+/// library is replaced: a name that is taken gets a number added.
+pub fn plan_import(source: &Path, library: &Path) -> io::Result<ImportPlan> {
+    let name = source.file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| io::Error::other("The selection has no name"))?;
+    let name = unused_name(library, name, source.is_dir());
+    let target = library.join(&name);
+    // hidden, so the player and file browsers never pick up a half finished copy
+    let partial = library.join(format!(".{}.partial", name));
+
+    let files = if source.is_dir() {
+        slideshow_files(source, true)?
+            .into_iter()
+            .filter_map(|file| {
+                let relative = file.strip_prefix(source).ok()?.to_path_buf();
+                Some((file, partial.join(relative)))
+            })
+            .collect()
+    } else {
+        vec![(source.to_path_buf(), partial.clone())]
+    };
+
+    let mut total_bytes = 0;
+    for (file, _) in files.iter() {
+        total_bytes += fs::metadata(file)?.len();
+    }
+    Ok(ImportPlan {
+        target,
+        partial,
+        files,
+        total_bytes
+    })
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `dir`.
+pub fn free_space(runner: &dyn CommandRunner, dir: &Path) -> Result<u64, Box<dyn Error>> {
+    let output = runner.output(Command::new("df")
+        .args(["--output=avail", "-B1"])
+        .arg(dir))?;
+    parse_free_space(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| tr!("library.free_space", path = dir.display()).into())
+}
+
+fn parse_free_space(output: &str) -> Option<u64> {
+    // the first line is the column heading
+    output.lines().nth(1)?.trim().parse().ok()
+}
+
+/// The sha256 of the contents of `path`.
+pub(crate) fn hash_file(path: &Path) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+enum ImportUpdate {
+    Copied(u64),
+    Finished,
+    Failed(String)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ImportState {
+    // fraction of the bytes copied
+    Running(f64),
+    Finished(PathBuf),
+    Failed(String)
+}
+
+/// Copies one file, hashing it on the way, then checks the copy against that hash.
+fn copy_verified(source: &Path, destination: &Path, cancel: &AtomicBool, updates: &Sender<ImportUpdate>) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = destination.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut input = File::open(source)?;
+    let mut output = File::create(destination)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("The copy was cancelled".into());
+        }
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        output.write_all(&buffer[..read])?;
+        let _ = updates.send(ImportUpdate::Copied(read as u64));
+    }
+    output.sync_all()?;
+
+    if hash_file(destination)? != hasher.finalize().to_vec() {
+        return Err(tr!("library.mismatch", path = source.display()).into());
+    }
+    Ok(())
+}
+
+fn remove_partial(partial: &Path) {
+    let _ = if partial.is_dir() {
+        fs::remove_dir_all(partial)
+    } else {
+        fs::remove_file(partial)
+    };
+}
+
+/// Copies and checks every file of `plan`, then moves the copy into place. Only the partial copy
+/// is ever removed, so a failed or cancelled import cannot touch what is already in the library.
+fn run_import(plan: &ImportPlan, cancel: &AtomicBool, updates: &Sender<ImportUpdate>) -> Result<(), Box<dyn Error>> {
+    // left over from an import that was interrupted
+    remove_partial(&plan.partial);
+    // an empty folder still becomes an empty folder in the library
+    if plan.files.is_empty() {
+        fs::create_dir_all(&plan.partial)?;
+    }
+    let copied = plan.files.iter()
+        .try_for_each(|(source, destination)| copy_verified(source, destination, cancel, updates))
+        .and_then(|()| if plan.target.exists() {
+            Err(tr!("library.exists", path = plan.target.display()).into())
+        } else {
+            fs::rename(&plan.partial, &plan.target).map_err(|e| e.into())
+        });
+    if copied.is_err() {
+        remove_partial(&plan.partial);
+    }
+    copied
+}
+
+/// A copy into the library running in the background.
+pub struct ImportJob {
+    updates: Receiver<ImportUpdate>,
+    cancel: Arc<AtomicBool>,
+    target: PathBuf,
+    total_bytes: u64,
+    copied_bytes: u64
+}
+
+impl ImportJob {
+    pub fn start(source: &Path) -> Result<Self, Box<dyn Error>> {
+        let library = local_media_dir();
+        fs::create_dir_all(&library)?;
+        let plan = plan_import(source, &library)?;
+
+        let available = free_space(&SystemRunner, &library)?;
+        if plan.total_bytes + SPARE_BYTES > available {
+            return Err(tr!("library.no_space",
+                needed = (plan.total_bytes + SPARE_BYTES) / 1_000_000, free = available / 1_000_000).into());
+        }
+
+        logi!("Copying {} into the media library", source.display());
+        let (sender, updates) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = Arc::clone(&cancel);
+        let target = plan.target.clone();
+        let total_bytes = plan.total_bytes;
+        thread::spawn(move || {
+            let update = match run_import(&plan, &thread_cancel, &sender) {
+                Ok(()) => ImportUpdate::Finished,
+                Err(e) => ImportUpdate::Failed(e.to_string())
+            };
+            let _ = sender.send(update);
+        });
+
+        Ok(Self {
+            updates,
+            cancel,
+            target,
+            total_bytes,
+            copied_bytes: 0
+        })
+    }
+
+    pub fn poll(&mut self) -> ImportState {
+        loop {
+            match self.updates.try_recv() {
+                Ok(ImportUpdate::Copied(bytes)) => self.copied_bytes += bytes,
+                Ok(ImportUpdate::Finished) => {
+                    logi!("Copy finished and verified");
+                    return ImportState::Finished(self.target.clone());
+                },
+                Ok(ImportUpdate::Failed(reason)) => {
+                    loge!("Copy failed: {}", reason);
+                    return ImportState::Failed(reason);
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return ImportState::Failed(tr!("library.stopped").to_string())
+            }
+        }
+        if self.total_bytes == 0 {
+            return ImportState::Running(0.0);
+        }
+        ImportState::Running((self.copied_bytes as f64 / self.total_bytes as f64).min(1.0))
+    }
+
+    /// Stops the copy. The copying thread removes anything it has written.
+    pub fn cancel(self) {
+        logw!("Copy cancelled");
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn check_parse_free_space() {
+        assert_eq!(parse_free_space("    Avail\n53687091200\n"), Some(53_687_091_200));
+        assert_eq!(parse_free_space("Avail\n"), None);
+    }
+
+    #[test]
+    fn check_plan_import() {
+        let usb = tempdir().unwrap();
+        let library = tempdir().unwrap();
+        let folder = usb.path().join("gallery");
+        fs::create_dir_all(folder.join("more")).unwrap();
+        fs::write(folder.join("a.png"), "12345").unwrap();
+        fs::write(folder.join("more/b.png"), "123").unwrap();
+
+        let plan = plan_import(&folder, library.path()).unwrap();
+        assert_eq!(plan.target, library.path().join("gallery"));
+        assert_eq!(plan.total_bytes, 8);
+        assert!(plan.files.contains(&(folder.join("more/b.png"), library.path().join(".gallery.partial/more/b.png"))));
+
+        let plan = plan_import(&folder.join("a.png"), library.path()).unwrap();
+        assert_eq!(plan.files, vec![(folder.join("a.png"), library.path().join(".a.png.partial"))]);
+
+        // names already in the library are not reused
+        fs::create_dir(library.path().join("gallery")).unwrap();
+        fs::write(library.path().join("a.png"), "").unwrap();
+        fs::write(library.path().join("a (2).png"), "").unwrap();
+        assert_eq!(plan_import(&folder, library.path()).unwrap().target, library.path().join("gallery (2)"));
+        assert_eq!(plan_import(&folder.join("a.png"), library.path()).unwrap().target, library.path().join("a (3).png"));
+    }
+
+    #[test]
+    fn check_run_import() {
+        let usb = tempdir().unwrap();
+        let library = tempdir().unwrap();
+        let folder = usb.path().join("gallery");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("a.png"), "12345").unwrap();
+        fs::create_dir(library.path().join("gallery")).unwrap();
+        fs::write(library.path().join("gallery/saved.png"), "in use").unwrap();
+        let (sender, _updates) = mpsc::channel();
+
+        // a cancelled import leaves the item with the same name alone
+        let plan = plan_import(&folder, library.path()).unwrap();
+        assert!(run_import(&plan, &AtomicBool::new(true), &sender).is_err());
+        assert!(!plan.partial.exists());
+        assert_eq!(fs::read_to_string(library.path().join("gallery/saved.png")).unwrap(), "in use");
+
+        run_import(&plan, &AtomicBool::new(false), &sender).unwrap();
+        assert!(!plan.partial.exists());
+        assert_eq!(fs::read_to_string(library.path().join("gallery (2)/a.png")).unwrap(), "12345");
+        assert_eq!(fs::read_to_string(library.path().join("gallery/saved.png")).unwrap(), "in use");
+    }
+
+    #[test]
+    fn check_copy_verified() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("clip.mp4");
+        fs::write(&source, vec![7u8; CHUNK_SIZE + 10]).unwrap();
+        let destination = dir.path().join("library/clip.mp4");
+        let (sender, updates) = mpsc::channel();
+
+        copy_verified(&source, &destination, &AtomicBool::new(false), &sender).unwrap();
+        assert_eq!(hash_file(&source).unwrap(), hash_file(&destination).unwrap());
+        let copied: u64 = updates.try_iter().map(|u| match u {
+            ImportUpdate::Copied(bytes) => bytes,
+            _ => 0
+        }).sum();
+        assert_eq!(copied, CHUNK_SIZE as u64 + 10);
+
+        assert!(copy_verified(&source, &destination, &AtomicBool::new(true), &sender).is_err());
+    }
+}
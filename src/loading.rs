@@ -30,11 +30,12 @@ use std::{
     }
 };
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    TEXT_FG_COLOR,
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
 };
+use crate::styles::theme;
+use crate::tr;
 
 pub struct LoadingWidget {
     should_exit: Arc<AtomicBool>,
@@ -70,11 +71,10 @@ impl LoadingWidget {
 
     fn run_command(&self, should_exit: Arc<AtomicBool>) {
         thread::spawn(move|| {
-            let _enable_mediatimer_init = Command::new("systemctl")
+            let _enable_mediatimer_init = SystemRunner.output(Command::new("systemctl")
                 .arg("--user")
                 .arg("start")
-                .arg("mediatimer_init.service")
-                .output()
+                .arg("mediatimer_init.service"))
                 .expect("Media Timer not restarted");
 
             should_exit.store(true, Ordering::Relaxed);
@@ -113,7 +113,7 @@ impl LoadingWidget {
     }
 
     fn render_text(area: Rect, buf: &mut Buffer) {
-        let title = Line::raw("Loading").centered();
+        let title = Line::raw(tr!("loading.title")).centered();
         /*
         let _length = title.width() * 4;
         */
@@ -121,22 +121,20 @@ impl LoadingWidget {
             .title(title.clone())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
+            .border_style(theme().item_header)
             .padding(Padding::uniform(4))
-            .bg(NORMAL_ROW_BG);
+            .bg(theme().normal_row_bg);
 
         Paragraph::new(
             vec![
-                Line::from("Please Wait"
-                ),
-                Line::from("Loading the configuration..."
-                ),
+                Line::from(tr!("loading.wait")),
+                Line::from(tr!("loading.text")),
                 Line::from(""),
             ])
             .block(block)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .render(
                 area,
                 buf
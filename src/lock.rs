@@ -0,0 +1,557 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Stylize,
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, Padding, Paragraph,
+        Widget, Wrap
+    },
+    DefaultTerminal,
+};
+use std::{
+    error::Error,
+    fs,
+    io::{
+        ErrorKind,
+        Read
+    },
+    os::unix::fs::PermissionsExt,
+    path::{
+        Path,
+        PathBuf
+    },
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH
+    }
+};
+use sha2::{
+    Digest,
+    Sha256
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::styles::theme;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::env_file_path;
+use crate::mount::{
+    Drive,
+    identify_mounted_drives
+};
+use crate::provision::{
+    PROVISION_DIR,
+    read_key,
+    sign,
+    verify
+};
+use crate::runner::SystemRunner;
+
+// the hashed PIN, as "salt:hash" in hex
+const PIN_FILE: &str = "pin";
+// the wrong PINs entered since the last correct one, and when the lockout ends
+const LOCKOUT_FILE: &str = "pin_lockout";
+/// The file in the provisioning folder of a drive that removes the PIN.
+pub const RECOVERY_FILE: &str = "pin.reset";
+// what the recovery file signs with the deployment key, with the hash of the PIN file it removes
+const RECOVERY_MESSAGE: &[u8] = b"mediatimer pin reset";
+
+pub const MIN_PIN_LENGTH: usize = 4;
+pub const MAX_PIN_LENGTH: usize = 12;
+/// Wrong PINs allowed before setup is locked.
+pub const MAX_ATTEMPTS: u32 = 5;
+/// How long setup stays locked after too many wrong PINs.
+pub const LOCKOUT_SECONDS: u64 = 300;
+// makes guessing every PIN from a copy of the file slow
+const HASH_ROUNDS: u32 = 10_000;
+
+/// Whether `pin` can be used as a PIN: 4 to 12 digits.
+pub fn valid_pin(pin: &str) -> bool {
+    (MIN_PIN_LENGTH..=MAX_PIN_LENGTH).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+// every round of the hash but the last, which `hash_pin` and `pin_matches` finish
+fn unfinished_hash(salt: &str, pin: &str) -> Vec<u8> {
+    let mut hash = pin.as_bytes().to_vec();
+    for _ in 1..HASH_ROUNDS {
+        hash = sign(salt.as_bytes(), &hash).into_bytes();
+    }
+    hash
+}
+
+fn hash_pin(salt: &str, pin: &str) -> String {
+    sign(salt.as_bytes(), &unfinished_hash(salt, pin))
+}
+
+// compares in constant time, so the time taken gives nothing away about the stored hash
+fn pin_matches(salt: &str, pin: &str, hash: &str) -> bool {
+    verify(salt.as_bytes(), &unfinished_hash(salt, pin), hash)
+}
+
+fn new_salt() -> Result<String, Box<dyn Error>> {
+    let mut salt = [0u8; 16];
+    fs::File::open("/dev/urandom")?.read_exact(&mut salt)?;
+    Ok(salt.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Seconds since 1970, which the lockout is measured in.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0)
+}
+
+fn remove_if_present(path: &Path) -> Result<(), Box<dyn Error>> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(())
+    }
+}
+
+/// What happened when a PIN was entered.
+#[derive(Debug, PartialEq)]
+pub enum Attempt {
+    Unlocked,
+    Wrong { left: u32 },
+    LockedOut { seconds: u64 }
+}
+
+/// The PIN that protects the setup screens, and the lockout after wrong PINs. Both are kept in the
+/// config folder, so the lockout survives restarting mediatimer.
+#[derive(Debug, Clone)]
+pub struct PinStore {
+    dir: PathBuf
+}
+
+impl Default for PinStore {
+    fn default() -> Self {
+        let vars = env_file_path();
+        PinStore::in_dir(vars.parent().unwrap_or(Path::new("/")))
+    }
+}
+
+impl PinStore {
+    pub fn in_dir(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf()
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.dir.join(PIN_FILE).is_file()
+    }
+
+    /// Replaces the PIN and forgets any wrong PINs entered before.
+    pub fn set(&self, pin: &str) -> Result<(), Box<dyn Error>> {
+        if !valid_pin(pin) {
+            return Err(format!("A PIN must be {} to {} digits", MIN_PIN_LENGTH, MAX_PIN_LENGTH).into());
+        }
+        let salt = new_salt()?;
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(PIN_FILE);
+        fs::write(&path, format!("{}:{}\n", salt, hash_pin(&salt, pin)))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        remove_if_present(&self.dir.join(LOCKOUT_FILE))
+    }
+
+    /// What a recovery file for the current PIN signs, or None without a PIN.
+    pub fn recovery_message(&self) -> Option<Vec<u8>> {
+        fs::read_to_string(self.dir.join(PIN_FILE)).ok().map(|stored| recovery_message(&stored))
+    }
+
+    /// Removes the PIN, which leaves the setup screens open to anyone.
+    pub fn clear(&self) -> Result<(), Box<dyn Error>> {
+        remove_if_present(&self.dir.join(PIN_FILE))?;
+        remove_if_present(&self.dir.join(LOCKOUT_FILE))
+    }
+
+    // the wrong PINs so far and when the lockout ends
+    fn lockout(&self) -> (u32, u64) {
+        let Ok(contents) = fs::read_to_string(self.dir.join(LOCKOUT_FILE)) else {
+            return (0, 0);
+        };
+        let mut values = contents.split_whitespace();
+        let failures = values.next().and_then(|value| value.parse().ok()).unwrap_or(0);
+        let until = values.next().and_then(|value| value.parse().ok()).unwrap_or(0);
+        (failures, until)
+    }
+
+    fn save_lockout(&self, failures: u32, until: u64) -> Result<(), Box<dyn Error>> {
+        fs::write(self.dir.join(LOCKOUT_FILE), format!("{} {}\n", failures, until))?;
+        Ok(())
+    }
+
+    /// How many seconds are left of a lockout at `now`, if there is one.
+    pub fn locked_for(&self, now: u64) -> Option<u64> {
+        let (_, until) = self.lockout();
+        (until > now).then(|| until - now)
+    }
+
+    /// Checks `pin` at `now`. After `MAX_ATTEMPTS` wrong PINs in a row no PIN is accepted for
+    /// `LOCKOUT_SECONDS`.
+    pub fn check(&self, pin: &str, now: u64) -> Result<Attempt, Box<dyn Error>> {
+        if let Some(seconds) = self.locked_for(now) {
+            return Ok(Attempt::LockedOut { seconds });
+        }
+        let stored = match fs::read_to_string(self.dir.join(PIN_FILE)) {
+            Ok(stored) => stored,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Attempt::Unlocked),
+            Err(e) => return Err(e.into())
+        };
+        // a damaged PIN file matches nothing, so it can only be removed with a recovery file
+        let correct = stored.trim().split_once(':')
+            .is_some_and(|(salt, hash)| pin_matches(salt, pin, hash));
+        if correct {
+            remove_if_present(&self.dir.join(LOCKOUT_FILE))?;
+            return Ok(Attempt::Unlocked);
+        }
+
+        let (failures, _) = self.lockout();
+        let failures = failures + 1;
+        if failures >= MAX_ATTEMPTS {
+            self.save_lockout(0, now + LOCKOUT_SECONDS)?;
+            Ok(Attempt::LockedOut { seconds: LOCKOUT_SECONDS })
+        } else {
+            self.save_lockout(failures, 0)?;
+            Ok(Attempt::Wrong { left: MAX_ATTEMPTS - failures })
+        }
+    }
+}
+
+/// What a recovery file for the PIN file `stored` signs. Setting a PIN always picks a new salt,
+/// so once a recovery file has removed a PIN it cannot remove the next one.
+pub fn recovery_message(stored: &str) -> Vec<u8> {
+    let digest: String = Sha256::digest(stored.trim().as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+    [RECOVERY_MESSAGE, b" ", digest.as_bytes()].concat()
+}
+
+/// The recovery file on the first drive whose signature of `message` matches `key`.
+pub fn find_recovery(drives: &[Drive], key: &[u8], message: &[u8]) -> Option<PathBuf> {
+    drives.iter()
+        .filter_map(|drive| drive.mountpoint.as_ref())
+        .map(|mountpoint| mountpoint.join(PROVISION_DIR).join(RECOVERY_FILE))
+        .find(|file| fs::read_to_string(file).is_ok_and(|signature| verify(key, message, &signature)))
+}
+
+/// Writes a recovery file into `dir` that removes the PIN `message` was made from, signed with
+/// `key`. It only works once, but should be kept as safely as the key until it is used.
+pub fn write_recovery(dir: &Path, key: &[u8], message: &[u8]) -> Result<PathBuf, Box<dyn Error>> {
+    let file = dir.join(RECOVERY_FILE);
+    fs::create_dir_all(dir)?;
+    fs::write(&file, format!("{}\n", sign(key, message)))?;
+    Ok(file)
+}
+
+/// Asks for the PIN before the configuration can be changed.
+pub struct LockWidget {
+    should_exit: bool,
+    store: PinStore,
+    entered: String,
+    message: Option<String>,
+    // the PIN was removed with a recovery file, and any key continues
+    recovered: bool,
+    unlocked: bool,
+    help: bool
+}
+
+impl LockWidget {
+    pub fn new(store: PinStore) -> Self {
+        Self {
+            should_exit: false,
+            store,
+            entered: String::new(),
+            message: None,
+            recovered: false,
+            unlocked: false,
+            help: false
+        }
+    }
+
+    /// Returns true when the PIN was entered or removed, and false to stay read only.
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<bool, Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            // keep the lockout counting down while waiting for a key
+            if event::poll(Duration::from_secs(1))? && let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            }
+        }
+        Ok(self.unlocked)
+    }
+
+    fn keymap(&self) -> Keymap {
+        let keymap = if self.recovered {
+            // any other key continues
+            Keymap::default()
+        } else {
+            Keymap::default()
+                .bind(&[KeyCode::Enter], Action::Unlock)
+                .bind(&[KeyCode::Backspace], Action::DeleteChar)
+                .bind(&[KeyCode::Char('r')], Action::Recover)
+                .bind(&[KeyCode::Esc, KeyCode::Char('q')], Action::Back)
+        };
+        keymap.bind(HELP_KEYS, Action::Help)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match self.keymap().action(key.code) {
+            Some(Action::Unlock) => self.unlock(),
+            Some(Action::DeleteChar) => {
+                self.entered.pop();
+            },
+            Some(Action::Recover) => self.recover(),
+            Some(Action::Back) => self.should_exit = true,
+            Some(Action::Help) => self.help = true,
+            _ if self.recovered => {
+                self.unlocked = true;
+                self.should_exit = true;
+            },
+            _ => if let KeyCode::Char(c) = key.code && c.is_ascii_digit() && self.entered.len() < MAX_PIN_LENGTH {
+                self.entered.push(c);
+            }
+        }
+    }
+
+    fn unlock(&mut self) {
+        let attempt = self.store.check(&self.entered, now());
+        self.entered.clear();
+        match attempt {
+            Ok(Attempt::Unlocked) => {
+                logi!("Setup unlocked with the PIN");
+                self.unlocked = true;
+                self.should_exit = true;
+            },
+            Ok(Attempt::Wrong { left }) => {
+                logw!("Wrong PIN entered, {} attempts left", left);
+                self.message = Some(tr!("lock.wrong", count = left));
+            },
+            // the countdown is shown instead
+            Ok(Attempt::LockedOut { seconds }) => {
+                logw!("Setup locked for {} seconds after too many wrong PINs", seconds);
+                self.message = None;
+            },
+            Err(e) => {
+                loge!("Could not check the PIN: {}", e);
+                self.message = Some(tr!("lock.check_failed", error = e));
+            }
+        }
+    }
+
+    fn recover(&mut self) {
+        let drives = identify_mounted_drives(&SystemRunner).unwrap_or_else(|e| {
+            logw!("No storage devices identified, Error: {}", e);
+            Vec::new()
+        });
+        let found = read_key().zip(self.store.recovery_message())
+            .and_then(|(key, message)| find_recovery(&drives, &key, &message));
+        let Some(file) = found else {
+            self.message = Some(tr!("lock.no_recovery").to_string());
+            return;
+        };
+        match self.store.clear() {
+            Ok(()) => {
+                logw!("PIN removed with the recovery file {}", file.display());
+                self.recovered = true;
+                self.message = Some(tr!("lock.recovered", path = file.display()));
+            },
+            Err(e) => {
+                loge!("Could not remove the PIN: {}", e);
+                self.message = Some(tr!("lock.check_failed", error = e));
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+        if !self.recovered {
+            lines.push(Line::from(tr!("lock.prompt")).bold());
+            lines.push(Line::from(""));
+            lines.push(Line::from("● ".repeat(self.entered.len())));
+            lines.push(Line::from(""));
+        }
+        if let Some(seconds) = self.store.locked_for(now()) {
+            let time = format!("{}:{:02}", seconds / 60, seconds % 60);
+            lines.push(Line::from(tr!("lock.locked_out", time = time)));
+        } else if let Some(message) = self.message.as_ref() {
+            lines.push(Line::from(message.as_str()));
+        }
+        if !self.recovered {
+            lines.push(Line::from(""));
+            lines.push(Line::from(tr!("lock.read_only")).italic());
+        }
+        lines
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("lock.header"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let text = if self.recovered {
+            tr!("provision.continue_hint")
+        } else {
+            tr!("lock.footer")
+        };
+        Paragraph::new(text)
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_text(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(tr!("lock.title")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .padding(Padding::uniform(4))
+            .bg(theme().normal_row_bg);
+
+        Paragraph::new(self.lines())
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .fg(theme().text_fg)
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut LockWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        LockWidget::render_header(header_area, buf);
+        self.render_footer(footer_area, buf);
+        self.render_text(main_area, buf);
+
+        if self.help {
+            self.keymap().render_help(tr!("help.lock"), area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mount::tests::drive;
+    use tempfile::tempdir;
+
+    const KEY: &[u8] = b"deployment secret";
+
+    #[test]
+    fn check_valid_pin() {
+        assert!(valid_pin("1234"));
+        assert!(valid_pin("123456789012"));
+        assert!(!valid_pin("123"));
+        assert!(!valid_pin("1234567890123"));
+        assert!(!valid_pin("12a4"));
+        assert!(!valid_pin("١٢٣٤"));
+    }
+
+    #[test]
+    fn check_pin() {
+        let dir = tempdir().unwrap();
+        let store = PinStore::in_dir(dir.path());
+        assert!(!store.is_set());
+        // without a PIN anything unlocks
+        assert_eq!(store.check("", 0).unwrap(), Attempt::Unlocked);
+
+        store.set("2468").unwrap();
+        assert!(store.is_set());
+        let stored = fs::read_to_string(dir.path().join(PIN_FILE)).unwrap();
+        // only the salt and the hash are kept
+        let (salt, hash) = stored.trim().split_once(':').unwrap();
+        assert_eq!(hash, hash_pin(salt, "2468"));
+        assert!(pin_matches(salt, "2468", hash));
+        assert!(!pin_matches(salt, "2469", hash));
+        assert_eq!(store.check("1357", 0).unwrap(), Attempt::Wrong { left: MAX_ATTEMPTS - 1 });
+        assert_eq!(store.check("2468", 0).unwrap(), Attempt::Unlocked);
+        assert!(store.set("12").is_err());
+
+        store.clear().unwrap();
+        assert!(!store.is_set());
+        store.clear().unwrap();
+    }
+
+    #[test]
+    fn check_lockout() {
+        let dir = tempdir().unwrap();
+        let store = PinStore::in_dir(dir.path());
+        store.set("2468").unwrap();
+
+        // a correct PIN starts the count again
+        store.check("0000", 100).unwrap();
+        store.check("2468", 100).unwrap();
+        for left in (1..MAX_ATTEMPTS).rev() {
+            assert_eq!(store.check("0000", 100).unwrap(), Attempt::Wrong { left });
+        }
+        assert_eq!(store.check("0000", 100).unwrap(), Attempt::LockedOut { seconds: LOCKOUT_SECONDS });
+        // even the right PIN is refused until the lockout ends
+        assert_eq!(store.check("2468", 160).unwrap(), Attempt::LockedOut { seconds: LOCKOUT_SECONDS - 60 });
+        assert_eq!(store.locked_for(100 + LOCKOUT_SECONDS), None);
+        assert_eq!(store.check("2468", 100 + LOCKOUT_SECONDS).unwrap(), Attempt::Unlocked);
+
+        // setting a new PIN ends a lockout
+        for _ in 0..MAX_ATTEMPTS {
+            store.check("0000", 1000).unwrap();
+        }
+        assert!(store.locked_for(1000).is_some());
+        store.set("1357").unwrap();
+        assert_eq!(store.check("1357", 1000).unwrap(), Attempt::Unlocked);
+    }
+
+    #[test]
+    fn check_find_recovery() {
+        let dir = tempdir().unwrap();
+        let store = PinStore::in_dir(&dir.path().join("config"));
+        assert_eq!(store.recovery_message(), None);
+        store.set("2468").unwrap();
+        let message = store.recovery_message().unwrap();
+        let stick = dir.path().join("STICK");
+        write_recovery(&stick.join(PROVISION_DIR), KEY, &message).unwrap();
+        let at = |mountpoint: PathBuf| drive("1234-ABCD", None, &mountpoint);
+
+        let drives = [at(dir.path().join("OTHER")), at(stick.clone())];
+        assert_eq!(find_recovery(&drives, KEY, &message), Some(stick.join(PROVISION_DIR).join(RECOVERY_FILE)));
+        // signed for a different deployment
+        assert_eq!(find_recovery(&drives, b"another secret", &message), None);
+        assert_eq!(find_recovery(&drives[..1], KEY, &message), None);
+
+        // once used, it does not remove the next PIN, even the same one
+        store.clear().unwrap();
+        store.set("2468").unwrap();
+        assert_eq!(find_recovery(&drives, KEY, &store.recovery_message().unwrap()), None);
+    }
+}
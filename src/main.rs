@@ -6,6 +6,7 @@ use std::{
     io::Error as IoError,
     error::Error,
     path::{
+        Path,
         PathBuf,
     },
     process::{
@@ -15,6 +16,7 @@ use std::{
 use regex::Regex;
 use ratatui::{
     prelude::CrosstermBackend,
+    DefaultTerminal
 };
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -25,23 +27,21 @@ use strum::{
     Display,
     AsRefStr
 };
+use derive_setters::Setters;
 
 use log::{info, warn, error};
 
 mod proctype;
-use crate::proctype::ProcTypeWidget;
 
 mod web;
-use crate::web::WebWidget;
 
 mod fileselect;
-use crate::fileselect::FileSelectWidget;
 
 mod autoloop;
-use crate::autoloop::AutoloopWidget;
+
+mod slideshow;
 
 mod advanced_schedule;
-use crate::advanced_schedule::AdvancedScheduleWidget;
 
 mod landing;
 use crate::landing::LandingWidget;
@@ -49,6 +49,9 @@ use crate::landing::LandingWidget;
 mod loading;
 use crate::loading::LoadingWidget;
 
+mod media_check;
+use crate::media_check::MediaCheckWidget;
+
 mod loggers;
 use crate::loggers::{
     setup_logger
@@ -57,18 +60,53 @@ use crate::loggers::{
 mod mount;
 use crate::mount::{
     match_mountpoint,
-    identify_mounted_drives
+    identify_mounted_drives,
+    resolve_media
+};
+
+mod runner;
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
 };
 
 mod styles;
+mod i18n;
 mod areas;
+mod mouse;
+mod keymap;
+mod hotplug;
+mod eject;
+mod drives;
+mod probe;
+mod capabilities;
+mod transcode;
+mod library;
 
 mod schedule;
 
-use crate::schedule::timings::{
-    TimingsWidget
+mod supervisor;
+mod player;
+mod watchdog;
+mod status;
+mod provision;
+mod wizard;
+mod review;
+mod dashboard;
+mod cli;
+mod lock;
+use crate::lock::{
+    LockWidget,
+    PinStore
+};
+use crate::dashboard::DashboardWidget;
+use crate::wizard::Wizard;
+use crate::provision::{
+    ProvisionWidget,
+    find_provision
 };
 
+
 #[derive(Debug, PartialEq, Display,Clone, AsRefStr)]
 pub enum Model {
     Eco,
@@ -86,6 +124,40 @@ pub enum ProcType {
     Executable,
 }
 
+/// The program used to play Video and Audio tasks.
+#[derive(Debug, Display, PartialEq, AsRefStr, Clone)]
+pub enum MediaPlayer {
+    Ffplay,
+    Mpv,
+    Vlc
+}
+
+/// The order slides are shown in.
+#[derive(Debug, Display, PartialEq, AsRefStr, Clone)]
+pub enum SlideOrder {
+    Name,
+    Modified,
+    Shuffle
+}
+
+/// Slideshow settings other than the delay, which predates them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideshowOptions {
+    pub order: SlideOrder,
+    pub recursive: bool,
+    pub skip_non_images: bool
+}
+
+impl Default for SlideshowOptions {
+    fn default() -> Self {
+        Self {
+            order: SlideOrder::Name,
+            recursive: false,
+            skip_non_images: true
+        }
+    }
+}
+
 #[derive(Debug, Display, PartialEq)]
 pub enum Autoloop {
     Yes,
@@ -169,7 +241,8 @@ pub fn to_weekday(value: String, day: Weekday) -> Result<Weekday, Box<dyn Error>
 /// The Task struct is the main set of instructions that are written out into an env file to be 
 /// interpreted in future by the init program.
 
-#[derive( Debug)]
+#[derive( Debug, Setters)]
+#[setters(generate = false)]
 struct Task {
     proc_type: ProcType,
     auto_loop: Autoloop,
@@ -179,6 +252,10 @@ struct Task {
     slide_delay: u32,
     url: String,
     uuid: String,
+    #[setters(generate)]
+    media_player: MediaPlayer,
+    #[setters(generate)]
+    slideshow: SlideshowOptions,
 }
 
 impl Task {
@@ -191,7 +268,9 @@ impl Task {
             file,
             slide_delay,
             url,
-            uuid
+            uuid,
+            media_player: MediaPlayer::Ffplay,
+            slideshow: SlideshowOptions::default()
         }
     }
 }
@@ -261,12 +340,17 @@ fn write_task(task: Task) -> Result<(), Box<dyn Error>> {
         // write uuid
         writeln!(file, "MT_UUID=\"{}\"", task.uuid)?;
 
-        // write slide delay
-
+        // write slideshow settings
         writeln!(file, "MT_SLIDE_DELAY=\"{}\"", task.slide_delay)?;
+        writeln!(file, "MT_SLIDE_ORDER=\"{}\"", task.slideshow.order.to_string().to_lowercase())?;
+        writeln!(file, "MT_SLIDE_RECURSIVE=\"{}\"", task.slideshow.recursive)?;
+        writeln!(file, "MT_SLIDE_SKIP=\"{}\"", task.slideshow.skip_non_images)?;
 
         // write url
         writeln!(file, "MT_URL=\"{}\"", task.url)?;
+
+        // write the player used for video and audio
+        writeln!(file, "MT_PLAYER=\"{}\"", task.media_player.to_string().to_lowercase())?;
     } else {
         loge!("Could not find home directory");
         IoError::other("Could not find home directory");
@@ -274,6 +358,17 @@ fn write_task(task: Task) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// The label of the drive holding `file`, the {label} in /media/{username}/{label}/... Its
+/// components are /, media, {username} and then the label, so the label is the fourth and not
+/// the third, which is the username.
+fn drive_label<'a>(file: &'a Path, username: &str) -> Option<&'a str> {
+    file.strip_prefix(Path::new("/media").join(username)).ok()?
+        .components()
+        .next()?
+        .as_os_str()
+        .to_str()
+}
+
 fn fresh_uuid(mut uuid: String, username: &str, file: &PathBuf) -> Result<String, Box<dyn Error>> {
     // if file contains /media/{username}
     let media_point = format!("/media/{}", username); 
@@ -282,40 +377,21 @@ fn fresh_uuid(mut uuid: String, username: &str, file: &PathBuf) -> Result<String
             // check uuid against file path
             // This passes only the device label in /media/{username}/{label} as this device has 
             // recently been mounted
-            if let Some(item) = file.components().nth(2) {
-                if let Some(used_device_label) = item.as_os_str().to_str() {
-                    uuid = match_mountpoint(used_device_label)?
-                }
-            } 
+            if let Some(used_device_label) = drive_label(file, username) {
+                uuid = match_mountpoint(&SystemRunner, used_device_label)?
+            }
+        } else {
+            // files kept on the device, such as the media library, are not on a drive
+            uuid.clear();
         }
     }
     Ok(uuid)
 }
 
-/// Before the program starts, it unmounts and remounts any usb drives.
-/// This is  called in order to unmount and remount any usbs using the naming conventions
-/// that the mediatimer_init uses. The mount points for usb drives must be standardised in
-/// order for this program to work. The program is designed this way so that it can be utilised 
-/// by non-technical users.
-fn main() -> Result<(), Box<dyn Error>> {
-
-    // issue command to pause mediatimer_init
-    // systemctl --user stop mediatimer_init.service
-    let _stop_mediatimer_init = Command::new("systemctl")
-        .arg("--user")
-        .arg("stop")
-        .arg("mediatimer_init.service")
-        .output()?;
-
-    if let Err(e) = setup_logger() {
-        loge!("Logger failed to initialise: {}", e);
-    }
-    logi!("Initialising");
-    logi!("Loggers initialised");
-
-
-    // Preset model to "pro" version so that all features are enabled if the model details 
-    // cannot be found
+/// Reads the device model from the AdaptableOS model file.
+/// Preset model to "pro" version so that all features are enabled if the model details 
+/// cannot be found
+fn read_model() -> Model {
     let mut model: Model = Model::Pro;
     // read model type
     if let Ok(model_name) = fs::read_to_string("/etc/adaptableos/MODEL") {
@@ -327,25 +403,41 @@ fn main() -> Result<(), Box<dyn Error>> {
     } else {
         logw!("No Adaptable model set at /etc/adaptableos/MODEL. Default model Pro will be used.");
     }
-    let selected_model = format!("Model selected: {}", model);
-    logi!("Media Timer model: {}", &selected_model);
+    model
+}
 
+/// Where usb drives are mounted, each under its own label.
+fn media_root() -> PathBuf {
+    let username = whoami::username();
+    ["/media/", &username].iter().collect()
+}
 
-    // Find and load any existing config for the user
+/// Where media kept on the device itself is stored.
+fn local_media_dir() -> PathBuf {
     let username = whoami::username();
-    let env_dir_path: PathBuf =["/home/", &username, ".mediatimer_config/vars"].iter().collect();
+    ["/home/", &username, "mediatimer_media"].iter().collect()
+}
 
+/// The env file that the Task is written to and read back from.
+fn env_file_path() -> PathBuf {
+    let username = whoami::username();
+    ["/home/", &username, ".mediatimer_config/vars"].iter().collect()
+}
 
-    // mounts all of the drives automatically using udisksctl
-    let identified_drives = identify_mounted_drives();
-    let mut mounted_drives = Vec::new();
-    let _ = match identified_drives {
-        Ok(drives) => mounted_drives = drives,
-        Err(e) => {
-            logw!("No storage devices identified, Error: {}", e);
-        }
-    };
+/// Loads any existing config for the user. Keys that are missing from the env file keep their
+/// default values.
+fn load_task(env_dir_path: &Path) -> Result<Task, Box<dyn Error>> {
+    if !env_dir_path.exists() {
+        return parse_task(std::iter::empty());
+    }
+    if dotenvy::from_path_override(env_dir_path).is_err() {
+        loge!("Cannot find env vars at path: {}", env_dir_path.display());
+    }
+    parse_task(env::vars())
+}
 
+/// Builds a Task from MT_* keys and values, as found in the env file.
+fn parse_task(vars: impl IntoIterator<Item = (String, String)>) -> Result<Task, Box<dyn Error>> {
     // set up the config vars
     let mut file = PathBuf::new();
     let mut uuid = String::new();
@@ -354,6 +446,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut proc_type = ProcType::Video;
     let mut auto_loop = Autoloop::Yes;
     let mut schedule = AdvancedSchedule::No;
+    let mut media_player = MediaPlayer::Ffplay;
+    let mut slideshow = SlideshowOptions::default();
     let mut timings: Timings = Vec::with_capacity(7);
     let mut monday: Weekday = Weekday::Monday(Vec::with_capacity(2));
     let mut tuesday: Weekday = Weekday::Tuesday(Vec::with_capacity(2));
@@ -363,12 +457,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut saturday: Weekday = Weekday::Saturday(Vec::with_capacity(2));
     let mut sunday: Weekday = Weekday::Sunday(Vec::with_capacity(2));
 
-    if env_dir_path.exists() {
-        if dotenvy::from_path_override(env_dir_path.as_path()).is_err() {
-            loge!("Cannot find env vars at path: {}", env_dir_path.display());
-        }
+    let mut vars = vars.into_iter().peekable();
+    if vars.peek().is_some() {
         // parse the environmental vars
-        for (key, value) in env::vars() {
+        for (key, value) in vars {
             match key.as_str() {
                 // proctype should always be stored and checked lowercase
                 "MT_PROCTYPE" => proc_type = match value.to_lowercase().as_str() {
@@ -390,6 +482,18 @@ fn main() -> Result<(), Box<dyn Error>> {
                 "MT_UUID" => uuid.push_str(value.as_str()),
                 "MT_URL" => web_url.push_str(value.as_str()),
                 "MT_SLIDE_DELAY" => slide_delay = value.parse::<u32>()?,
+                "MT_SLIDE_ORDER" => slideshow.order = match value.as_str() {
+                    "modified" => SlideOrder::Modified,
+                    "shuffle" => SlideOrder::Shuffle,
+                    &_ => SlideOrder::Name
+                },
+                "MT_SLIDE_RECURSIVE" => slideshow.recursive = value == "true",
+                "MT_SLIDE_SKIP" => slideshow.skip_non_images = value != "false",
+                "MT_PLAYER" => media_player = match value.to_lowercase().as_str() {
+                    "mpv" => MediaPlayer::Mpv,
+                    "vlc" => MediaPlayer::Vlc,
+                    &_ => MediaPlayer::Ffplay
+                },
                 "MT_SCHEDULE" => schedule = match value.as_str() {
                     "true" => AdvancedSchedule::Yes,
                     "false" => AdvancedSchedule::No,
@@ -408,6 +512,95 @@ fn main() -> Result<(), Box<dyn Error>> {
 
         timings = vec![monday, tuesday, wednesday, thursday, friday, saturday, sunday]; 
     }
+    Ok(Task::new(proc_type, auto_loop, schedule, timings, file, slide_delay, web_url, uuid)
+        .media_player(media_player)
+        .slideshow(slideshow))
+}
+
+/// Before the program starts, it unmounts and remounts any usb drives.
+/// This is  called in order to unmount and remount any usbs using the naming conventions
+/// that the mediatimer_init uses. The mount points for usb drives must be standardised in
+/// order for this program to work. The program is designed this way so that it can be utilised 
+/// by non-technical users.
+fn main() -> Result<(), Box<dyn Error>> {
+
+    // `mediatimer run` plays the saved configuration instead of opening the setup screens
+    if env::args().nth(1).as_deref() == Some("run") {
+        if let Err(e) = setup_logger() {
+            loge!("Logger failed to initialise: {}", e);
+        }
+        logi!("Starting playback supervisor");
+        return supervisor::run();
+    }
+
+    // `mediatimer provision` applies a provisioning file from a drive without the setup screens
+    // and `mediatimer sign <folder>` signs one with this device's key
+    match env::args().nth(1).as_deref() {
+        Some("provision") => {
+            if let Err(e) = setup_logger() {
+                loge!("Logger failed to initialise: {}", e);
+            }
+            return provision::run();
+        },
+        Some("sign") => {
+            let dir = env::args().nth(2).ok_or("Usage: mediatimer sign <folder>")?;
+            return provision::sign_folder(Path::new(&dir));
+        },
+        _ => {}
+    }
+
+    // `mediatimer show|set|schedule|validate|apply|pin` configure the device from scripts
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|command| cli::COMMANDS.contains(&command.as_str())) {
+        if let Err(e) = setup_logger() {
+            loge!("Logger failed to initialise: {}", e);
+        }
+        std::process::exit(cli::run(&args));
+    }
+
+    if let Err(e) = setup_logger() {
+        loge!("Logger failed to initialise: {}", e);
+    }
+    logi!("Initialising");
+    logi!("Loggers initialised");
+
+    styles::load_theme();
+    i18n::load_locale();
+
+    let model = read_model();
+    let selected_model = format!("Model selected: {}", model);
+    logi!("Media Timer model: {}", &selected_model);
+
+
+    // Find and load any existing config for the user
+    let username = whoami::username();
+    let env_dir_path = env_file_path();
+
+
+    // mounts all of the drives automatically using udisksctl
+    let identified_drives = identify_mounted_drives(&SystemRunner);
+    let mut mounted_drives = Vec::new();
+    let _ = match identified_drives {
+        Ok(drives) => mounted_drives = drives,
+        Err(e) => {
+            logw!("No storage devices identified, Error: {}", e);
+        }
+    };
+
+    let mut task = load_task(&env_dir_path)?;
+
+    // a drive carrying a provisioning file can replace the configuration in one step
+    let provision = find_provision(&mounted_drives)
+        .map(|dir| ProvisionWidget::new(dir, &task));
+
+    // the drive holding the media may have been mounted under a different label since it was saved
+    let resolution = if task.file.as_os_str().is_empty() || task.proc_type == ProcType::Web {
+        None
+    } else {
+        let resolution = resolve_media(&task.file, &task.uuid, &media_root(), &mounted_drives);
+        task.file = resolution.path().to_path_buf();
+        Some(resolution)
+    };
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -415,46 +608,53 @@ fn main() -> Result<(), Box<dyn Error>> {
     let _backend = CrosstermBackend::new(stdout);
     let mut terminal = ratatui::init();
 
-    LandingWidget::new(model.clone()).run(&mut terminal)?;
-    // returns Ok(ProcType) e.g. Ok(ProcType::Video)
-
-    let proctype = ProcTypeWidget::new(model.clone(), proc_type).run(&mut terminal)?;
-    let can_be_dir: bool = matches!(&proctype, ProcType::Slideshow);
-
-    if proctype == ProcType::Web {
-        //returns Ok(String);
-        web_url = WebWidget::new(web_url, mounted_drives.clone())?.run(&mut terminal)?;
-    } else {
-        // return Ok(PathBuf)
-        let proctype_clone = proctype.clone();
-        file = FileSelectWidget::new(model, file, can_be_dir, proctype_clone, mounted_drives.clone())?.run(&mut terminal)?;
-
-        uuid = fresh_uuid(uuid, &username, &file)?; 
+    // looking at the dashboard leaves playback alone, it only stops once the user chooses to edit
+    // and, when a PIN is set, has entered it. Without the PIN the dashboard is read only.
+    let saved = env_dir_path.exists().then_some(&task);
+    let pin = PinStore::default();
+    loop {
+        if !DashboardWidget::new(model.clone(), saved, resolution.clone(), mounted_drives.clone()).run(&mut terminal)? {
+            return restore_terminal(&mut terminal);
+        }
+        LandingWidget::new(model.clone()).run(&mut terminal)?;
+        if !pin.is_set() || LockWidget::new(pin.clone()).run(&mut terminal)? {
+            break;
+        }
     }
 
-    let advanced_schedule = AdvancedScheduleWidget::new(schedule).run(&mut terminal)?;
+    // issue command to pause mediatimer_init
+    // systemctl --user stop mediatimer_init.service
+    let _stop_mediatimer_init = SystemRunner.output(Command::new("systemctl")
+        .arg("--user")
+        .arg("stop")
+        .arg("mediatimer_init.service"))?;
 
-    if advanced_schedule == AdvancedSchedule::Yes {
-        //returns Ok(Timings)
-        timings = TimingsWidget::new(timings, mounted_drives)?.run(&mut terminal)?;
+    if let Some(provision) = provision && provision.run(&mut terminal)? {
+        LoadingWidget::default().run(&mut terminal)?;
+        return restore_terminal(&mut terminal);
     }
 
-    let is_media_type: bool = matches!( &proctype, ProcType::Video | ProcType::Audio);
-
-    // return Ok(Autoloop) e.g. Ok(Autoloop::No)
-    if is_media_type && advanced_schedule == AdvancedSchedule::Yes {
-        auto_loop = AutoloopWidget::new(auto_loop).run(&mut terminal)?;
+    if let Some(resolution) = resolution {
+        MediaCheckWidget::new(resolution).run(&mut terminal)?;
     }
-    let task = Task::new(proctype, auto_loop, advanced_schedule, timings, file, slide_delay, web_url, uuid);
 
-    // write_task 
-    if let Err(e) = write_task(task) {
-        loge!("Error writing tasks to env file: {}", e);
+    // returns Ok(None) when setup is quit, which leaves the saved configuration as it was
+    match Wizard::new(model, username, mounted_drives, task).run(&mut terminal)? {
+        Some(task) => {
+            if let Err(e) = write_task(task) {
+                loge!("Error writing tasks to env file: {}", e);
+            }
+        },
+        None => logi!("Setup quit without saving")
     }
 
     // loading issues the command to enable the mediatimer_init service
     LoadingWidget::default().run(&mut terminal)?;
 
+    restore_terminal(&mut terminal)
+}
+
+fn restore_terminal(terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -462,19 +662,23 @@ fn main() -> Result<(), Box<dyn Error>> {
         DisableMouseCapture
     )?;
     terminal.show_cursor()?;
-
     Ok(())
 }
 
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::*;
     use std::path::Path;
     use std::io::{BufRead, BufReader};
     use tempfile::tempdir;
     use std::env;
 
+    /// A task from MT_* keys and values, as in the env file.
+    pub fn task(vars: &[(&str, &str)]) -> Task {
+        parse_task(vars.iter().map(|(key, value)| (key.to_string(), value.to_string()))).unwrap()
+    }
+
     #[test]
     fn check_to_weekday() {
         let weekday = to_weekday(String::new(), Weekday::Monday(Vec::new())).unwrap();
@@ -501,6 +705,16 @@ mod tests {
     }
 
 
+    #[test]
+    fn check_drive_label() {
+        assert_eq!(drive_label(Path::new("/media/pi/KINGSTON/videos/clip.mp4"), "pi"), Some("KINGSTON"));
+        assert_eq!(drive_label(Path::new("/media/pi/KINGSTON"), "pi"), Some("KINGSTON"));
+        // not the username, which is the third component
+        assert_eq!(drive_label(Path::new("/media/pi"), "pi"), None);
+        assert_eq!(drive_label(Path::new("/media/other/KINGSTON/clip.mp4"), "pi"), None);
+        assert_eq!(drive_label(Path::new("/home/pi/mediatimer_media/clip.mp4"), "pi"), None);
+    }
+
     #[test]
     fn test_write_task() {
         // Create a temporary directory to use as home directory for the test
@@ -0,0 +1,157 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect },
+    style::{
+        Stylize,
+    },
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, Padding, Paragraph,
+        Widget, Wrap
+    },
+    DefaultTerminal,
+};
+use ratatui::prelude::*;
+use std::error::Error;
+
+use crate::styles::theme;
+
+use crate::mount::Resolution;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+
+/// Shows whether the saved media could be found before setup starts.
+pub struct MediaCheckWidget {
+    resolution: Resolution,
+    should_exit: bool,
+    help: bool
+}
+
+impl MediaCheckWidget {
+    pub fn new (resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            should_exit: false,
+            help: false
+        }
+    }
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<(), Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            };
+        }
+        Ok(())
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::default()
+            .bind(&[KeyCode::Enter, KeyCode::Esc, KeyCode::Char('q')], Action::Continue)
+            .bind(HELP_KEYS, Action::Help)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match MediaCheckWidget::keymap().action(key.code) {
+            Some(Action::Continue) => self.should_exit = true,
+            Some(Action::Help) => self.help = true,
+            _ => {}
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        match &self.resolution {
+            Resolution::Found(path) => vec![
+                Line::from(tr!("media_check.found")).bold(),
+                Line::from(""),
+                Line::from(path.display().to_string()),
+            ],
+            Resolution::Relocated { from, to } => vec![
+                Line::from(tr!("media_check.relocated")).bold(),
+                Line::from(""),
+                Line::from(tr!("media_check.relocated_to")),
+                Line::from(to.display().to_string()),
+                Line::from(""),
+                Line::from(tr!("media_check.saved_as")),
+                Line::from(from.display().to_string()),
+            ],
+            Resolution::Missing(path) => vec![
+                Line::from(tr!("media_check.missing")).bold(),
+                Line::from(""),
+                Line::from(tr!("media_check.missing_at")),
+                Line::from(path.display().to_string()),
+                Line::from(""),
+                Line::from(tr!("media_check.missing_hint")),
+            ],
+        }
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Media Timer")
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("common.press_continue"))
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_text(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::raw(tr!("media_check.title")).centered();
+        let block = Block::new()
+            .title(title)
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .padding(Padding::uniform(4))
+            .bg(theme().normal_row_bg);
+
+        Paragraph::new(self.lines())
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .fg(theme().text_fg)
+            .render(
+                area,
+                buf
+            );
+    }
+}
+
+impl Widget for &mut MediaCheckWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        MediaCheckWidget::render_header(header_area, buf);
+        MediaCheckWidget::render_footer(footer_area, buf);
+        self.render_text(main_area, buf);
+
+        if self.help {
+            MediaCheckWidget::keymap().render_help(tr!("help.media_check"), area, buf);
+        }
+    }
+}
@@ -1,177 +1,381 @@
 use std:: {
-    process::{
-        Command,
-        Stdio
-    },
-    io::{
-        Error as IoError,
-        ErrorKind
-    },
-    path::PathBuf
+    process::Command,
+    io::Error as IoError,
+    path::{
+        Path,
+        PathBuf
+    }
 };
-use regex::Regex;
+use serde::Deserialize;
 use std::error::Error;
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+use crate::runner::CommandRunner;
+use crate::tr;
 
-enum Usb {
-    SDA1,
-    SDA2,
-    SDA3,
-    SDA4,
-    SDB1,
-    SDB2,
-    SDB3,
-    SDB4,
-    SDC1,
-    SDC2,
-    SDC3,
-    SDC4,
-    Unknown
+/// A removable drive holding a filesystem, as reported by lsblk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drive {
+    pub name: String,
+    pub path: PathBuf,
+    pub uuid: String,
+    pub label: Option<String>,
+    pub fstype: String,
+    pub size: String,
+    pub mountpoint: Option<PathBuf>
 }
 
-impl Usb {
-    fn as_device_path(&self) -> &'static str {
-        match self {
-            Usb::SDA1 => "/dev/sda1", 
-            Usb::SDA2 => "/dev/sda2", 
-            Usb::SDA3 => "/dev/sda3", 
-            Usb::SDA4 => "/dev/sda4",
-            Usb::SDB1 => "/dev/sdb1", 
-            Usb::SDB2 => "/dev/sdb2", 
-            Usb::SDB3 => "/dev/sdb3",
-            Usb::SDB4 => "/dev/sdb4",
-            Usb::SDC1 => "/dev/sdc1",
-            Usb::SDC2 => "/dev/sdc2",
-            Usb::SDC3 => "/dev/sdc3",
-            Usb::SDC4 => "/dev/sdc4",
-            Usb::Unknown => ""
+impl Drive {
+    /// A one line description for the file pickers, e.g. "KINGSTON (14.9G, vfat) at /media/pi/KINGSTON".
+    pub fn summary(&self) -> String {
+        let name = self.label.as_deref().unwrap_or(&self.name);
+        match &self.mountpoint {
+            Some(mountpoint) => tr!("mount.summary", name = name, size = self.size, fstype = self.fstype, mountpoint = mountpoint.display()),
+            None => tr!("mount.summary_unmounted", name = name, size = self.size, fstype = self.fstype)
         }
+    }
 
+    /// Whether `path` is on this drive.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.mountpoint.as_ref().is_some_and(|mountpoint| path.starts_with(mountpoint))
     }
 }
-pub fn identify_mounted_drives() -> Result<Vec<(PathBuf, String)>, Box<dyn Error>> {
-    let mut mounts = Vec::with_capacity(2);
-    // find out if any drives mounted, otherwise default to /home/username
-    let all_drives = Command::new("lsblk")
-        .arg("-l")
+
+#[derive(Deserialize)]
+struct Lsblk {
+    blockdevices: Vec<BlockDevice>
+}
+
+#[derive(Deserialize)]
+struct BlockDevice {
+    name: String,
+    path: Option<String>,
+    // older versions of lsblk print "1" and "0" rather than booleans
+    #[serde(default, deserialize_with = "flag")]
+    hotplug: bool,
+    uuid: Option<String>,
+    label: Option<String>,
+    fstype: Option<String>,
+    #[serde(default)]
+    mountpoints: Vec<Option<String>>,
+    size: Option<String>,
+    #[serde(default)]
+    children: Vec<BlockDevice>
+}
+
+fn flag<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Text(String)
+    }
+    Ok(match Option::<Flag>::deserialize(deserializer)? {
+        Some(Flag::Bool(value)) => value,
+        Some(Flag::Text(value)) => value == "1",
+        None => false
+    })
+}
+
+/// Reads the output of `lsblk -J -o NAME,PATH,HOTPLUG,UUID,LABEL,FSTYPE,MOUNTPOINTS,SIZE`.
+/// Every hotplugged device or partition that holds a filesystem is returned, whatever it is
+/// called, so memory cards and NVMe enclosures show up alongside USB sticks.
+pub fn parse_drives(json: &str) -> Result<Vec<Drive>, Box<dyn Error>> {
+    fn collect(devices: Vec<BlockDevice>, drives: &mut Vec<Drive>) {
+        for device in devices {
+            if let (true, Some(fstype), Some(uuid)) = (device.hotplug, &device.fstype, &device.uuid)
+                && fstype != "swap" {
+                drives.push(Drive {
+                    path: device.path.clone()
+                        .map_or_else(|| Path::new("/dev").join(&device.name), PathBuf::from),
+                    name: device.name.clone(),
+                    uuid: uuid.clone(),
+                    label: device.label.clone(),
+                    fstype: fstype.clone(),
+                    size: device.size.clone().unwrap_or_default(),
+                    mountpoint: device.mountpoints.iter().flatten().next().map(PathBuf::from)
+                });
+            }
+            collect(device.children, drives);
+        }
+    }
+    let lsblk: Lsblk = serde_json::from_str(json)?;
+    let mut drives = Vec::new();
+    collect(lsblk.blockdevices, &mut drives);
+    Ok(drives)
+}
+
+/// Reads the mount point from the output of `udisksctl mount`, which looks like
+/// "Mounted /dev/sda1 at /media/pi/MY STICK". Labels can contain spaces.
+pub fn parse_udisks_mount(output: &str) -> Option<PathBuf> {
+    let (_, mountpoint) = output.trim().split_once(" at ")?;
+    let mountpoint = mountpoint.trim_end_matches('.');
+    (!mountpoint.is_empty()).then(|| PathBuf::from(mountpoint))
+}
+
+fn list_drives(runner: &dyn CommandRunner) -> Result<Vec<Drive>, Box<dyn Error>> {
+    let output = runner.output(Command::new("lsblk")
+        .arg("-J")
         .arg("-o")
-        .arg("NAME,HOTPLUG,UUID")
-        .output()?;
-
-    let all_drives_string = String::from_utf8_lossy(&all_drives.stdout);
-    
-    let re = Regex::new(r"sd[a,b,c][1-4]")?;
-    for line in all_drives_string.lines() {
-        if re.is_match(line) {
-            let drive_info = line.split(' ')
-                .filter(|d| !d.is_empty() )
-                .collect::<Vec<_>>();
-                if drive_info[1] == "1" { 
-                    
-                    //To Remove:: have the thread sleep for one second as puppy umount sometimes fails
-                    //let one_second = Duration::from_millis(1000); 
-                    //thread::sleep(one_second);
-                    let drive = match drive_info[0] {
-                        "sda1" => Usb::SDA1,
-                        "sda2" => Usb::SDA2,
-                        "sda3" => Usb::SDA3,
-                        "sda4" => Usb::SDA4,
-                        "sdb1" => Usb::SDB1,
-                        "sdb2" => Usb::SDB2,
-                        "sdb3" => Usb::SDB3,
-                        "sdb4" => Usb::SDB4,
-                        "sdc1" => Usb::SDC1,
-                        "sdc2" => Usb::SDC2,
-                        "sdc3" => Usb::SDC3,
-                        "sdc4" => Usb::SDC4,
-                        &_ => Usb::Unknown
-
-                    };
-    
-                // check if device mounted
-                let mut udc_info = Command::new("udisksctl")
-                    .arg("info")
-                    .arg("-b")
-                    .arg(drive.as_device_path())
-                    .stdout(Stdio::piped())
-                    .spawn()?;
-
-                let pipe = udc_info.stdout.take().ok_or(IoError::new(ErrorKind::Other, "Could not take stdout"))?;
-
-                let udc_m_grep = Command::new("grep")
-                    .arg("MountPoints")
-                    .stdin(pipe)
-                    .stdout(Stdio::piped())
-                    .spawn()?;
-
-                let udc_mounted_output = udc_m_grep.wait_with_output()?;
-                let _ = udc_info.wait();
-
-
-                let udc_mounted_output = String::from_utf8_lossy(&udc_mounted_output.stdout);
-                
-                let mount_info = udc_mounted_output.split(" ")
-                    .map(|x| x.trim())
-                    .filter(|d| !d.is_empty() )
-                    .collect::<Vec<_>>();
-
-                // if the previous step has revealed that the partition is not mounted expect a 
-                // vector of length=1 with "MountPoints" contained within.
-                if mount_info.len() == 1 { 
-                    // mount the device
-                    let udc_output = Command::new("udisksctl")
-                        .arg("mount")
-                        .arg("-b")
-                        .arg(drive.as_device_path())
-                        .output()?;
-
-                    let udc_output = String::from_utf8_lossy(&udc_output.stdout);
-
-                    let mounted_drive_info = udc_output.split(" ")
-                        .map(|x| x.trim())
-                        .filter(|d| !d.is_empty())
-                        .collect::<Vec<_>>();
-        
-                    // this will be a vector with four parts
-
-                    if mounted_drive_info.len() == 4 && !mounted_drive_info[3].is_empty() {
-                        mounts.push((
-                                PathBuf::from(mounted_drive_info[3]),
-                                String::from(drive_info[2])
-                        ));
-                    }
-
-                } else if mount_info.len() == 2 {
-                    mounts.push((
-                        PathBuf::from(mount_info[1]),
-                        String::from(drive_info[2])
-                    ));
-                }
+        .arg("NAME,PATH,HOTPLUG,UUID,LABEL,FSTYPE,MOUNTPOINTS,SIZE"))?;
+    parse_drives(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Finds every removable drive, mounting any that are not mounted yet, and returns the ones
+/// that are now mounted.
+pub fn identify_mounted_drives(runner: &dyn CommandRunner) -> Result<Vec<Drive>, Box<dyn Error>> {
+    let mut mounts = Vec::with_capacity(2);
+    for mut drive in list_drives(runner)? {
+        if drive.mountpoint.is_none() {
+            let udc_output = runner.output(Command::new("udisksctl")
+                .arg("mount")
+                .arg("-b")
+                .arg(&drive.path))?;
+            drive.mountpoint = parse_udisks_mount(&String::from_utf8_lossy(&udc_output.stdout));
+            match &drive.mountpoint {
+                Some(mountpoint) => logi!("Mounted {} at {}", drive.path.display(), mountpoint.display()),
+                None => logw!("Could not mount {}", drive.path.display())
             }
         }
+        if drive.mountpoint.is_some() {
+            mounts.push(drive);
+        }
     }
     Ok(mounts)
 }
 
 // returns uuid string
-pub fn match_mountpoint(device_label: &str) -> Result<String, Box<dyn Error>> {
+pub fn match_mountpoint(runner: &dyn CommandRunner, device_label: &str) -> Result<String, Box<dyn Error>> {
+    list_drives(runner)?
+        .into_iter()
+        .find(|drive| drive.mountpoint.as_deref()
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == device_label))
+        .map(|drive| drive.uuid)
+        .ok_or_else(|| {
+            loge!("No mounted drive is labelled {}", device_label);
+            IoError::other("Could not match UUID").into()
+        })
+}
 
-    let all_drives = Command::new("lsblk")
-        .arg("-l")
-        .arg("-o")
-        .arg("NAME,HOTPLUG,UUID,MOUNTPOINT")
-        .output()?;
-
-    let all_drives_string = String::from_utf8_lossy(&all_drives.stdout);
-    
-    for line in all_drives_string.lines() {
-        if line.contains(device_label) {
-            // the device label matches so return the UUID
-            let drive_info = line.split(' ')
-                .filter(|d| !d.is_empty() )
-                .collect::<Vec<_>>();
-            // returns the UUID
-            return Ok(String::from(drive_info[2]));
+/// What became of the configured media file when the config was loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// The file is where it was saved.
+    Found(PathBuf),
+    /// The drive was mounted somewhere else and the file was found at the new mount point.
+    Relocated {
+        from: PathBuf,
+        to: PathBuf
+    },
+    /// The file could not be found on any mounted drive.
+    Missing(PathBuf)
+}
+
+impl Resolution {
+    /// The path that should be used for the task.
+    pub fn path(&self) -> &Path {
+        match self {
+            Resolution::Found(path) | Resolution::Missing(path) => path,
+            Resolution::Relocated { to, .. } => to
         }
     }
-    Err(Box::new(IoError::new(ErrorKind::Other, "Could not match UUID")))
+}
+
+/// Finds the media file again using the UUID of the drive it was saved on.
+/// Drives are mounted at `{media_root}/{label}`, so when the saved path no longer exists the part
+/// after the label is re-based onto wherever the drive with the same UUID is mounted now.
+pub fn resolve_media(file: &Path, uuid: &str, media_root: &Path, mounted_drives: &[Drive]) -> Resolution {
+    if file.exists() {
+        return Resolution::Found(file.to_path_buf());
+    }
+    if uuid.is_empty() {
+        return Resolution::Missing(file.to_path_buf());
+    }
+    // drop the old label, keeping the path of the file on the drive
+    let Ok(on_drive) = file.strip_prefix(media_root) else {
+        return Resolution::Missing(file.to_path_buf());
+    };
+    let relative: PathBuf = on_drive.components().skip(1).collect();
+
+    mounted_drives.iter()
+        .filter(|drive| drive.uuid == uuid)
+        .filter_map(|drive| drive.mountpoint.as_ref())
+        .map(|mountpoint| mountpoint.join(&relative))
+        .find(|candidate| candidate.exists())
+        .map_or_else(
+            || Resolution::Missing(file.to_path_buf()),
+            |to| Resolution::Relocated { from: file.to_path_buf(), to }
+        )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    use crate::runner::fake::FakeRunner;
+
+    /// A USB stick mounted at `mountpoint`.
+    pub fn drive(uuid: &str, label: Option<&str>, mountpoint: &Path) -> Drive {
+        Drive {
+            name: String::from("sdb1"),
+            path: PathBuf::from("/dev/sdb1"),
+            uuid: String::from(uuid),
+            label: label.map(String::from),
+            fstype: String::from("vfat"),
+            size: String::from("7.4G"),
+            mountpoint: Some(mountpoint.to_path_buf())
+        }
+    }
+
+    const LSBLK: &str = r#"{
+       "blockdevices": [
+          {"name":"mmcblk0", "path":"/dev/mmcblk0", "hotplug":false, "uuid":null, "label":null, "fstype":null, "mountpoints":[null], "size":"29.7G",
+             "children": [
+                {"name":"mmcblk0p1", "path":"/dev/mmcblk0p1", "hotplug":false, "uuid":"4EF3-1C2A", "label":"bootfs", "fstype":"vfat", "mountpoints":["/boot/firmware"], "size":"512M"},
+                {"name":"mmcblk0p2", "path":"/dev/mmcblk0p2", "hotplug":false, "uuid":"a1b2c3d4-0000", "label":"rootfs", "fstype":"ext4", "mountpoints":["/"], "size":"29.2G"}
+             ]
+          },
+          {"name":"sdd", "path":"/dev/sdd", "hotplug":true, "uuid":null, "label":null, "fstype":null, "mountpoints":[null], "size":"57.3G",
+             "children": [
+                {"name":"sdd5", "path":"/dev/sdd5", "hotplug":true, "uuid":"1234-ABCD", "label":"MY STICK", "fstype":"exfat", "mountpoints":["/media/pi/MY STICK"], "size":"57.3G"}
+             ]
+          },
+          {"name":"mmcblk1p1", "path":"/dev/mmcblk1p1", "hotplug":true, "uuid":"5678-EFGH", "label":null, "fstype":"vfat", "mountpoints":[null], "size":"7.4G"}
+       ]
+    }"#;
+
+    #[test]
+    fn test_parse_drives() {
+        let drives = parse_drives(LSBLK).unwrap();
+        assert_eq!(drives, vec![
+            Drive {
+                name: String::from("sdd5"),
+                path: PathBuf::from("/dev/sdd5"),
+                uuid: String::from("1234-ABCD"),
+                label: Some(String::from("MY STICK")),
+                fstype: String::from("exfat"),
+                size: String::from("57.3G"),
+                mountpoint: Some(PathBuf::from("/media/pi/MY STICK"))
+            },
+            Drive {
+                name: String::from("mmcblk1p1"),
+                path: PathBuf::from("/dev/mmcblk1p1"),
+                uuid: String::from("5678-EFGH"),
+                label: None,
+                fstype: String::from("vfat"),
+                size: String::from("7.4G"),
+                mountpoint: None
+            }
+        ]);
+        assert_eq!(drives[0].summary(), "MY STICK (57.3G, exfat) at /media/pi/MY STICK");
+        assert_eq!(drives[1].summary(), "mmcblk1p1 (7.4G, vfat) not mounted");
+    }
+
+    #[test]
+    fn test_parse_drives_old_lsblk() {
+        // util-linux before 2.37 prints hotplug as a string
+        let json = r#"{"blockdevices": [
+            {"name":"sde1", "path":"/dev/sde1", "hotplug":"1", "uuid":"AAAA-BBBB", "label":"USB", "fstype":"vfat", "mountpoints":[null], "size":"3.8G"},
+            {"name":"sda1", "path":"/dev/sda1", "hotplug":"0", "uuid":"CCCC-DDDD", "label":"DATA", "fstype":"ext4", "mountpoints":["/data"], "size":"1T"}
+        ]}"#;
+        let drives = parse_drives(json).unwrap();
+        assert_eq!(drives.len(), 1);
+        assert_eq!(drives[0].name, "sde1");
+    }
+
+    #[test]
+    fn test_identify_mounted_drives() {
+        let runner = FakeRunner::new()
+            .respond("lsblk", LSBLK)
+            .respond("udisksctl", "Mounted /dev/mmcblk1p1 at /media/pi/SD CARD\n");
+
+        let drives = identify_mounted_drives(&runner).unwrap();
+        assert_eq!(runner.calls(), vec![
+            "lsblk -J -o NAME,PATH,HOTPLUG,UUID,LABEL,FSTYPE,MOUNTPOINTS,SIZE",
+            "udisksctl mount -b /dev/mmcblk1p1"
+        ]);
+        let mountpoints: Vec<_> = drives.iter().filter_map(|drive| drive.mountpoint.clone()).collect();
+        assert_eq!(mountpoints, vec![PathBuf::from("/media/pi/MY STICK"), PathBuf::from("/media/pi/SD CARD")]);
+    }
+
+    #[test]
+    fn test_identify_mounted_drives_mount_fails() {
+        let runner = FakeRunner::new()
+            .respond("lsblk", LSBLK)
+            .respond_with("udisksctl", 1, "", "Error mounting /dev/mmcblk1p1: wrong fs type");
+
+        let drives = identify_mounted_drives(&runner).unwrap();
+        assert_eq!(drives.len(), 1);
+        assert_eq!(drives[0].label.as_deref(), Some("MY STICK"));
+    }
+
+    #[test]
+    fn test_match_mountpoint() {
+        let runner = FakeRunner::new()
+            .respond("lsblk", LSBLK)
+            .respond("lsblk", LSBLK);
+
+        assert_eq!(match_mountpoint(&runner, "MY STICK").unwrap(), "1234-ABCD");
+        // only the last part of the mount point counts
+        assert!(match_mountpoint(&runner, "MY").is_err());
+    }
+
+    #[test]
+    fn test_parse_udisks_mount() {
+        assert_eq!(parse_udisks_mount("Mounted /dev/sdd5 at /media/pi/MY STICK\n"), Some(PathBuf::from("/media/pi/MY STICK")));
+        assert_eq!(parse_udisks_mount("Mounted /dev/sda1 at /media/pi/USB."), Some(PathBuf::from("/media/pi/USB")));
+        assert_eq!(parse_udisks_mount(""), None);
+    }
+
+    #[test]
+    fn test_resolve_found() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("OLD/films/intro.mp4");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, b"").unwrap();
+
+        assert_eq!(resolve_media(&file, "1234-ABCD", dir.path(), &[]), Resolution::Found(file));
+    }
+
+    #[test]
+    fn test_resolve_relocated() {
+        let dir = tempdir().unwrap();
+        let old = dir.path().join("OLD/films/intro.mp4");
+        let new = dir.path().join("NEW LABEL/films/intro.mp4");
+        fs::create_dir_all(new.parent().unwrap()).unwrap();
+        fs::write(&new, b"").unwrap();
+        let drives = vec![
+            drive("9999-0000", None, &dir.path().join("OTHER")),
+            drive("1234-ABCD", None, &dir.path().join("NEW LABEL"))
+        ];
+
+        let resolution = resolve_media(&old, "1234-ABCD", dir.path(), &drives);
+        assert_eq!(resolution, Resolution::Relocated { from: old, to: new.clone() });
+        assert_eq!(resolution.path(), new);
+    }
+
+    #[test]
+    fn test_resolve_missing() {
+        let dir = tempdir().unwrap();
+        let old = dir.path().join("OLD/films/intro.mp4");
+        fs::create_dir_all(dir.path().join("NEW/films")).unwrap();
+        let drives = vec![drive("1234-ABCD", None, &dir.path().join("NEW"))];
+
+        // the drive is there but the file is not
+        assert_eq!(resolve_media(&old, "1234-ABCD", dir.path(), &drives), Resolution::Missing(old.clone()));
+        // a different drive is mounted
+        assert_eq!(resolve_media(&old, "5678-EFGH", dir.path(), &drives), Resolution::Missing(old.clone()));
+        // no uuid was saved
+        assert_eq!(resolve_media(&old, "", dir.path(), &drives), Resolution::Missing(old));
+    }
 }
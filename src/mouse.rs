@@ -0,0 +1,229 @@
+use ratatui::{
+    crossterm::event::{
+        KeyCode,
+        MouseButton,
+        MouseEvent,
+        MouseEventKind
+    },
+    layout::{
+        Position,
+        Rect
+    },
+    style::Stylize,
+    text::{
+        Line,
+        Span
+    },
+    widgets::{
+        Block,
+        Borders,
+        ListState,
+        Padding
+    }
+};
+use ratatui_explorer::FileExplorer;
+
+use crate::keymap::{
+    Action,
+    Keymap
+};
+use crate::tr;
+
+/// What a mouse event asks a screen to do.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Click {
+    /// The same as pressing this key.
+    Key(KeyCode),
+    /// Highlight this row of the list.
+    Select(usize)
+}
+
+/// Where the parts of a screen that respond to the mouse were last drawn.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Targets {
+    pub screen: Rect,
+    /// The rows of the list that takes clicks, inside its block.
+    pub list: Rect
+}
+
+impl Targets {
+    /// What `mouse` means on a screen showing a list of `rows` rows drawn with `state`, and
+    /// `footer` on its bottom line. Clicking a row highlights it and clicking it again chooses it,
+    /// so a touchscreen shows the details of an item before acting on it.
+    pub fn click(&self, mouse: MouseEvent, state: &ListState, rows: usize, footer: Option<&Footer>) -> Option<Click> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => Some(Click::Key(KeyCode::Down)),
+            MouseEventKind::ScrollUp => Some(Click::Key(KeyCode::Up)),
+            MouseEventKind::Down(MouseButton::Left) if self.list.contains(Position::new(mouse.column, mouse.row)) => {
+                let row = state.offset() + usize::from(mouse.row - self.list.y);
+                if row >= rows {
+                    None
+                } else if state.selected() == Some(row) {
+                    Some(Click::Key(KeyCode::Enter))
+                } else {
+                    Some(Click::Select(row))
+                }
+            },
+            MouseEventKind::Down(MouseButton::Left) if mouse.row + 1 == self.screen.bottom() => {
+                footer?.key_at(self.screen, mouse.column).map(Click::Key)
+            },
+            _ => None
+        }
+    }
+}
+
+/// Whether `mouse` is a press of the main button, e.g. a tap on a touchscreen.
+pub fn is_click(mouse: &MouseEvent) -> bool {
+    mouse.kind == MouseEventKind::Down(MouseButton::Left)
+}
+
+/// The rows of a file explorer drawn in `area`, inside the top border and padding every file
+/// browser gives it.
+pub fn explorer_rows(area: Rect) -> Rect {
+    Block::new().borders(Borders::TOP).padding(Padding::horizontal(1)).inner(area)
+}
+
+/// The scroll position of a file explorer whose rows are `height` high. The explorer does not keep
+/// one between frames, so it always shows the rows that bring the highlighted file into view.
+pub fn explorer_state(explorer: &FileExplorer, height: u16) -> ListState {
+    let selected = explorer.selected_idx();
+    let offset = (selected + 1).saturating_sub(usize::from(height));
+    ListState::default().with_offset(offset).with_selected(Some(selected))
+}
+
+/// A footer of hints such as "ESC to go back". Hints that name a key can be clicked to do the
+/// same as pressing it.
+#[derive(Debug, Default)]
+pub struct Footer {
+    hints: Vec<(&'static str, Option<KeyCode>)>
+}
+
+impl Footer {
+    /// The footer of a screen with a list to choose from, whose `keymap` unselects with ← and
+    /// chooses with →.
+    pub fn list(keymap: &Keymap) -> Self {
+        Footer::default()
+            .hint(tr!("footer.move"))
+            .bound(keymap, tr!("footer.unselect"), KeyCode::Left, Action::Unselect)
+            .bound(keymap, tr!("footer.change"), KeyCode::Right, Action::Choose)
+            .hint(tr!("footer.top_bottom"))
+            .bound(keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+            .bound(keymap, tr!("footer.quit"), KeyCode::Char('q'), Action::Quit)
+            .bound(keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help)
+    }
+
+    /// A hint that is only there to be read.
+    pub fn hint(mut self, text: &'static str) -> Self {
+        self.hints.push((text, None));
+        self
+    }
+
+    /// A hint that presses `key` when clicked.
+    pub fn action(mut self, text: &'static str, key: KeyCode) -> Self {
+        self.hints.push((text, Some(key)));
+        self
+    }
+
+    /// A hint that presses `key` when clicked, where `keymap` binds `key` to `action`. Checking
+    /// the binding stops the hint promising something the key does not do.
+    pub fn bound(self, keymap: &Keymap, text: &'static str, key: KeyCode, action: Action) -> Self {
+        debug_assert_eq!(keymap.action(key), Some(action), "\"{}\" does not match the keymap", text);
+        self.action(text, key)
+    }
+
+    // the pieces of the line, with the key each one presses
+    fn spans(&self) -> Vec<(Span<'static>, Option<KeyCode>)> {
+        let mut spans = Vec::new();
+        for (i, (text, key)) in self.hints.iter().enumerate() {
+            if i > 0 {
+                spans.push((Span::raw(", "), None));
+            }
+            let span = match key {
+                Some(_) => Span::raw(*text).underlined(),
+                None => Span::raw(*text)
+            };
+            spans.push((span, *key));
+        }
+        spans.push((Span::raw("."), None));
+        spans
+    }
+
+    pub fn line(&self) -> Line<'static> {
+        Line::from(self.spans().into_iter().map(|(span, _)| span).collect::<Vec<_>>())
+    }
+
+    /// The key of the hint at `column`, with the footer centred on the bottom line of `screen`.
+    pub fn key_at(&self, screen: Rect, column: u16) -> Option<KeyCode> {
+        let spans = self.spans();
+        let width: i32 = spans.iter().map(|(span, _)| span.width() as i32).sum();
+        // a footer wider than the screen loses the same amount from each end
+        let mut start = i32::from(screen.x) + (i32::from(screen.width) - width) / 2;
+        for (span, key) in spans {
+            let end = start + span.width() as i32;
+            if (start..end).contains(&i32::from(column)) {
+                return key;
+            }
+            start = end;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE
+        }
+    }
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+    }
+
+    #[test]
+    fn check_list_clicks() {
+        let targets = Targets {
+            screen: Rect::new(0, 0, 80, 24),
+            list: Rect::new(0, 3, 40, 10)
+        };
+        let state = ListState::default().with_offset(2).with_selected(Some(4));
+        assert_eq!(targets.click(click(5, 3), &state, 7, None), Some(Click::Select(2)));
+        assert_eq!(targets.click(click(5, 5), &state, 7, None), Some(Click::Key(KeyCode::Enter)));
+        // below the last row
+        assert_eq!(targets.click(click(5, 8), &state, 7, None), None);
+        assert_eq!(targets.click(click(50, 3), &state, 7, None), None);
+        assert_eq!(targets.click(mouse(MouseEventKind::ScrollDown, 70, 20), &state, 7, None), Some(Click::Key(KeyCode::Down)));
+        assert_eq!(targets.click(mouse(MouseEventKind::Up(MouseButton::Left), 5, 3), &state, 7, None), None);
+    }
+
+    #[test]
+    fn check_footer_clicks() {
+        let footer = Footer::default()
+            .hint("Use ↓↑ to move")
+            .action("ESC to go back", KeyCode::Esc)
+            .action("Q to quit", KeyCode::Char('q'));
+        assert_eq!(footer.line().to_string(), "Use ↓↑ to move, ESC to go back, Q to quit.");
+
+        // 42 columns wide, so it starts at column 19 of an 80 column screen
+        let screen = Rect::new(0, 0, 80, 24);
+        assert_eq!(footer.key_at(screen, 19), None);
+        assert_eq!(footer.key_at(screen, 35), Some(KeyCode::Esc));
+        assert_eq!(footer.key_at(screen, 50), None);
+        assert_eq!(footer.key_at(screen, 59), Some(KeyCode::Char('q')));
+        assert_eq!(footer.key_at(screen, 60), None);
+
+        let targets = Targets {
+            screen,
+            list: Rect::default()
+        };
+        assert_eq!(targets.click(click(35, 23), &ListState::default(), 0, Some(&footer)), Some(Click::Key(KeyCode::Esc)));
+        assert_eq!(targets.click(click(35, 22), &ListState::default(), 0, Some(&footer)), None);
+    }
+}
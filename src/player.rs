@@ -0,0 +1,10 @@
+pub mod backend;
+pub mod process;
+pub mod ffplay;
+pub mod mpv;
+pub mod vlc;
+pub mod feh;
+pub mod chromium;
+pub mod exec;
+#[cfg(test)]
+pub mod mock;
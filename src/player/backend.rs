@@ -0,0 +1,158 @@
+use std::{
+    error::Error,
+    process::Command
+};
+
+use crate::{
+    Task,
+    ProcType,
+    Autoloop,
+    AdvancedSchedule,
+    Model,
+    MediaPlayer
+};
+use super::{
+    process::Process,
+    ffplay::Ffplay,
+    mpv::Mpv,
+    vlc::Vlc,
+    feh::Feh,
+    chromium::Chromium,
+    exec::Exec
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerStatus {
+    Stopped,
+    Running,
+    // the exit code, None when the player was killed by a signal
+    Exited(Option<i32>)
+}
+
+/// Something that can play a Task. The supervisor only talks to players through this trait so
+/// that it can be tested without launching real programs.
+pub trait PlayerBackend {
+    fn name(&self) -> &'static str;
+    fn start(&mut self, task: &Task) -> Result<(), Box<dyn Error>>;
+    fn stop(&mut self);
+    /// Reports an exit only once, after which the backend is Stopped again.
+    fn status(&mut self) -> PlayerStatus;
+    /// The process id of the running player, used to watch its CPU time.
+    fn pid(&mut self) -> Option<u32> {
+        None
+    }
+    /// The playback position in seconds, for backends that can report it.
+    fn position(&mut self) -> Option<f64> {
+        None
+    }
+}
+
+/// A backend that plays a Task by running a single external program.
+pub trait CommandPlayer {
+    fn name(&self) -> &'static str;
+    fn supports(&self, proc_type: &ProcType) -> bool;
+    fn command(&self, task: &Task) -> Command;
+    fn process(&mut self) -> &mut Process;
+    /// Runs before each start, for players that need files written first.
+    fn prepare(&mut self, _task: &Task) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+    fn position(&mut self) -> Option<f64> {
+        None
+    }
+}
+
+impl<T: CommandPlayer> PlayerBackend for T {
+    fn name(&self) -> &'static str {
+        CommandPlayer::name(self)
+    }
+
+    fn start(&mut self, task: &Task) -> Result<(), Box<dyn Error>> {
+        if !CommandPlayer::supports(self, &task.proc_type) {
+            return Err(format!("{} cannot play {} tasks", CommandPlayer::name(self), task.proc_type).into());
+        }
+        self.prepare(task)?;
+        let command = self.command(task);
+        self.process().spawn(command)
+    }
+
+    fn stop(&mut self) {
+        self.process().stop();
+    }
+
+    fn status(&mut self) -> PlayerStatus {
+        self.process().status()
+    }
+
+    fn pid(&mut self) -> Option<u32> {
+        self.process().pid()
+    }
+
+    fn position(&mut self) -> Option<f64> {
+        CommandPlayer::position(self)
+    }
+}
+
+/// Players loop internally where they can, so an exit while content should still be showing is
+/// treated as a crash.
+pub fn loops(task: &Task) -> bool {
+    task.advanced_schedule == AdvancedSchedule::No || task.auto_loop == Autoloop::Yes
+}
+
+/// Picks the backend for the task. Video and audio use the configured media player, everything
+/// else has a single player.
+pub fn backend_for(task: &Task, model: &Model) -> Box<dyn PlayerBackend> {
+    // the eco model plays video without audio
+    let eco = *model == Model::Eco;
+    match task.proc_type {
+        ProcType::Video | ProcType::Audio => match task.media_player {
+            MediaPlayer::Ffplay => Box::new(Ffplay::new(eco)),
+            MediaPlayer::Mpv => Box::new(Mpv::new(eco)),
+            MediaPlayer::Vlc => Box::new(Vlc::new(eco))
+        },
+        ProcType::Image | ProcType::Slideshow => Box::new(Feh::new()),
+        ProcType::Web | ProcType::Browser => Box::new(Chromium::new()),
+        ProcType::Executable => Box::new(Exec::new())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    pub fn task(proc_type: ProcType, auto_loop: Autoloop, advanced_schedule: AdvancedSchedule) -> Task {
+        Task::new(proc_type, auto_loop, advanced_schedule, Vec::new(), PathBuf::from("/media/user/USB/file.mp4"), 5, String::from("https://example.com"), String::new())
+    }
+
+    pub fn args(command: &Command) -> Vec<String> {
+        command.get_args().map(|a| a.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn check_backend_for() {
+        let video = task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No);
+        assert_eq!(backend_for(&video, &Model::Pro).name(), "ffplay");
+        let video = video.media_player(MediaPlayer::Mpv);
+        assert_eq!(backend_for(&video, &Model::Pro).name(), "mpv");
+        let audio = task(ProcType::Audio, Autoloop::Yes, AdvancedSchedule::No).media_player(MediaPlayer::Vlc);
+        assert_eq!(backend_for(&audio, &Model::Pro).name(), "vlc");
+
+        // the media player setting only applies to video and audio
+        let slideshow = task(ProcType::Slideshow, Autoloop::No, AdvancedSchedule::No).media_player(MediaPlayer::Mpv);
+        assert_eq!(backend_for(&slideshow, &Model::Pro).name(), "feh");
+        let web = task(ProcType::Web, Autoloop::No, AdvancedSchedule::No);
+        assert_eq!(backend_for(&web, &Model::Pro).name(), "chromium");
+        let executable = task(ProcType::Executable, Autoloop::No, AdvancedSchedule::No);
+        assert_eq!(backend_for(&executable, &Model::Pro).name(), "exec");
+    }
+
+    #[test]
+    fn check_unsupported_task() {
+        let mut feh = Feh::new();
+        let video = task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No);
+        assert!(!CommandPlayer::supports(&feh, &ProcType::Video));
+        assert!(PlayerBackend::start(&mut feh, &video).is_err());
+        assert_eq!(PlayerBackend::status(&mut feh), PlayerStatus::Stopped);
+    }
+}
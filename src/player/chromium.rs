@@ -0,0 +1,72 @@
+use std::process::Command;
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::{
+    backend::CommandPlayer,
+    process::Process
+};
+
+pub struct Chromium {
+    process: Process
+}
+
+impl Chromium {
+    pub fn new() -> Self {
+        Self {
+            process: Process::new()
+        }
+    }
+}
+
+impl CommandPlayer for Chromium {
+    fn name(&self) -> &'static str {
+        "chromium"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        matches!(proc_type, ProcType::Web | ProcType::Browser)
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        let mut command = Command::new("chromium");
+        command.args(["--kiosk", "--noerrdialogs", "--disable-infobars"]);
+        if task.proc_type == ProcType::Browser {
+            // browser applications are loaded from the drive
+            command.arg("--allow-file-access-from-files");
+            command.arg(format!("file://{}", task.file.display()));
+        } else {
+            command.arg(&task.url);
+        }
+        command
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Autoloop,
+        AdvancedSchedule
+    };
+    use crate::player::backend::tests::{
+        task,
+        args
+    };
+
+    #[test]
+    fn check_chromium_command() {
+        let web = Chromium::new().command(&task(ProcType::Web, Autoloop::No, AdvancedSchedule::No));
+        assert_eq!(web.get_program(), "chromium");
+        assert_eq!(args(&web).last(), Some(&String::from("https://example.com")));
+
+        let browser = Chromium::new().command(&task(ProcType::Browser, Autoloop::No, AdvancedSchedule::No));
+        assert_eq!(args(&browser).last(), Some(&String::from("file:///media/user/USB/file.mp4")));
+    }
+}
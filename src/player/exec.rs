@@ -0,0 +1,41 @@
+use std::process::Command;
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::{
+    backend::CommandPlayer,
+    process::Process
+};
+
+/// Runs the task file itself.
+pub struct Exec {
+    process: Process
+}
+
+impl Exec {
+    pub fn new() -> Self {
+        Self {
+            process: Process::new()
+        }
+    }
+}
+
+impl CommandPlayer for Exec {
+    fn name(&self) -> &'static str {
+        "exec"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        *proc_type == ProcType::Executable
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        Command::new(&task.file)
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+}
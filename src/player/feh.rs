@@ -0,0 +1,125 @@
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf,
+    process::Command
+};
+
+use crate::{
+    Task,
+    ProcType,
+    SlideOrder,
+    env_file_path
+};
+use crate::slideshow::{
+    is_image,
+    slideshow_files
+};
+use super::{
+    backend::CommandPlayer,
+    process::Process
+};
+
+pub struct Feh {
+    process: Process
+}
+
+impl Feh {
+    pub fn new() -> Self {
+        Self {
+            process: Process::new()
+        }
+    }
+}
+
+// images to show when other files in the folder are skipped
+fn file_list_path() -> PathBuf {
+    env_file_path().with_file_name("slideshow.list")
+}
+
+impl CommandPlayer for Feh {
+    fn name(&self) -> &'static str {
+        "feh"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        matches!(proc_type, ProcType::Image | ProcType::Slideshow)
+    }
+
+    fn prepare(&mut self, task: &Task) -> Result<(), Box<dyn Error>> {
+        if task.proc_type == ProcType::Slideshow && task.slideshow.skip_non_images {
+            let images: Vec<String> = slideshow_files(&task.file, task.slideshow.recursive)?
+                .into_iter()
+                .filter(|file| is_image(file))
+                .map(|file| file.display().to_string())
+                .collect();
+            if images.is_empty() {
+                return Err(format!("No images found in {}", task.file.display()).into());
+            }
+            fs::write(file_list_path(), images.join("\n"))?;
+        }
+        Ok(())
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        let mut command = Command::new("feh");
+        command.args(["--fullscreen", "--auto-zoom", "--hide-pointer"]);
+        if task.proc_type == ProcType::Slideshow {
+            command.arg("--slideshow-delay").arg(task.slide_delay.to_string());
+            match task.slideshow.order {
+                SlideOrder::Name => command.args(["--sort", "filename", "--version-sort"]),
+                SlideOrder::Modified => command.args(["--sort", "mtime"]),
+                SlideOrder::Shuffle => command.arg("--randomize")
+            };
+            if task.slideshow.skip_non_images {
+                // the list is already filtered and includes subfolders when asked
+                command.arg("--filelist").arg(file_list_path());
+                return command;
+            }
+            if task.slideshow.recursive {
+                command.arg("--recursive");
+            }
+        }
+        command.arg(&task.file);
+        command
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Autoloop,
+        AdvancedSchedule,
+        SlideshowOptions
+    };
+    use crate::player::backend::tests::{
+        task,
+        args
+    };
+
+    #[test]
+    fn check_feh_command() {
+        let slideshow = Feh::new().command(&task(ProcType::Slideshow, Autoloop::No, AdvancedSchedule::No));
+        assert_eq!(slideshow.get_program(), "feh");
+        assert!(args(&slideshow).contains(&String::from("5")));
+        assert!(args(&slideshow).contains(&String::from("--filelist")));
+
+        let options = SlideshowOptions {
+            order: SlideOrder::Shuffle,
+            recursive: true,
+            skip_non_images: false
+        };
+        let slideshow = Feh::new().command(&task(ProcType::Slideshow, Autoloop::No, AdvancedSchedule::No).slideshow(options));
+        assert!(args(&slideshow).contains(&String::from("--randomize")));
+        assert!(args(&slideshow).contains(&String::from("--recursive")));
+        assert_eq!(args(&slideshow).last(), Some(&String::from("/media/user/USB/file.mp4")));
+
+        let image = Feh::new().command(&task(ProcType::Image, Autoloop::No, AdvancedSchedule::No));
+        assert!(!args(&image).contains(&String::from("--slideshow-delay")));
+    }
+}
@@ -0,0 +1,88 @@
+use std::process::Command;
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::{
+    backend::{
+        CommandPlayer,
+        loops
+    },
+    process::Process
+};
+
+pub struct Ffplay {
+    eco: bool,
+    process: Process
+}
+
+impl Ffplay {
+    pub fn new(eco: bool) -> Self {
+        Self {
+            eco,
+            process: Process::new()
+        }
+    }
+}
+
+impl CommandPlayer for Ffplay {
+    fn name(&self) -> &'static str {
+        "ffplay"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        matches!(proc_type, ProcType::Video | ProcType::Audio)
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        let mut command = Command::new("ffplay");
+        command.args(["-loglevel", "error", "-autoexit"]);
+        if task.proc_type == ProcType::Audio {
+            command.arg("-nodisp");
+        } else {
+            command.arg("-fs");
+            if self.eco {
+                command.arg("-an");
+            }
+        }
+        if loops(task) {
+            command.args(["-loop", "0"]);
+        }
+        command.arg(&task.file);
+        command
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Autoloop,
+        AdvancedSchedule
+    };
+    use crate::player::backend::tests::{
+        task,
+        args
+    };
+
+    #[test]
+    fn check_ffplay_command() {
+        let video = Ffplay::new(true).command(&task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No));
+        assert_eq!(video.get_program(), "ffplay");
+        assert!(args(&video).contains(&String::from("-an")));
+        assert!(args(&video).contains(&String::from("-loop")));
+
+        let video = Ffplay::new(false).command(&task(ProcType::Video, Autoloop::No, AdvancedSchedule::Yes));
+        assert!(!args(&video).contains(&String::from("-an")));
+        assert!(!args(&video).contains(&String::from("-loop")));
+
+        let audio = Ffplay::new(true).command(&task(ProcType::Audio, Autoloop::No, AdvancedSchedule::No));
+        assert!(args(&audio).contains(&String::from("-nodisp")));
+        assert!(!args(&audio).contains(&String::from("-an")));
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    rc::Rc
+};
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::backend::{
+    PlayerBackend,
+    PlayerStatus
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockCall {
+    Start(ProcType),
+    Stop
+}
+
+/// Records the calls made to it instead of running a player. Clones share their state, so a
+/// test can keep a clone to inspect the calls and make the player exit.
+#[derive(Clone)]
+pub struct MockPlayer {
+    calls: Rc<RefCell<Vec<MockCall>>>,
+    status: Rc<RefCell<PlayerStatus>>,
+    fail_start: Rc<RefCell<bool>>,
+    position: Rc<RefCell<Option<f64>>>
+}
+
+impl MockPlayer {
+    pub fn new() -> Self {
+        Self {
+            calls: Rc::new(RefCell::new(Vec::new())),
+            status: Rc::new(RefCell::new(PlayerStatus::Stopped)),
+            fail_start: Rc::new(RefCell::new(false)),
+            position: Rc::new(RefCell::new(None))
+        }
+    }
+
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.calls.borrow_mut().clear();
+    }
+
+    /// Makes a running player exit with `code`.
+    pub fn exit(&self, code: Option<i32>) {
+        *self.status.borrow_mut() = PlayerStatus::Exited(code);
+    }
+
+    pub fn set_fail_start(&self, fail: bool) {
+        *self.fail_start.borrow_mut() = fail;
+    }
+
+    pub fn set_position(&self, position: Option<f64>) {
+        *self.position.borrow_mut() = position;
+    }
+}
+
+impl PlayerBackend for MockPlayer {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn start(&mut self, task: &Task) -> Result<(), Box<dyn Error>> {
+        self.calls.borrow_mut().push(MockCall::Start(task.proc_type.clone()));
+        if *self.fail_start.borrow() {
+            return Err("mock player failed to start".into());
+        }
+        *self.status.borrow_mut() = PlayerStatus::Running;
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.calls.borrow_mut().push(MockCall::Stop);
+        *self.status.borrow_mut() = PlayerStatus::Stopped;
+    }
+
+    fn status(&mut self) -> PlayerStatus {
+        let status = self.status.borrow().clone();
+        // an exit is reported once, like a reaped process
+        if let PlayerStatus::Exited(_) = status {
+            *self.status.borrow_mut() = PlayerStatus::Stopped;
+        }
+        status
+    }
+
+    fn position(&mut self) -> Option<f64> {
+        *self.position.borrow()
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Write
+    },
+    os::unix::net::UnixStream,
+    process::Command,
+    time::Duration
+};
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::{
+    backend::{
+        CommandPlayer,
+        loops
+    },
+    process::Process
+};
+
+// mpv answers playback queries on this socket, which lets the watchdog see frozen output
+const IPC_SOCKET: &str = "/tmp/mediatimer-mpv.sock";
+
+pub struct Mpv {
+    eco: bool,
+    process: Process
+}
+
+impl Mpv {
+    pub fn new(eco: bool) -> Self {
+        Self {
+            eco,
+            process: Process::new()
+        }
+    }
+}
+
+impl CommandPlayer for Mpv {
+    fn name(&self) -> &'static str {
+        "mpv"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        matches!(proc_type, ProcType::Video | ProcType::Audio)
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        let mut command = Command::new("mpv");
+        command.args(["--really-quiet", "--no-terminal", "--no-osc"]);
+        command.arg(format!("--input-ipc-server={}", IPC_SOCKET));
+        if task.proc_type == ProcType::Audio {
+            command.arg("--no-video");
+        } else {
+            command.arg("--fs");
+            if self.eco {
+                command.arg("--no-audio");
+            }
+        }
+        if loops(task) {
+            command.arg("--loop-file=inf");
+        }
+        command.arg(&task.file);
+        command
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+
+    fn position(&mut self) -> Option<f64> {
+        let mut stream = UnixStream::connect(IPC_SOCKET).ok()?;
+        stream.set_read_timeout(Some(Duration::from_millis(200))).ok()?;
+        stream.write_all(b"{\"command\": [\"get_property\", \"time-pos\"]}\n").ok()?;
+        // skip any event messages sent before the reply
+        let reader = BufReader::new(stream);
+        reader.lines()
+            .map_while(Result::ok)
+            .take(10)
+            .find_map(|line| parse_time_pos(&line))
+    }
+}
+
+fn parse_time_pos(reply: &str) -> Option<f64> {
+    let reply: serde_json::Value = serde_json::from_str(reply).ok()?;
+    if reply.get("error")?.as_str()? != "success" {
+        return None;
+    }
+    reply.get("data")?.as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Autoloop,
+        AdvancedSchedule
+    };
+    use crate::player::backend::tests::{
+        task,
+        args
+    };
+
+    #[test]
+    fn check_mpv_command() {
+        let video = Mpv::new(true).command(&task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No));
+        assert_eq!(video.get_program(), "mpv");
+        assert!(args(&video).contains(&String::from("--no-audio")));
+        assert!(args(&video).contains(&String::from("--loop-file=inf")));
+
+        let audio = Mpv::new(false).command(&task(ProcType::Audio, Autoloop::No, AdvancedSchedule::Yes));
+        assert!(args(&audio).contains(&String::from("--no-video")));
+        assert!(!args(&audio).contains(&String::from("--loop-file=inf")));
+    }
+
+    #[test]
+    fn check_parse_time_pos() {
+        assert_eq!(parse_time_pos(r#"{"data":12.5,"request_id":0,"error":"success"}"#), Some(12.5));
+        assert_eq!(parse_time_pos(r#"{"event":"playback-restart"}"#), None);
+        assert_eq!(parse_time_pos(r#"{"data":null,"error":"property unavailable"}"#), None);
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    error::Error,
+    process::{
+        Child,
+        Command,
+        Stdio
+    }
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use super::backend::PlayerStatus;
+
+/// The running program behind a CommandPlayer.
+pub struct Process {
+    child: Option<Child>
+}
+
+impl Process {
+    pub fn new() -> Self {
+        Self {
+            child: None
+        }
+    }
+
+    pub fn spawn(&mut self, mut command: Command) -> Result<(), Box<dyn Error>> {
+        self.stop();
+        command.stdin(Stdio::null());
+        logi!("Starting player: {:?}", command);
+        self.child = Some(command.spawn()?);
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            logi!("Stopping player");
+            if let Err(e) = child.kill() {
+                logw!("Could not stop player: {}", e);
+            }
+            // reap it so it does not linger as a zombie
+            let _ = child.wait();
+        }
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        self.child.as_ref().map(|child| child.id())
+    }
+
+    pub fn status(&mut self) -> PlayerStatus {
+        let Some(child) = self.child.as_mut() else {
+            return PlayerStatus::Stopped;
+        };
+        match child.try_wait() {
+            Ok(None) => PlayerStatus::Running,
+            Ok(Some(status)) => {
+                self.child = None;
+                PlayerStatus::Exited(status.code())
+            },
+            Err(e) => {
+                loge!("Could not check player: {}", e);
+                self.child = None;
+                PlayerStatus::Exited(None)
+            }
+        }
+    }
+}
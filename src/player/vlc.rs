@@ -0,0 +1,85 @@
+use std::process::Command;
+
+use crate::{
+    Task,
+    ProcType
+};
+use super::{
+    backend::{
+        CommandPlayer,
+        loops
+    },
+    process::Process
+};
+
+/// VLC without its interface, through `cvlc`.
+pub struct Vlc {
+    eco: bool,
+    process: Process
+}
+
+impl Vlc {
+    pub fn new(eco: bool) -> Self {
+        Self {
+            eco,
+            process: Process::new()
+        }
+    }
+}
+
+impl CommandPlayer for Vlc {
+    fn name(&self) -> &'static str {
+        "vlc"
+    }
+
+    fn supports(&self, proc_type: &ProcType) -> bool {
+        matches!(proc_type, ProcType::Video | ProcType::Audio)
+    }
+
+    fn command(&self, task: &Task) -> Command {
+        let mut command = Command::new("cvlc");
+        command.args(["--quiet", "--play-and-exit"]);
+        if task.proc_type == ProcType::Audio {
+            command.arg("--no-video");
+        } else {
+            command.args(["--fullscreen", "--no-video-title-show"]);
+            if self.eco {
+                command.arg("--no-audio");
+            }
+        }
+        if loops(task) {
+            command.arg("--loop");
+        }
+        command.arg(&task.file);
+        command
+    }
+
+    fn process(&mut self) -> &mut Process {
+        &mut self.process
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Autoloop,
+        AdvancedSchedule
+    };
+    use crate::player::backend::tests::{
+        task,
+        args
+    };
+
+    #[test]
+    fn check_vlc_command() {
+        let video = Vlc::new(true).command(&task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No));
+        assert_eq!(video.get_program(), "cvlc");
+        assert!(args(&video).contains(&String::from("--no-audio")));
+        assert!(args(&video).contains(&String::from("--loop")));
+
+        let audio = Vlc::new(false).command(&task(ProcType::Audio, Autoloop::No, AdvancedSchedule::Yes));
+        assert!(args(&audio).contains(&String::from("--no-video")));
+        assert!(!args(&audio).contains(&String::from("--loop")));
+    }
+}
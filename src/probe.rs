@@ -0,0 +1,297 @@
+use std::{
+    error::Error,
+    path::Path,
+    process::Command
+};
+use serde::Deserialize;
+
+use crate::runner::CommandRunner;
+use crate::tr;
+
+/// What ffprobe reports about a media file, reduced to what mediatimer cares about.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MediaInfo {
+    pub container: String,
+    pub duration: Option<f64>,
+    // bits per second for the whole file
+    pub bit_rate: Option<u64>,
+    pub video: Option<VideoStream>,
+    pub audio: Option<AudioStream>
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VideoStream {
+    pub codec: String,
+    pub profile: Option<String>,
+    pub pix_fmt: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub frame_rate: Option<f64>,
+    // degrees clockwise the picture is turned when played
+    pub rotation: i32
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AudioStream {
+    pub codec: String,
+    pub channels: u32,
+    pub sample_rate: Option<u32>
+}
+
+// ffprobe -print_format json writes most numbers as strings
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    format: Option<ProbeFormat>
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    profile: Option<String>,
+    pix_fmt: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    avg_frame_rate: Option<String>,
+    r_frame_rate: Option<String>,
+    channels: Option<u32>,
+    sample_rate: Option<String>,
+    #[serde(default)]
+    tags: ProbeTags,
+    #[serde(default)]
+    side_data_list: Vec<ProbeSideData>,
+    #[serde(default)]
+    disposition: ProbeDisposition
+}
+
+#[derive(Deserialize, Default)]
+struct ProbeTags {
+    rotate: Option<String>
+}
+
+#[derive(Deserialize)]
+struct ProbeSideData {
+    rotation: Option<f64>
+}
+
+#[derive(Deserialize, Default)]
+struct ProbeDisposition {
+    #[serde(default)]
+    attached_pic: u8
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    format_name: Option<String>,
+    duration: Option<String>,
+    bit_rate: Option<String>
+}
+
+pub fn probe(runner: &dyn CommandRunner, file: &Path) -> Result<MediaInfo, Box<dyn Error>> {
+    let output = runner.output(Command::new("ffprobe")
+        .args(["-loglevel", "error", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(file))?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!("ffprobe could not read {}: {}", file.display(), reason).into());
+    }
+    parse_probe(&String::from_utf8_lossy(&output.stdout))
+}
+
+pub fn parse_probe(json: &str) -> Result<MediaInfo, Box<dyn Error>> {
+    let output: ProbeOutput = serde_json::from_str(json)?;
+    let mut info = MediaInfo::default();
+
+    if let Some(format) = output.format {
+        info.container = format.format_name.unwrap_or_default();
+        info.duration = format.duration.and_then(|d| d.parse().ok());
+        info.bit_rate = format.bit_rate.and_then(|b| b.parse().ok());
+    }
+
+    for stream in output.streams {
+        match stream.codec_type.as_deref() {
+            // cover art in audio files is reported as a video stream
+            Some("video") if info.video.is_none() && stream.disposition.attached_pic == 0 => {
+                let frame_rate = stream.avg_frame_rate.as_deref()
+                    .and_then(parse_rate)
+                    .or_else(|| stream.r_frame_rate.as_deref().and_then(parse_rate));
+                let rotation = stream.side_data_list.iter()
+                    .find_map(|side_data| side_data.rotation)
+                    .map(|r| r as i32)
+                    .or_else(|| stream.tags.rotate.as_deref().and_then(|r| r.parse().ok()))
+                    .unwrap_or(0);
+                info.video = Some(VideoStream {
+                    codec: stream.codec_name.unwrap_or_default(),
+                    profile: stream.profile,
+                    pix_fmt: stream.pix_fmt,
+                    width: stream.width.unwrap_or(0),
+                    height: stream.height.unwrap_or(0),
+                    frame_rate,
+                    // side data counts anticlockwise
+                    rotation: (-rotation).rem_euclid(360)
+                });
+            },
+            Some("audio") if info.audio.is_none() => {
+                info.audio = Some(AudioStream {
+                    codec: stream.codec_name.unwrap_or_default(),
+                    channels: stream.channels.unwrap_or(0),
+                    sample_rate: stream.sample_rate.and_then(|s| s.parse().ok())
+                });
+            },
+            _ => {}
+        }
+    }
+    Ok(info)
+}
+
+// frame rates are fractions such as 30000/1001, 0/0 means unknown
+fn parse_rate(rate: &str) -> Option<f64> {
+    let (numerator, denominator) = rate.split_once('/')?;
+    let numerator: f64 = numerator.parse().ok()?;
+    let denominator: f64 = denominator.parse().ok()?;
+    if numerator == 0.0 || denominator == 0.0 {
+        return None;
+    }
+    Some(numerator / denominator)
+}
+
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
+
+pub fn format_bit_rate(bits: u64) -> String {
+    if bits >= 1_000_000 {
+        format!("{:.1} Mb/s", bits as f64 / 1_000_000.0)
+    } else {
+        format!("{} kb/s", bits / 1000)
+    }
+}
+
+impl MediaInfo {
+    /// One line per property, for showing in the interface.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = vec![tr!("probe.format", format = self.container)];
+        if let Some(duration) = self.duration {
+            lines.push(tr!("probe.duration", duration = format_duration(duration)));
+        }
+        if let Some(video) = self.video.as_ref() {
+            let mut line = tr!("probe.video", codec = video.codec);
+            if let Some(profile) = video.profile.as_ref() {
+                line.push_str(&format!(" ({})", profile));
+            }
+            line.push_str(&format!(", {}x{}", video.width, video.height));
+            if let Some(frame_rate) = video.frame_rate {
+                line.push_str(&tr!("probe.frame_rate", rate = format!("{:.2}", frame_rate)));
+            }
+            if video.rotation != 0 {
+                line.push_str(&tr!("probe.rotated", degrees = video.rotation));
+            }
+            lines.push(line);
+        }
+        if let Some(audio) = self.audio.as_ref() {
+            lines.push(tr!("probe.audio", codec = audio.codec, channels = audio.channels));
+        }
+        if let Some(bit_rate) = self.bit_rate {
+            lines.push(tr!("probe.bit_rate", rate = format_bit_rate(bit_rate)));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::fake::FakeRunner;
+
+    const VIDEO_JSON: &str = r#"{
+        "streams": [
+            {
+                "index": 0,
+                "codec_name": "hevc",
+                "codec_type": "video",
+                "profile": "Main 10",
+                "width": 3840,
+                "height": 2160,
+                "pix_fmt": "yuv420p10le",
+                "r_frame_rate": "60/1",
+                "avg_frame_rate": "60000/1001",
+                "side_data_list": [
+                    { "side_data_type": "Display Matrix", "rotation": -90 }
+                ]
+            },
+            {
+                "index": 1,
+                "codec_name": "aac",
+                "codec_type": "audio",
+                "sample_rate": "48000",
+                "channels": 2
+            }
+        ],
+        "format": {
+            "filename": "clip.mp4",
+            "format_name": "mov,mp4,m4a,3gp,3g2,mj2",
+            "duration": "83.400000",
+            "bit_rate": "45000000"
+        }
+    }"#;
+
+    const AUDIO_JSON: &str = r#"{
+        "streams": [
+            { "codec_name": "mp3", "codec_type": "audio", "channels": 1, "sample_rate": "44100" },
+            { "codec_name": "mjpeg", "codec_type": "video", "width": 500, "height": 500, "disposition": { "attached_pic": 1 } }
+        ],
+        "format": { "format_name": "mp3", "duration": "200.1", "bit_rate": "128000" }
+    }"#;
+
+    #[test]
+    fn check_parse_video() {
+        let info = parse_probe(VIDEO_JSON).unwrap();
+        assert_eq!(info.container, "mov,mp4,m4a,3gp,3g2,mj2");
+        assert_eq!(info.bit_rate, Some(45_000_000));
+        let video = info.video.unwrap();
+        assert_eq!(video.codec, "hevc");
+        assert_eq!(video.profile, Some(String::from("Main 10")));
+        assert_eq!((video.width, video.height), (3840, 2160));
+        assert!((video.frame_rate.unwrap() - 59.94).abs() < 0.01);
+        assert_eq!(video.rotation, 90);
+        assert_eq!(info.audio.unwrap().channels, 2);
+    }
+
+    #[test]
+    fn check_parse_audio() {
+        let info = parse_probe(AUDIO_JSON).unwrap();
+        // the cover art is not a video
+        assert_eq!(info.video, None);
+        assert_eq!(info.audio.as_ref().unwrap().sample_rate, Some(44100));
+        assert_eq!(info.summary(), vec![
+            String::from("Format: mp3"),
+            String::from("Duration: 00:03:20"),
+            String::from("Audio: mp3, 1 channels"),
+            String::from("Bitrate: 128 kb/s")
+        ]);
+        assert!(parse_probe("not json").is_err());
+    }
+
+    #[test]
+    fn check_probe() {
+        let runner = FakeRunner::new()
+            .respond("ffprobe", AUDIO_JSON)
+            .respond_with("ffprobe", 1, "", "My Song.mp3: Invalid data found when processing input");
+
+        let file = Path::new("/media/pi/MY STICK/My Song.mp3");
+        assert_eq!(probe(&runner, file).unwrap().container, "mp3");
+        let error = probe(&runner, file).unwrap_err().to_string();
+        assert!(error.contains("Invalid data found"));
+        assert_eq!(runner.calls()[0], "ffprobe -loglevel error -print_format json -show_format -show_streams /media/pi/MY STICK/My Song.mp3");
+    }
+
+    #[test]
+    fn check_parse_rate() {
+        assert_eq!(parse_rate("25/1"), Some(25.0));
+        assert_eq!(parse_rate("0/0"), None);
+        assert_eq!(parse_rate("fast"), None);
+    }
+}
@@ -1,9 +1,9 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
     layout::{Constraint, Layout, Rect},
     style::{
-        Color,  Stylize,
+        Stylize,
     },
     symbols,
     text::Line,
@@ -15,22 +15,46 @@ use ratatui::{
 };
 use std::error::Error;
 use crate::ProcType;
-
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    ALT_ROW_BG_COLOR,
-    SELECTED_STYLE,
-    TEXT_FG_COLOR,
-    FOOTER_STYLE
+use crate::wizard::{
+    Nav,
+    Step
 };
 
+use crate::styles::theme;
+
 use crate::Model;
+use crate::tr;
+use crate::mouse::{
+    Click,
+    Footer,
+    Targets,
+    is_click
+};
+use crate::keymap::{
+    Action,
+    Keymap
+};
+
+/// The name of `proc_type` as shown on screen.
+pub fn proc_type_label(proc_type: &ProcType) -> &'static str {
+    match proc_type {
+        ProcType::Video => tr!("proctype.video"),
+        ProcType::Audio => tr!("proctype.audio"),
+        ProcType::Image => tr!("proctype.image"),
+        ProcType::Slideshow => tr!("proctype.slideshow"),
+        ProcType::Web => tr!("proctype.web"),
+        ProcType::Browser => tr!("proctype.browser"),
+        ProcType::Executable => tr!("proctype.executable")
+    }
+}
 
 pub struct ProcTypeWidget {
     should_exit: bool,
+    nav: Nav,
     selected_type: ProcType,
-    proc_type_entries: ProcTypeList
+    proc_type_entries: ProcTypeList,
+    targets: Targets,
+    help: bool
 }
 
 struct ProcTypeList {
@@ -56,7 +80,7 @@ struct ProcTypeEntry {
 
 impl From<&ProcTypeEntry> for ListItem<'_> {
     fn from(value: &ProcTypeEntry) -> Self {
-        let line = Line::styled(value.proc_type.to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(proc_type_label(&value.proc_type), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -76,55 +100,64 @@ impl ProcTypeWidget {
             Model::Eco => {
                 Self {
                     should_exit: false,
+                    nav: Nav::Next,
                     selected_type: preset_type,
                     proc_type_entries: ProcTypeList::from_iter([
-                        (ProcType::Video, "A video file, played without audio. Example files: mp4, avi, mkv etc. Most formats are accepted."),
-                        (ProcType::Audio, "An audio file. Example files: mp3, wav, flac etc. Most formats are accepted."),
-                        (ProcType::Image, "An image file. Example files: jpg, png, webp etc. Most formats are accepted."),
-                        (ProcType::Slideshow, "A slideshow of images. Example files: jpg, png, webp etc. Most formats are accepted. The folder selected must only contain images."),
-                        (ProcType::Web, "A website or other internet resource, accepts a URL"),
-                        (ProcType::Browser, "A browser based application or file, such as P5 or html."),
-                        (ProcType::Executable, "A binary executable or shell script. Use this option to launch complex software installations via a shell script."),
+                        (ProcType::Video, tr!("proctype.video_info_eco")),
+                        (ProcType::Audio, tr!("proctype.audio_info")),
+                        (ProcType::Image, tr!("proctype.image_info")),
+                        (ProcType::Slideshow, tr!("proctype.slideshow_info")),
+                        (ProcType::Web, tr!("proctype.web_info")),
+                        (ProcType::Browser, tr!("proctype.browser_info")),
+                        (ProcType::Executable, tr!("proctype.executable_info")),
 
                     ]),
+                    targets: Targets::default(),
+                    help: false
                 }
             },
             Model::Standard => {
                 Self {
                     should_exit: false,
+                    nav: Nav::Next,
                     selected_type: preset_type,
                     proc_type_entries: ProcTypeList::from_iter([
-                        (ProcType::Video, "A video file. Example files: mp4, avi, mkv etc. Most formats are accepted."),
-                        (ProcType::Audio, "An audio file. Example files: mp3, wav, flac etc. Most formats are accepted."),
-                        (ProcType::Image, "An image file. Example files: jpg, png, webp etc. Most formats are accepted."),
-                        (ProcType::Slideshow, "A slideshow of images. Example files: jpg, png, webp etc. Most formats are accepted. The folder selected must only contain images."),
-                        (ProcType::Web, "A website or other internet resource, accepts a URL"),
-                        (ProcType::Browser, "A browser based application or file, such as P5 or html."),
-                        (ProcType::Executable, "A binary executable or shell script. Use this option to launch complex software installations via a shell script."),
+                        (ProcType::Video, tr!("proctype.video_info")),
+                        (ProcType::Audio, tr!("proctype.audio_info")),
+                        (ProcType::Image, tr!("proctype.image_info")),
+                        (ProcType::Slideshow, tr!("proctype.slideshow_info")),
+                        (ProcType::Web, tr!("proctype.web_info")),
+                        (ProcType::Browser, tr!("proctype.browser_info")),
+                        (ProcType::Executable, tr!("proctype.executable_info")),
 
                     ]),
+                    targets: Targets::default(),
+                    help: false
                 }
             },
             Model::Pro => {
                 Self {
                     should_exit: false,
+                    nav: Nav::Next,
                     selected_type: preset_type,
                     proc_type_entries: ProcTypeList::from_iter([
-                        (ProcType::Video, "A video file. Example files: mp4, avi, mkv etc. Most formats are accepted."),
-                        (ProcType::Audio, "An audio file. Example files: mp3, wav, flac etc. Most formats are accepted."),
-                        (ProcType::Image, "An image file. Example files: jpg, png, webp etc. Most formats are accepted."),
-                        (ProcType::Slideshow, "A slideshow of images. Example files: jpg, png, webp etc. Most formats are accepted. The folder selected must only contain images."),
-                        (ProcType::Web, "A website or other internet resource, accepts a URL"),
-                        (ProcType::Browser, "A browser based application or file, such as P5 or html."),
-                        (ProcType::Executable, "A binary executable or shell script. Use this option to launch complex software installations via a shell script."),
+                        (ProcType::Video, tr!("proctype.video_info")),
+                        (ProcType::Audio, tr!("proctype.audio_info")),
+                        (ProcType::Image, tr!("proctype.image_info")),
+                        (ProcType::Slideshow, tr!("proctype.slideshow_info")),
+                        (ProcType::Web, tr!("proctype.web_info")),
+                        (ProcType::Browser, tr!("proctype.browser_info")),
+                        (ProcType::Executable, tr!("proctype.executable_info")),
 
                     ]),
+                    targets: Targets::default(),
+                    help: false
                 }
             }
         }
     }
 
-    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<ProcType, Box< dyn Error>> {
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<ProcType>, Box< dyn Error>> {
         // set default value
         // get the index of the selected item
         if let Some(index) = self.proc_type_entries.list.iter().position(|i| i.proc_type == self.selected_type) {
@@ -134,25 +167,42 @@ impl ProcTypeWidget {
 
         while !self.should_exit {
             let _ = &terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
-            if let Event::Key(key) = event::read()? {
-                self.handle_key(key);
-            };
+            match event::read()? {
+                Event::Key(key) => self.handle_key(key),
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                _ => {}
+            }
         }
-        Ok(self.selected_type)
+        Ok(self.nav.with(self.selected_type))
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::list()
+            .bind(&[KeyCode::Char('h'), KeyCode::Left], Action::Unselect)
+            .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose)
+            .bind(&[KeyCode::Esc], Action::Back)
+            .bind(&[KeyCode::Char('q')], Action::Quit)
     }
 
     fn handle_key(&mut self, key: KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
         }
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => self.should_exit = true,
-            KeyCode::Char('h') | KeyCode::Left => self.select_none(),
-            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-            KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-            KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-            KeyCode::Char('G') | KeyCode::End => self.select_last(),
-            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match ProcTypeWidget::keymap().action(key.code) {
+            Some(Action::Help) => self.help = true,
+            Some(Action::Back) => self.leave(Nav::Back),
+            Some(Action::Quit) => self.leave(Nav::Cancel),
+            Some(Action::Unselect) => self.select_none(),
+            Some(Action::Next) => self.select_next(),
+            Some(Action::Previous) => self.select_previous(),
+            Some(Action::First) => self.select_first(),
+            Some(Action::Last) => self.select_last(),
+            Some(Action::Choose) => {
                 // add code to select the list item
                 self.set_current_type();
                 self.should_exit = true;
@@ -160,7 +210,26 @@ impl ProcTypeWidget {
             _ => {}
         }
     }
-    
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.help {
+            self.help = !is_click(&mouse);
+            return;
+        }
+        let footer = Footer::list(&Self::keymap());
+        let rows = self.proc_type_entries.list.len();
+        match self.targets.click(mouse, &self.proc_type_entries.state, rows, Some(&footer)) {
+            Some(Click::Key(code)) => self.handle_key(KeyEvent::from(code)),
+            Some(Click::Select(i)) => self.proc_type_entries.state.select(Some(i)),
+            None => {}
+        }
+    }
+
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
     fn set_current_type(&mut self) {
         if let Some(i) = self.proc_type_entries.state.selected() {
             match self.proc_type_entries.list[i].proc_type {
@@ -194,9 +263,7 @@ impl ProcTypeWidget {
 
     // rendering logic
     fn render_header(&self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from(vec![
-            "Media".into(), 
-        ]);
+        let title = Line::from(tr!("proctype.header"));
         Paragraph::new(title)
             .bold()
             .centered()
@@ -204,19 +271,21 @@ impl ProcTypeWidget {
     }
 
     fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .style(FOOTER_STYLE)
+        Paragraph::new(Footer::list(&Self::keymap()).line())
+            .style(theme().footer)
             .centered()
             .render(area, buf);
     }
 
     fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw("Select your file type").centered())
+            .title(Line::raw(tr!("proctype.question")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        self.targets.list = block.inner(area);
 
         // Iterate through all the elements in the 'items' and stylise them
         let items: Vec<ListItem> = self 
@@ -225,7 +294,7 @@ impl ProcTypeWidget {
             .iter()
             .enumerate()
             .map(|(i, list_item)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(list_item).bg(color)
             })
             .collect();
@@ -233,7 +302,7 @@ impl ProcTypeWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -242,54 +311,36 @@ impl ProcTypeWidget {
 
     fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
         // get the info
-        let (info, mut title) = if let Some(i) = self.proc_type_entries.state.selected() {
-            let pt_str = match self.proc_type_entries.list[i].proc_type {
-                ProcType::Video => ProcType::Video.as_ref(),
-                ProcType::Audio => ProcType::Audio.as_ref(),
-                ProcType::Image => ProcType::Image.as_ref(),
-                ProcType::Slideshow => ProcType::Slideshow.as_ref(),
-                ProcType::Web => ProcType::Web.as_ref(),
-                ProcType::Browser => ProcType::Browser.as_ref(),
-                ProcType::Executable => ProcType::Executable.as_ref(),
-            };
-            let title_str = pt_str.to_uppercase();
+        let (info, title) = if let Some(i) = self.proc_type_entries.state.selected() {
+            let pt_str = proc_type_label(&self.proc_type_entries.list[i].proc_type);
+            let title_str = tr!("proctype.info_title", kind = pt_str.to_uppercase());
                 (self.proc_type_entries.list[i].info.clone(), title_str)
              } else {
-                 ("Nothing selected...".to_string(), "".to_string())
+                 (tr!("common.nothing_selected").to_string(), tr!("proctype.info_title", kind = ""))
         };
-    
-
-        title.push_str(" INFO");
 
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw(&title).centered().style(FOOTER_STYLE))
+            .title(Line::raw(&title).centered().style(theme().footer))
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(info)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
 
 }
 
-const fn alternate_colors(i: usize) -> Color {
-    if i.is_multiple_of(2) {
-        NORMAL_ROW_BG
-    } else {
-        ALT_ROW_BG_COLOR
-    }
-}
-
 impl Widget for &mut ProcTypeWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        self.targets.screen = area;
         let [header_area, main_area, footer_area] = Layout::vertical([
             Constraint::Length(2),
             Constraint::Fill(1),
@@ -307,6 +358,10 @@ impl Widget for &mut ProcTypeWidget {
         ProcTypeWidget::render_footer(footer_area, buf);
         self.render_list(list_area, buf);
         self.render_selected_item(item_area, buf);
+
+        if self.help {
+            ProcTypeWidget::keymap().render_help(tr!("help.proctype"), area, buf);
+        }
     }
 
 }
@@ -0,0 +1,737 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, Gauge, Padding, Paragraph,
+        Widget, Wrap
+    },
+    DefaultTerminal,
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    io,
+    path::{
+        Component,
+        Path,
+        PathBuf
+    },
+    thread,
+    time::Duration
+};
+use hmac::{
+    Hmac,
+    Mac
+};
+use sha2::Sha256;
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::styles::theme;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::i18n::{
+    weekday_name,
+    yes_no
+};
+use crate::proctype::proc_type_label;
+
+use crate::{
+    Task,
+    ProcType,
+    Autoloop,
+    AdvancedSchedule,
+    env_file_path,
+    load_task,
+    parse_task,
+    write_task
+};
+use crate::schedule::active::day_schedule;
+use crate::mount::{
+    Drive,
+    identify_mounted_drives
+};
+use crate::library::{
+    ImportJob,
+    ImportState,
+    hash_file
+};
+use crate::slideshow::slideshow_files;
+use crate::runner::SystemRunner;
+
+/// The folder on a drive that holds the provisioning file and its media.
+pub const PROVISION_DIR: &str = "mediatimer";
+// the same MT_* keys as the env file, with MT_FILE relative to the provisioning folder
+const PROVISION_FILE: &str = "provision.mt";
+// the sha256 of the provisioning file and of each media file, which is what the signature covers
+const MANIFEST_FILE: &str = "provision.manifest";
+const SIGNATURE_FILE: &str = "provision.sig";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The secret shared by every device in a deployment. Provisioning files must be signed with it,
+/// so a stick that was not prepared by the installer cannot reconfigure the device.
+pub(crate) fn key_path() -> PathBuf {
+    env_file_path().with_file_name("provision.key")
+}
+
+pub(crate) fn read_key() -> Option<Vec<u8>> {
+    fs::read(key_path()).ok()
+        .map(|key| key.trim_ascii().to_vec())
+        .filter(|key| !key.is_empty())
+}
+
+/// The hex HMAC-SHA256 of `contents`.
+pub fn sign(key: &[u8], contents: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(contents);
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Whether `signature` is the hex HMAC-SHA256 of `contents`.
+pub(crate) fn verify(key: &[u8], contents: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let Some(bytes) = (0..signature.len()).step_by(2)
+        .map(|i| u8::from_str_radix(signature.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>() else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(contents);
+    // compares in constant time
+    mac.verify_slice(&bytes).is_ok()
+}
+
+/// The provisioning folder on the first drive that has one.
+pub fn find_provision(drives: &[Drive]) -> Option<PathBuf> {
+    drives.iter()
+        .filter_map(|drive| drive.mountpoint.as_ref())
+        .map(|mountpoint| mountpoint.join(PROVISION_DIR))
+        .find(|dir| dir.join(PROVISION_FILE).is_file())
+}
+
+/// The sha256 of each signed file, by its path relative to the provisioning folder.
+type Manifest = BTreeMap<PathBuf, String>;
+
+fn file_hash(path: &Path) -> io::Result<String> {
+    Ok(hash_file(path)?.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+// a path that stays inside the folder it is relative to
+fn is_inside(path: &Path) -> bool {
+    !path.as_os_str().is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+// "<sha256>  <path>" lines, as written by sha256sum
+fn write_manifest(manifest: &Manifest) -> String {
+    manifest.iter()
+        .map(|(path, hash)| format!("{}  {}\n", hash, path.display()))
+        .collect()
+}
+
+fn parse_manifest(contents: &str) -> Option<Manifest> {
+    contents.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (hash, path) = line.split_once("  ")?;
+            let path = PathBuf::from(path);
+            is_inside(&path).then(|| (path, hash.to_string()))
+        })
+        .collect()
+}
+
+// the files of the media at `root`, relative to it: an empty path when it is a single file
+fn media_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        return Ok(vec![PathBuf::new()]);
+    }
+    Ok(slideshow_files(root, true)?
+        .into_iter()
+        .filter_map(|file| file.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect())
+}
+
+fn join_relative(root: &Path, relative: &Path) -> PathBuf {
+    if relative.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(relative)
+    }
+}
+
+/// Checks that the media named `media` in the manifest, read from `found`, has exactly the files
+/// the manifest lists for it and that none of them has changed. `found` is the media on the drive,
+/// or the copy of it in the library.
+fn check_media(manifest: &Manifest, media: &Path, found: &Path) -> Result<(), Box<dyn Error>> {
+    let files = media_files(found)?;
+    for relative in files.iter() {
+        let name = join_relative(media, relative);
+        let hash = manifest.get(&name)
+            .ok_or_else(|| tr!("provision.unlisted", file = name.display()))?;
+        if file_hash(&join_relative(found, relative))? != *hash {
+            logw!("Rejected provisioned media {} that was changed after signing", name.display());
+            return Err(tr!("provision.changed", file = name.display()).into());
+        }
+    }
+    let listed = manifest.keys().find(|path| path.starts_with(media)
+        && !files.iter().any(|relative| join_relative(media, relative) == **path));
+    match listed {
+        Some(missing) => Err(tr!("provision.media_missing", file = missing.display()).into()),
+        None => Ok(())
+    }
+}
+
+/// A checked provisioning file.
+#[derive(Debug)]
+pub struct Provision {
+    pub task: Task,
+    // the media on the drive that is copied onto the device
+    pub media: Option<PathBuf>,
+    // checks the copy of the media, as the drive could change while it is copied
+    manifest: Manifest
+}
+
+/// Reads and checks the provisioning file in `dir` and its media. The manifest of them must be
+/// signed with `key`.
+pub fn read_provision(dir: &Path, key: Option<&[u8]>) -> Result<Provision, Box<dyn Error>> {
+    let key = key.ok_or_else(|| tr!("provision.not_enabled", path = key_path().display()))?;
+    let signed = fs::read(dir.join(MANIFEST_FILE))
+        .map_err(|_| tr!("provision.not_signed", file = PROVISION_FILE))?;
+    let signature = fs::read_to_string(dir.join(SIGNATURE_FILE))
+        .map_err(|_| tr!("provision.not_signed", file = PROVISION_FILE))?;
+    if !verify(key, &signed, &signature) {
+        logw!("Rejected provisioning file with a bad signature in {}", dir.display());
+        return Err(tr!("provision.bad_signature", file = PROVISION_FILE).into());
+    }
+    let manifest = parse_manifest(&String::from_utf8_lossy(&signed))
+        .ok_or_else(|| tr!("provision.not_signed", file = PROVISION_FILE))?;
+
+    let contents = fs::read(dir.join(PROVISION_FILE))?;
+    if manifest.get(Path::new(PROVISION_FILE)) != Some(&file_hash(&dir.join(PROVISION_FILE))?) {
+        logw!("Rejected provisioning file that was changed after signing in {}", dir.display());
+        return Err(tr!("provision.changed", file = PROVISION_FILE).into());
+    }
+
+    let vars = dotenvy::from_read_iter(contents.as_slice()).collect::<Result<Vec<_>, _>>()?;
+    let task = parse_task(vars)?;
+
+    let media = match task.proc_type {
+        ProcType::Web => {
+            if task.url.is_empty() {
+                return Err(tr!("provision.no_url").into());
+            }
+            None
+        },
+        ProcType::Video | ProcType::Audio | ProcType::Image | ProcType::Slideshow => {
+            // the media must be inside the provisioning folder
+            if !is_inside(&task.file) {
+                return Err(tr!("provision.outside_folder").into());
+            }
+            let media = dir.join(&task.file);
+            if !media.exists() {
+                return Err(tr!("provision.media_missing", file = task.file.display()).into());
+            }
+            check_media(&manifest, &task.file, &media)?;
+            Some(media)
+        },
+        ProcType::Browser | ProcType::Executable => {
+            return Err(tr!("provision.unsupported", kind = proc_type_label(&task.proc_type)).into());
+        }
+    };
+
+    Ok(Provision {
+        task,
+        media,
+        manifest
+    })
+}
+
+fn describe(task: &Task) -> Vec<(String, String)> {
+    let mut rows = vec![
+        (tr!("review.type").to_string(), proc_type_label(&task.proc_type).to_string()),
+        (tr!("wizard.media").to_string(), task.file.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()),
+        (tr!("review.url").to_string(), task.url.clone()),
+        (tr!("review.loop").to_string(), yes_no(task.auto_loop == Autoloop::Yes).to_string()),
+        (tr!("review.schedule").to_string(), yes_no(task.advanced_schedule == AdvancedSchedule::Yes).to_string()),
+    ];
+    for day in 0..7 {
+        let times = day_schedule(&task.timings, day)
+            .map(|schedule| schedule.iter()
+                .map(|(start, end)| format!("{}-{}", start, end))
+                .collect::<Vec<_>>()
+                .join(", "))
+            .unwrap_or_default();
+        rows.push((weekday_name(day).to_string(), times));
+    }
+    rows.push((tr!("review.slide_delay").to_string(), task.slide_delay.to_string()));
+    rows.push((tr!("provision.player").to_string(), task.media_player.to_string()));
+    rows
+}
+
+/// What applying `new` would change, one line per setting, e.g. "Media: intro.mp4 → lobby.mp4".
+pub fn changes(current: &Task, new: &Task) -> Vec<String> {
+    describe(current).into_iter()
+        .zip(describe(new))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((name, old), (_, new))| {
+            let show = |value: String| if value.is_empty() { tr!("provision.none").to_string() } else { value };
+            tr!("provision.change", name = name, old = show(old), new = show(new))
+        })
+        .collect()
+}
+
+/// Checks the copy of the media in the library against the manifest, then saves the provisioned
+/// task pointing at it. A copy that does not match is removed, as nothing else uses it yet.
+fn apply(provision: Provision, copy: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let mut task = provision.task;
+    if let Some(copy) = copy {
+        if let Err(e) = check_media(&provision.manifest, &task.file, &copy) {
+            let _ = if copy.is_dir() { fs::remove_dir_all(&copy) } else { fs::remove_file(&copy) };
+            return Err(e);
+        }
+        task.file = copy;
+    }
+    // the media now lives on the device rather than on a drive
+    task.uuid.clear();
+    write_task(task)
+}
+
+/// `mediatimer provision` applies a provisioning file without the setup screens.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let drives = identify_mounted_drives(&SystemRunner)?;
+    let Some(dir) = find_provision(&drives) else {
+        println!("No provisioning file was found on any drive.");
+        return Ok(());
+    };
+    let provision = read_provision(&dir, read_key().as_deref())?;
+    let current = load_task(&env_file_path())?;
+    for change in changes(&current, &provision.task) {
+        println!("{}", change);
+    }
+
+    let copy = match provision.media.as_ref() {
+        Some(media) => {
+            println!("Copying {}", media.display());
+            let mut job = ImportJob::start(media)?;
+            loop {
+                match job.poll() {
+                    ImportState::Running(_) => thread::sleep(Duration::from_millis(250)),
+                    ImportState::Finished(copy) => break Some(copy),
+                    ImportState::Failed(reason) => return Err(reason.into())
+                }
+            }
+        },
+        None => None
+    };
+    apply(provision, copy)?;
+    logi!("Provisioned from {}", dir.display());
+    println!("Configuration applied from {}", dir.display());
+    Ok(())
+}
+
+// writes the manifest of the provisioning file in `dir` and its media, and signs it with `key`.
+// Returns how many media files it lists.
+fn write_signature(dir: &Path, key: &[u8]) -> Result<usize, Box<dyn Error>> {
+    let contents = fs::read(dir.join(PROVISION_FILE))?;
+    let vars = dotenvy::from_read_iter(contents.as_slice()).collect::<Result<Vec<_>, _>>()?;
+    let task = parse_task(vars)?;
+
+    let mut manifest = Manifest::new();
+    manifest.insert(PathBuf::from(PROVISION_FILE), file_hash(&dir.join(PROVISION_FILE))?);
+    if is_inside(&task.file) && dir.join(&task.file).exists() {
+        for relative in media_files(&dir.join(&task.file))? {
+            let name = join_relative(&task.file, &relative);
+            manifest.insert(name.clone(), file_hash(&dir.join(&name))?);
+        }
+    }
+    let signed = write_manifest(&manifest);
+    fs::write(dir.join(MANIFEST_FILE), &signed)?;
+    fs::write(dir.join(SIGNATURE_FILE), format!("{}\n", sign(key, signed.as_bytes())))?;
+    Ok(manifest.len() - 1)
+}
+
+/// `mediatimer sign <folder>` signs the provisioning file in a folder, and the media it names,
+/// with this device's key. Changing either afterwards means signing again.
+pub fn sign_folder(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let key = read_key().ok_or_else(|| format!("No deployment key found at {}", key_path().display()))?;
+    let media = write_signature(dir, &key)?;
+    println!("Signed {} and {} media files", dir.join(PROVISION_FILE).display(), media);
+    Ok(())
+}
+
+enum Screen {
+    Review,
+    Copying(ImportJob),
+    Done
+}
+
+/// Shows a provisioning file found on a drive and applies it once confirmed.
+pub struct ProvisionWidget {
+    should_exit: bool,
+    screen: Screen,
+    dir: PathBuf,
+    provision: Result<Provision, String>,
+    changes: Vec<String>,
+    progress: f64,
+    message: String,
+    applied: bool,
+    help: bool
+}
+
+impl ProvisionWidget {
+    pub(crate) fn new(dir: PathBuf, current: &Task) -> Self {
+        let provision = read_provision(&dir, read_key().as_deref()).map_err(|e| e.to_string());
+        if let Err(e) = provision.as_ref() {
+            loge!("Provisioning file in {} rejected: {}", dir.display(), e);
+        }
+        let changes = provision.as_ref()
+            .map(|provision| changes(current, &provision.task))
+            .unwrap_or_default();
+        Self {
+            should_exit: false,
+            screen: Screen::Review,
+            dir,
+            provision,
+            changes,
+            progress: 0.0,
+            message: String::new(),
+            applied: false,
+            help: false
+        }
+    }
+
+    /// Returns true when the configuration was replaced.
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<bool, Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            if matches!(self.screen, Screen::Copying(_)) {
+                // keep the progress bar moving while waiting for a key
+                if event::poll(Duration::from_millis(250))? && let Event::Key(key) = event::read()? {
+                    self.handle_key(key);
+                }
+                self.update_copy();
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            };
+        }
+        Ok(self.applied)
+    }
+
+    fn keymap(&self) -> Keymap {
+        let keymap = match (&self.screen, self.provision.is_ok()) {
+            (Screen::Review, true) => Keymap::default()
+                .bind(&[KeyCode::Enter], Action::Apply)
+                .bind(&[KeyCode::Char('q'), KeyCode::Esc], Action::Skip),
+            (Screen::Review, false) => Keymap::default()
+                .bind(&[KeyCode::Enter, KeyCode::Char('q'), KeyCode::Esc], Action::Continue),
+            (Screen::Copying(_), _) => Keymap::default()
+                .bind(&[KeyCode::Char('q'), KeyCode::Esc], Action::Cancel),
+            // any other key continues
+            (Screen::Done, _) => Keymap::default()
+        };
+        keymap.bind(HELP_KEYS, Action::Help)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        let action = self.keymap().action(key.code);
+        match (&self.screen, action) {
+            (_, Some(Action::Help)) => self.help = true,
+            (Screen::Review, Some(Action::Apply)) => self.start(),
+            (Screen::Review, Some(Action::Skip | Action::Continue)) => self.should_exit = true,
+            (Screen::Copying(_), Some(Action::Cancel)) => {
+                if let Screen::Copying(job) = std::mem::replace(&mut self.screen, Screen::Done) {
+                    job.cancel();
+                    self.message = tr!("provision.cancelled").to_string();
+                }
+            },
+            (Screen::Done, _) => self.should_exit = true,
+            _ => {}
+        }
+    }
+
+    fn start(&mut self) {
+        let media = self.provision.as_ref().ok().and_then(|provision| provision.media.clone());
+        match media {
+            Some(media) => match ImportJob::start(&media) {
+                Ok(job) => self.screen = Screen::Copying(job),
+                Err(e) => self.finish(Err(e.to_string()))
+            },
+            None => self.finish(Ok(None))
+        }
+    }
+
+    fn update_copy(&mut self) {
+        let Screen::Copying(job) = &mut self.screen else {
+            return;
+        };
+        match job.poll() {
+            ImportState::Running(progress) => self.progress = progress,
+            ImportState::Finished(copy) => self.finish(Ok(Some(copy))),
+            ImportState::Failed(reason) => self.finish(Err(reason))
+        }
+    }
+
+    fn finish(&mut self, copy: Result<Option<PathBuf>, String>) {
+        self.screen = Screen::Done;
+        let provision = std::mem::replace(&mut self.provision, Err(String::from("Already applied")));
+        let result = copy.and_then(|copy| match provision {
+            Ok(provision) => apply(provision, copy).map_err(|e| e.to_string()),
+            Err(e) => Err(e)
+        });
+        match result {
+            Ok(()) => {
+                logi!("Provisioned from {}", self.dir.display());
+                self.applied = true;
+                self.message = tr!("provision.applied").to_string();
+            },
+            Err(e) => {
+                loge!("Provisioning failed: {}", e);
+                self.message = tr!("provision.failed", reason = e);
+            }
+        }
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("provision.header"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let text = match (&self.screen, self.provision.is_ok()) {
+            (Screen::Review, true) => tr!("provision.apply_hint"),
+            (Screen::Review, false) => tr!("provision.skip_hint"),
+            (Screen::Copying(_), _) => tr!("provision.cancel_hint"),
+            (Screen::Done, _) => tr!("provision.continue_hint")
+        };
+        Paragraph::new(text)
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_text(&self, area: Rect, buf: &mut Buffer) {
+        let mut text = vec![
+            Line::from(tr!("provision.found", path = self.dir.display())),
+            Line::from(""),
+        ];
+        match (&self.screen, &self.provision) {
+            (Screen::Done, _) => text.push(Line::from(self.message.as_str())),
+            (_, Err(e)) => {
+                text.push(Line::from(tr!("provision.unusable")));
+                text.push(Line::from(e.as_str()));
+            },
+            (_, Ok(_)) if self.changes.is_empty() => text.push(Line::from(tr!("provision.unchanged"))),
+            (_, Ok(_)) => {
+                text.push(Line::from(tr!("provision.will_change")));
+                text.extend(self.changes.iter().map(|change| Line::from(change.as_str())));
+            }
+        }
+
+        let block = Block::new()
+            .title(Line::raw(tr!("provision.title")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(text)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn render_progress(&self, area: Rect, buf: &mut Buffer) {
+        Gauge::default()
+            .block(Block::new().title(Line::raw(tr!("provision.copying")).centered()))
+            .gauge_style(theme().item_header)
+            .ratio(self.progress)
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut ProvisionWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, progress_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        ProvisionWidget::render_header(header_area, buf);
+        self.render_footer(footer_area, buf);
+        self.render_text(main_area, buf);
+        if matches!(self.screen, Screen::Copying(_)) {
+            self.render_progress(progress_area, buf);
+        }
+
+        if self.help {
+            self.keymap().render_help(tr!("help.provision"), area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mount::tests::drive;
+    use tempfile::tempdir;
+
+    const KEY: &[u8] = b"deployment secret";
+
+    // a provisioning folder with `media` files in it, signed with KEY
+    fn provision_dir(contents: &str, media: &[&str]) -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(PROVISION_FILE), contents).unwrap();
+        for file in media {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file.as_bytes()).unwrap();
+        }
+        write_signature(dir.path(), KEY).unwrap();
+        dir
+    }
+
+    #[test]
+    fn check_sign() {
+        // RFC 4231 test case 2
+        assert_eq!(sign(b"Jefe", b"what do ya want for nothing?"), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        let signature = sign(KEY, b"MT_PROCTYPE=\"video\"");
+        assert!(verify(KEY, b"MT_PROCTYPE=\"video\"", &format!("{}\n", signature)));
+        assert!(!verify(KEY, b"MT_PROCTYPE=\"web\"", &signature));
+        assert!(!verify(b"another key", b"MT_PROCTYPE=\"video\"", &signature));
+        assert!(!verify(KEY, b"MT_PROCTYPE=\"video\"", "not hex"));
+    }
+
+    #[test]
+    fn check_read_provision() {
+        let dir = provision_dir("MT_PROCTYPE=\"video\"\nMT_FILE=\"films/intro.mp4\"\nMT_SCHEDULE=\"true\"\nMT_MONDAY=09:00:00-17:00:00\n", &["films/intro.mp4"]);
+
+        let provision = read_provision(dir.path(), Some(KEY)).unwrap();
+        assert_eq!(provision.task.proc_type, ProcType::Video);
+        assert_eq!(provision.media, Some(dir.path().join("films/intro.mp4")));
+        assert_eq!(day_schedule(&provision.task.timings, 0), Some(&vec![(String::from("09:00:00"), String::from("17:00:00"))]));
+    }
+
+    #[test]
+    fn check_read_provision_rejected() {
+        let dir = provision_dir("MT_PROCTYPE=\"video\"\nMT_FILE=\"../intro.mp4\"\n", &[]);
+        // no key on the device
+        assert!(read_provision(dir.path(), None).is_err());
+        // signed with a different key
+        assert!(read_provision(dir.path(), Some(b"another key")).unwrap_err().to_string().contains("signature"));
+        // media outside the provisioning folder
+        assert!(read_provision(dir.path(), Some(KEY)).unwrap_err().to_string().contains("MT_FILE"));
+
+        let dir = provision_dir("MT_PROCTYPE=\"audio\"\nMT_FILE=\"missing.mp3\"\n", &[]);
+        assert!(read_provision(dir.path(), Some(KEY)).unwrap_err().to_string().contains("could not be found"));
+
+        let dir = provision_dir("MT_PROCTYPE=\"executable\"\nMT_FILE=\"run.sh\"\n", &[]);
+        assert!(read_provision(dir.path(), Some(KEY)).is_err());
+
+        // edited after signing
+        let dir = provision_dir("MT_PROCTYPE=\"web\"\nMT_URL=\"https://example.com\"\n", &[]);
+        assert!(read_provision(dir.path(), Some(KEY)).is_ok());
+        fs::write(dir.path().join(PROVISION_FILE), "MT_PROCTYPE=\"web\"\nMT_URL=\"https://example.net\"\n").unwrap();
+        assert!(read_provision(dir.path(), Some(KEY)).is_err());
+    }
+
+    #[test]
+    fn check_signed_media() {
+        let dir = provision_dir("MT_PROCTYPE=\"slideshow\"\nMT_FILE=\"slides\"\n", &["slides/a.jpg", "slides/more/b.jpg", "notes.txt"]);
+        let provision = read_provision(dir.path(), Some(KEY)).unwrap();
+        assert_eq!(provision.manifest.len(), 3);
+
+        // one byte of an image changed after signing
+        fs::write(dir.path().join("slides/more/b.jpg"), "slides/more/b.jpG").unwrap();
+        assert!(read_provision(dir.path(), Some(KEY)).unwrap_err().to_string().contains("slides/more/b.jpg"));
+        write_signature(dir.path(), KEY).unwrap();
+        assert!(read_provision(dir.path(), Some(KEY)).is_ok());
+
+        // an image added after signing
+        fs::write(dir.path().join("slides/c.jpg"), "c").unwrap();
+        assert!(read_provision(dir.path(), Some(KEY)).unwrap_err().to_string().contains("slides/c.jpg"));
+        fs::remove_file(dir.path().join("slides/c.jpg")).unwrap();
+        // and one removed
+        fs::remove_file(dir.path().join("slides/a.jpg")).unwrap();
+        assert!(read_provision(dir.path(), Some(KEY)).unwrap_err().to_string().contains("slides/a.jpg"));
+    }
+
+    #[test]
+    fn check_copied_media() {
+        let dir = provision_dir("MT_PROCTYPE=\"video\"\nMT_FILE=\"intro.mp4\"\n", &["intro.mp4"]);
+        let provision = read_provision(dir.path(), Some(KEY)).unwrap();
+        let copy = dir.path().join("copy.mp4");
+        fs::copy(dir.path().join("intro.mp4"), &copy).unwrap();
+        assert!(check_media(&provision.manifest, &provision.task.file, &copy).is_ok());
+        // the stick was changed while it was copied
+        fs::write(&copy, "intro.mp5").unwrap();
+        assert!(check_media(&provision.manifest, &provision.task.file, &copy).is_err());
+    }
+
+    #[test]
+    fn check_changes() {
+        let current = parse_task(vec![
+            (String::from("MT_PROCTYPE"), String::from("video")),
+            (String::from("MT_FILE"), String::from("/media/pi/USB/intro.mp4")),
+        ]).unwrap();
+        let new = parse_task(vec![
+            (String::from("MT_PROCTYPE"), String::from("video")),
+            (String::from("MT_FILE"), String::from("lobby.mp4")),
+            (String::from("MT_MONDAY"), String::from("09:00-17:00")),
+        ]).unwrap();
+
+        assert_eq!(changes(&current, &new), vec![
+            String::from("Media: intro.mp4 → lobby.mp4"),
+            String::from("Monday: none → 09:00-17:00")
+        ]);
+        assert!(changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn check_find_provision() {
+        let dir = tempdir().unwrap();
+        let stick = dir.path().join("STICK");
+        fs::create_dir_all(stick.join(PROVISION_DIR)).unwrap();
+        fs::write(stick.join(PROVISION_DIR).join(PROVISION_FILE), "").unwrap();
+        let at = |mountpoint: PathBuf| drive("1234-ABCD", None, &mountpoint);
+
+        assert_eq!(find_provision(&[at(dir.path().join("OTHER")), at(stick.clone())]), Some(stick.join(PROVISION_DIR)));
+        assert_eq!(find_provision(&[at(dir.path().join("OTHER"))]), None);
+    }
+}
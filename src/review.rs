@@ -0,0 +1,332 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{
+        Stylize,
+    },
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, ListState, Padding, Paragraph,
+        StatefulWidget, Widget, Wrap, ListItem, List,
+        HighlightSpacing
+    },
+    DefaultTerminal,
+};
+use std::error::Error;
+
+use crate::styles::theme;
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap
+};
+use crate::i18n::{
+    weekday_short_name,
+    yes_no
+};
+use crate::proctype::proc_type_label;
+
+use crate::{
+    Task,
+    ProcType,
+    Autoloop,
+    AdvancedSchedule
+};
+use crate::schedule::active::day_schedule;
+use crate::provision::changes;
+
+/// What was chosen on the review screen.
+#[derive(Debug, PartialEq)]
+pub enum Review {
+    Apply,
+    /// Go back to the step with this index, in the order the steps were shown.
+    Edit(usize),
+    Cancel
+}
+
+/// The settings of `task` as label and value pairs, one per line.
+pub(crate) fn summary(task: &Task) -> Vec<(String, String)> {
+    let mut rows = vec![(tr!("review.type").to_string(), proc_type_label(&task.proc_type).to_string())];
+    if task.proc_type == ProcType::Web {
+        rows.push((tr!("review.url").to_string(), task.url.clone()));
+    } else {
+        rows.push((tr!("review.file").to_string(), task.file.display().to_string()));
+        rows.push((tr!("review.uuid").to_string(), if task.uuid.is_empty() {
+            tr!("review.no_uuid").to_string()
+        } else {
+            task.uuid.clone()
+        }));
+    }
+    if task.proc_type == ProcType::Slideshow {
+        rows.push((tr!("review.slide_delay").to_string(), tr!("review.seconds", count = task.slide_delay)));
+    }
+    rows.push((tr!("review.loop").to_string(), yes_no(task.auto_loop == Autoloop::Yes).to_string()));
+    if task.advanced_schedule == AdvancedSchedule::No {
+        rows.push((tr!("review.schedule").to_string(), tr!("review.always").to_string()));
+        return rows;
+    }
+    for day in 0..7 {
+        let times = match day_schedule(&task.timings, day) {
+            Some(schedule) if !schedule.is_empty() => schedule.iter()
+                .map(|(start, end)| format!("{}-{}", start, end))
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => tr!("review.off").to_string()
+        };
+        rows.push((weekday_short_name(day).to_string(), times));
+    }
+    rows
+}
+
+/// Shows the configuration about to be written next to what is saved now.
+pub struct ReviewWidget {
+    should_exit: bool,
+    summary: Vec<(String, String)>,
+    // None when nothing has been saved yet
+    changes: Option<Vec<String>>,
+    steps: Vec<&'static str>,
+    state: ListState,
+    review: Review,
+    help: bool
+}
+
+impl ReviewWidget {
+    /// `steps` are the names of the setup screens that were shown, in order.
+    pub(crate) fn new(task: &Task, saved: Option<&Task>, steps: Vec<&'static str>) -> Self {
+        let mut state = ListState::default();
+        state.select_first();
+        Self {
+            should_exit: false,
+            summary: summary(task),
+            changes: saved.map(|saved| changes(saved, task)),
+            review: Review::Edit(steps.len().saturating_sub(1)),
+            steps,
+            state,
+            help: false
+        }
+    }
+
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Review, Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            };
+        }
+        Ok(self.review)
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::list()
+            .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose)
+            .bind(&[KeyCode::Esc], Action::Back)
+            .bind(&[KeyCode::Char('q')], Action::Quit)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match ReviewWidget::keymap().action(key.code) {
+            // back to the last step, which is where the review was started from
+            Some(Action::Back) => self.leave(Review::Edit(self.steps.len().saturating_sub(1))),
+            Some(Action::Quit) => self.leave(Review::Cancel),
+            Some(Action::Next) => self.state.select_next(),
+            Some(Action::Previous) => self.state.select_previous(),
+            Some(Action::First) => self.state.select_first(),
+            Some(Action::Last) => self.state.select_last(),
+            Some(Action::Help) => self.help = true,
+            Some(Action::Choose) => {
+                if let Some(review) = self.state.selected().and_then(|i| self.action(i)) {
+                    self.leave(review);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, review: Review) {
+        self.review = review;
+        self.should_exit = true;
+    }
+
+    /// The actions are Apply, one Edit per step and Cancel.
+    fn action(&self, index: usize) -> Option<Review> {
+        match index {
+            0 => Some(Review::Apply),
+            i if i <= self.steps.len() => Some(Review::Edit(i - 1)),
+            i if i == self.steps.len() + 1 => Some(Review::Cancel),
+            _ => None
+        }
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("review.header"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("review.footer"))
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_summary(&self, area: Rect, buf: &mut Buffer) {
+        let width = self.summary.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        let text: Vec<Line> = self.summary.iter()
+            .map(|(label, value)| Line::from(format!("{:width$}  {}", label, value, width = width)))
+            .collect();
+
+        let block = Block::new()
+            .title(Line::raw(tr!("review.summary_title")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(text)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn render_changes(&self, area: Rect, buf: &mut Buffer) {
+        let text: Vec<Line> = match &self.changes {
+            None => vec![Line::from(tr!("review.nothing_saved"))],
+            Some(changes) if changes.is_empty() => vec![Line::from(tr!("review.unchanged"))],
+            Some(changes) => changes.iter().map(|change| Line::from(change.as_str())).collect()
+        };
+
+        let block = Block::new()
+            .title(Line::raw(tr!("review.changes_title")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(text)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(tr!("review.question")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        let actions = std::iter::once(tr!("review.apply").to_string())
+            .chain(self.steps.iter().map(|step| tr!("review.edit", step = step)))
+            .chain(std::iter::once(tr!("review.cancel").to_string()));
+        let items: Vec<ListItem> = actions
+            .enumerate()
+            .map(|(i, action)| {
+                let color = theme().row_bg(i);
+                ListItem::new(Line::styled(action, theme().text_fg)).bg(color)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme().selected)
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+}
+
+impl Widget for &mut ReviewWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [details_area, list_area] = Layout::vertical([
+            Constraint::Fill(2),
+            Constraint::Fill(1)
+        ])
+        .areas(main_area);
+
+        let [summary_area, changes_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1)
+        ])
+        .areas(details_area);
+
+        ReviewWidget::render_header(header_area, buf);
+        ReviewWidget::render_footer(footer_area, buf);
+        self.render_summary(summary_area, buf);
+        self.render_changes(changes_area, buf);
+        self.render_list(list_area, buf);
+
+        if self.help {
+            ReviewWidget::keymap().render_help(tr!("help.review"), area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::task;
+    use crate::parse_task;
+
+    #[test]
+    fn check_summary() {
+        let slideshow = task(&[
+            ("MT_PROCTYPE", "slideshow"),
+            ("MT_FILE", "/media/pi/STICK/photos"),
+            ("MT_UUID", "1234-ABCD"),
+            ("MT_SLIDE_DELAY", "8"),
+            ("MT_SCHEDULE", "true"),
+            ("MT_MONDAY", "09:00-12:00,13:00-17:00"),
+        ]);
+        let rows = summary(&slideshow);
+        assert!(rows.contains(&(String::from("Drive UUID"), String::from("1234-ABCD"))));
+        assert!(rows.contains(&(String::from("Slide delay"), String::from("8 seconds"))));
+        assert!(rows.contains(&(String::from("Mon"), String::from("09:00-12:00, 13:00-17:00"))));
+        assert!(rows.contains(&(String::from("Sun"), String::from("off"))));
+
+        let web = task(&[("MT_PROCTYPE", "web"), ("MT_URL", "https://example.com")]);
+        assert_eq!(summary(&web), vec![
+            (String::from("Type"), String::from("Web")),
+            (String::from("Web address"), String::from("https://example.com")),
+            (String::from("Loop"), String::from("Yes")),
+            (String::from("Schedule"), String::from("Plays all the time"))
+        ]);
+    }
+
+    #[test]
+    fn check_actions() {
+        let web = task(&[("MT_PROCTYPE", "web")]);
+        let mut widget = ReviewWidget::new(&web, None, vec!["Type", "Web address", "Schedule"]);
+        assert_eq!(widget.action(0), Some(Review::Apply));
+        assert_eq!(widget.action(2), Some(Review::Edit(1)));
+        assert_eq!(widget.action(4), Some(Review::Cancel));
+        assert_eq!(widget.action(5), None);
+
+        widget.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(widget.review, Review::Edit(2));
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    io,
+    process::{
+        Command,
+        Output
+    }
+};
+
+/// Runs external programs such as lsblk, udisksctl, systemctl and ffprobe and collects their
+/// output. Code that parses what these programs print takes a runner so that it can be tested
+/// against recorded output.
+pub trait CommandRunner {
+    fn output(&self, command: &mut Command) -> io::Result<Output>;
+}
+
+/// Runs the command on this machine.
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        command.output()
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::{
+        cell::RefCell,
+        collections::VecDeque,
+        os::unix::process::ExitStatusExt,
+        process::ExitStatus
+    };
+
+    /// The program and arguments of a command as one line, e.g. "udisksctl mount -b /dev/sdb1".
+    pub fn command_line(command: &Command) -> String {
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Replays recorded output. Each command must be the next one in the script, otherwise it
+    /// fails as if the program was not installed.
+    #[derive(Default)]
+    pub struct FakeRunner {
+        script: RefCell<VecDeque<(String, Output)>>,
+        calls: RefCell<Vec<String>>
+    }
+
+    impl FakeRunner {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Expects `program` to be run next and to print `stdout`.
+        pub fn respond(self, program: &str, stdout: &str) -> Self {
+            self.respond_with(program, 0, stdout, "")
+        }
+
+        pub fn respond_with(self, program: &str, code: i32, stdout: &str, stderr: &str) -> Self {
+            self.script.borrow_mut().push_back((String::from(program), Output {
+                // the raw wait status keeps the exit code in the second byte
+                status: ExitStatus::from_raw(code << 8),
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: stderr.as_bytes().to_vec()
+            }));
+            self
+        }
+
+        /// Every command run so far.
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.borrow().clone()
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn output(&self, command: &mut Command) -> io::Result<Output> {
+            let line = command_line(command);
+            self.calls.borrow_mut().push(line.clone());
+            let mut script = self.script.borrow_mut();
+            if !script.front().is_some_and(|(program, _)| command.get_program() == program.as_str()) {
+                return Err(io::Error::new(io::ErrorKind::NotFound, format!("unexpected command: {}", line)));
+            }
+            Ok(script.pop_front().expect("checked above").1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::fake::FakeRunner;
+
+    #[test]
+    fn check_fake_runner() {
+        let runner = FakeRunner::new()
+            .respond("lsblk", "{}")
+            .respond_with("udisksctl", 1, "", "Error mounting");
+
+        let output = runner.output(Command::new("lsblk").arg("-J")).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"{}");
+
+        // out of order
+        assert!(runner.output(&mut Command::new("systemctl")).is_err());
+
+        let output = runner.output(Command::new("udisksctl").args(["mount", "-b", "/dev/sdb1"])).unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(runner.calls(), vec!["lsblk -J", "systemctl", "udisksctl mount -b /dev/sdb1"]);
+    }
+}
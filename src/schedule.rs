@@ -1,3 +1,5 @@
 pub mod timings;
 pub mod import;
 pub mod export;
+pub mod active;
+pub mod picker;
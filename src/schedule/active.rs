@@ -0,0 +1,176 @@
+use crate::{
+    Schedule,
+    Timings,
+    Weekday
+};
+
+pub const DAY_SECONDS: u32 = 86_400;
+
+/// A single scheduled period. `day` is the day the period starts on (0 = Monday) and `start`
+/// and `end` are seconds since midnight. Periods whose end is before their start run past
+/// midnight into the following day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    pub day: usize,
+    pub start: u32,
+    pub end: u32,
+}
+
+// accepts both 00:00 and 00:00:00, matching to_weekday
+pub fn time_to_seconds(time: &str) -> Option<u32> {
+    let parts = time.trim()
+        .split(":")
+        .map(|x| x.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m] => (*h, *m, 0),
+        [h, m, s] => (*h, *m, *s),
+        _ => return None
+    };
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    Some(hours * 3600 + minutes * 60 + seconds)
+}
+
+// the timings vec is not guaranteed to be in weekday order, so match on the variant
+pub fn day_schedule(timings: &Timings, day: usize) -> Option<&Schedule> {
+    timings.iter().find_map(|weekday| match (weekday, day) {
+        (Weekday::Monday(schedule), 0) => Some(schedule),
+        (Weekday::Tuesday(schedule), 1) => Some(schedule),
+        (Weekday::Wednesday(schedule), 2) => Some(schedule),
+        (Weekday::Thursday(schedule), 3) => Some(schedule),
+        (Weekday::Friday(schedule), 4) => Some(schedule),
+        (Weekday::Saturday(schedule), 5) => Some(schedule),
+        (Weekday::Sunday(schedule), 6) => Some(schedule),
+        _ => None
+    })
+}
+
+pub fn day_windows(timings: &Timings, day: usize) -> Vec<Window> {
+    let mut windows = Vec::with_capacity(2);
+    if let Some(schedule) = day_schedule(timings, day) {
+        for (start, end) in schedule.iter() {
+            if let (Some(start), Some(end)) = (time_to_seconds(start), time_to_seconds(end)) {
+                windows.push(Window { day, start, end });
+            }
+        }
+    }
+    windows.sort_by_key(|w| w.start);
+    windows
+}
+
+/// Returns the window that should be playing on `day` (0 = Monday) at `seconds` since midnight.
+pub fn active_window(timings: &Timings, day: usize, seconds: u32) -> Option<Window> {
+    for window in day_windows(timings, day) {
+        let end_today = if window.end < window.start { DAY_SECONDS } else { window.end };
+        if seconds >= window.start && seconds < end_today {
+            return Some(window);
+        }
+    }
+
+    // overnight windows that started the day before
+    let yesterday = (day + 6) % 7;
+    day_windows(timings, yesterday)
+        .into_iter()
+        .find(|window| window.end < window.start && seconds < window.end)
+}
+
+/// When playback next starts or stops after `day` (0 = Monday) at `seconds` since midnight, as
+/// the day and seconds since midnight of the change. None when the schedule never changes.
+pub fn next_change(timings: &Timings, day: usize, seconds: u32) -> Option<(usize, u32)> {
+    let playing = active_window(timings, day, seconds).is_some();
+    // every start and end in the coming week, counted in seconds from the start of `day`
+    let mut boundaries = Vec::new();
+    for offset in 0..=7 {
+        for window in day_windows(timings, (day + offset) % 7) {
+            let start = offset as u32 * DAY_SECONDS + window.start;
+            let end = offset as u32 * DAY_SECONDS + window.end + if window.end < window.start { DAY_SECONDS } else { 0 };
+            boundaries.extend([start, end]);
+        }
+    }
+    boundaries.sort();
+    // back to back windows start and stop at the same moment without a change
+    boundaries.into_iter()
+        .filter(|at| *at > seconds)
+        .map(|at| ((day + (at / DAY_SECONDS) as usize) % 7, at % DAY_SECONDS))
+        .find(|(day, seconds)| active_window(timings, *day, *seconds).is_some() != playing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn week_with(day: Weekday) -> Timings {
+        let mut timings = vec![
+            Weekday::Monday(Vec::new()),
+            Weekday::Tuesday(Vec::new()),
+            Weekday::Wednesday(Vec::new()),
+            Weekday::Thursday(Vec::new()),
+            Weekday::Friday(Vec::new()),
+            Weekday::Saturday(Vec::new()),
+            Weekday::Sunday(Vec::new()),
+        ];
+        let index = timings.iter().position(|d| d.to_string() == day.to_string()).unwrap();
+        timings[index] = day;
+        timings
+    }
+
+    #[test]
+    fn check_time_to_seconds() {
+        assert_eq!(time_to_seconds("00:00:00"), Some(0));
+        assert_eq!(time_to_seconds("10:00"), Some(36_000));
+        assert_eq!(time_to_seconds("23:59:59"), Some(86_399));
+        assert_eq!(time_to_seconds("24:00:00"), None);
+        assert_eq!(time_to_seconds("10:60"), None);
+        assert_eq!(time_to_seconds("ten"), None);
+    }
+
+    #[test]
+    fn check_active_window() {
+        let timings = week_with(Weekday::Tuesday(vec![
+            (String::from("10:00:00"), String::from("11:00:00")),
+            (String::from("15:30"), String::from("16:45"))
+        ]));
+
+        assert_eq!(active_window(&timings, 1, 9 * 3600), None);
+        assert_eq!(active_window(&timings, 1, 10 * 3600), Some(Window { day: 1, start: 36_000, end: 39_600 }));
+        // the end of a window is exclusive
+        assert_eq!(active_window(&timings, 1, 11 * 3600), None);
+        assert!(active_window(&timings, 1, 16 * 3600).is_some());
+        // same time on a different day
+        assert_eq!(active_window(&timings, 2, 10 * 3600), None);
+    }
+
+    #[test]
+    fn check_overnight_window() {
+        let timings = week_with(Weekday::Sunday(vec![
+            (String::from("22:00:00"), String::from("02:00:00"))
+        ]));
+
+        assert!(active_window(&timings, 6, 23 * 3600).is_some());
+        // runs into Monday morning
+        assert_eq!(active_window(&timings, 0, 3600), Some(Window { day: 6, start: 79_200, end: 7_200 }));
+        assert_eq!(active_window(&timings, 0, 3 * 3600), None);
+    }
+
+    #[test]
+    fn check_next_change() {
+        let timings = week_with(Weekday::Tuesday(vec![
+            (String::from("10:00"), String::from("11:00")),
+            (String::from("11:00"), String::from("12:00")),
+            (String::from("22:00"), String::from("02:00"))
+        ]));
+
+        // Monday evening, the next start is on Tuesday
+        assert_eq!(next_change(&timings, 0, 20 * 3600), Some((1, 10 * 3600)));
+        // back to back windows only stop at the end of the second
+        assert_eq!(next_change(&timings, 1, 10 * 3600), Some((1, 12 * 3600)));
+        // the overnight window stops on Wednesday
+        assert_eq!(next_change(&timings, 1, 23 * 3600), Some((2, 2 * 3600)));
+        // after the last window of the week, the next start is a week away
+        assert_eq!(next_change(&timings, 2, 3 * 3600), Some((1, 10 * 3600)));
+        assert_eq!(next_change(&week_with(Weekday::Monday(Vec::new())), 0, 0), None);
+    }
+}
@@ -0,0 +1,312 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{
+        Position,
+        Rect
+    },
+    style::Stylize,
+    text::{
+        Line,
+        Span
+    },
+    widgets::{
+        Paragraph,
+        Widget,
+        Wrap
+    }
+};
+
+use crate::schedule::active::{
+    DAY_SECONDS,
+    time_to_seconds
+};
+use crate::styles::theme;
+use crate::tr;
+
+/// Reads a time of day such as 17, 9:30, 09:30:15, 9pm or 12:30am, as seconds since midnight.
+pub fn parse_time(text: &str) -> Option<u32> {
+    let text = text.trim().to_lowercase();
+    let (clock, pm) = match text.strip_suffix("am").or_else(|| text.strip_suffix("a.m.")) {
+        Some(clock) => (clock.trim_end(), Some(false)),
+        None => match text.strip_suffix("pm").or_else(|| text.strip_suffix("p.m.")) {
+            Some(clock) => (clock.trim_end(), Some(true)),
+            None => (text.as_str(), None)
+        }
+    };
+    if clock.is_empty() || !clock.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return None;
+    }
+    // a bare hour, e.g. the 9 in 9-17
+    let seconds = if clock.contains(':') {
+        time_to_seconds(clock)?
+    } else {
+        time_to_seconds(&format!("{}:00", clock))?
+    };
+    match pm {
+        None => Some(seconds),
+        // the 12 hour clock runs from 12am to 11:59pm
+        Some(pm) => {
+            let hours = seconds / 3600;
+            if !(1..=12).contains(&hours) {
+                return None;
+            }
+            let hours = hours % 12 + if pm { 12 } else { 0 };
+            Some(hours * 3600 + seconds % 3600)
+        }
+    }
+}
+
+/// Reads a timing such as 09:00:00-17:00:00, 9-17 or 9:30am-5pm as its start and end.
+pub fn parse_range(text: &str) -> Option<(u32, u32)> {
+    let (start, end) = text.split_once('-')?;
+    Some((parse_time(start)?, parse_time(end)?))
+}
+
+/// A time of day written as 09:30:00.
+pub fn format_time(seconds: u32) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+/// How long a timing runs for, e.g. 8h 30m. A timing that ends before it starts runs past midnight.
+pub fn format_duration(start: u32, end: u32) -> String {
+    let length = (end + DAY_SECONDS - start) % DAY_SECONDS;
+    let mut text = format!("{}h {:02}m", length / 3600, length % 3600 / 60);
+    if !length.is_multiple_of(60) {
+        text.push_str(&format!(" {:02}s", length % 60));
+    }
+    text
+}
+
+// the size of each part of a time in seconds, and how many of them fit in the part above it
+const PARTS: [(u32, u32); 3] = [(3600, 24), (60, 60), (1, 60)];
+
+/// The start and end of a timing, set with the arrow keys one part at a time, or typed in
+/// shorthand such as 9-17.
+#[derive(Debug, Clone)]
+pub struct TimeRangePicker {
+    // the start and end as seconds since midnight
+    times: [u32; 2],
+    // the part with focus: 0 to 2 are the hours, minutes and seconds of the start, 3 to 5 of the end
+    part: usize,
+    // shorthand being typed, which replaces the picker until the arrow keys are used. Shorthand
+    // that can be read is kept as the new start and end.
+    typed: String
+}
+
+impl Default for TimeRangePicker {
+    fn default() -> Self {
+        TimeRangePicker::new(9 * 3600, 17 * 3600)
+    }
+}
+
+impl TimeRangePicker {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self {
+            times: [start, end],
+            part: 0,
+            typed: String::new()
+        }
+    }
+
+    /// Starts from a saved timing, such as 09:00:00 and 17:00:00.
+    pub fn from_timing(start: &str, end: &str) -> Self {
+        match (time_to_seconds(start), time_to_seconds(end)) {
+            (Some(start), Some(end)) => TimeRangePicker::new(start, end),
+            _ => TimeRangePicker::default()
+        }
+    }
+
+    /// The timing as 09:00:00-17:00:00, or the typed shorthand as it is when it cannot be read.
+    pub fn text(&self) -> String {
+        let (start, end) = if self.typed.is_empty() {
+            (self.times[0], self.times[1])
+        } else {
+            match parse_range(&self.typed) {
+                Some(range) => range,
+                None => return self.typed.clone()
+            }
+        };
+        format!("{}-{}", format_time(start), format_time(end))
+    }
+
+    // goes back to the picker, starting from the typed shorthand when it can be read
+    fn stop_typing(&mut self) {
+        if let Some((start, end)) = parse_range(&self.typed) {
+            self.times = [start, end];
+        }
+        self.typed.clear();
+    }
+
+    /// Raises or lowers the part with focus by `amount`, going round from 23 hours to 0 and so on
+    /// without changing the other parts.
+    pub fn change(&mut self, amount: i32) {
+        self.stop_typing();
+        let time = &mut self.times[self.part / 3];
+        let (size, count) = PARTS[self.part % 3];
+        let value = (*time / size % count) as i32;
+        let changed = (value + amount).rem_euclid(count as i32) as u32;
+        *time = *time - value as u32 * size + changed * size;
+    }
+
+    pub fn next_part(&mut self) {
+        self.stop_typing();
+        self.part = (self.part + 1).min(5);
+    }
+
+    pub fn previous_part(&mut self) {
+        self.stop_typing();
+        self.part = self.part.saturating_sub(1);
+    }
+
+    /// Moves between the hours of the start and the hours of the end.
+    pub fn other_time(&mut self) {
+        self.stop_typing();
+        self.part = if self.part < 3 { 3 } else { 0 };
+    }
+
+    /// Adds to the shorthand being typed. Characters that cannot be part of a timing are ignored.
+    pub fn type_char(&mut self, c: char) {
+        if c.is_ascii_digit() || matches!(c.to_ascii_lowercase(), ':' | '-' | ' ' | '.' | 'a' | 'p' | 'm') {
+            self.typed.push(c);
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        self.typed.pop();
+    }
+
+    /// Where the cursor goes while shorthand is typed, inside the border of `area`.
+    pub fn cursor(&self, area: Rect) -> Option<Position> {
+        if self.typed.is_empty() {
+            return None;
+        }
+        Some(Position::new(area.x + self.typed.chars().count() as u16 + 1, area.y + 1))
+    }
+
+    // a time with the part that has focus highlighted
+    fn time_spans(&self, index: usize) -> Vec<Span<'static>> {
+        let text = format_time(self.times[index]);
+        let mut spans = Vec::new();
+        for (i, digits) in text.split(':').enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(":"));
+            }
+            let span = Span::raw(digits.to_string());
+            if index * 3 + i == self.part {
+                spans.push(span.style(theme().selected));
+            } else {
+                spans.push(span);
+            }
+        }
+        spans
+    }
+}
+
+impl Widget for &TimeRangePicker {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (time, range) = if self.typed.is_empty() {
+            let mut spans = vec![Span::raw(format!("{} ", tr!("schedule.start"))).bold()];
+            spans.extend(self.time_spans(0));
+            spans.push(Span::raw(format!("   {} ", tr!("schedule.end"))).bold());
+            spans.extend(self.time_spans(1));
+            (Line::from(spans), Some((self.times[0], self.times[1])))
+        } else {
+            (Line::from(self.typed.clone()), parse_range(&self.typed))
+        };
+        let duration = match range {
+            Some((start, end)) if end < start => tr!("schedule.duration_overnight", duration = format_duration(start, end)),
+            Some((start, end)) => tr!("schedule.duration", duration = format_duration(start, end)),
+            None => String::new()
+        };
+
+        Paragraph::new(vec![
+            time,
+            Line::from(duration),
+            Line::default(),
+            Line::from(tr!("schedule.picker_hint")).italic()
+        ])
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_time() {
+        assert_eq!(parse_time("17"), Some(17 * 3600));
+        assert_eq!(parse_time("9:30"), Some(9 * 3600 + 30 * 60));
+        assert_eq!(parse_time("09:30:15"), Some(9 * 3600 + 30 * 60 + 15));
+        assert_eq!(parse_time("9:30pm"), Some(21 * 3600 + 30 * 60));
+        assert_eq!(parse_time("5 PM"), Some(17 * 3600));
+        assert_eq!(parse_time("12am"), Some(0));
+        assert_eq!(parse_time("12pm"), Some(12 * 3600));
+        assert_eq!(parse_time("13pm"), None);
+        assert_eq!(parse_time("24"), None);
+        assert_eq!(parse_time("9:"), None);
+        assert_eq!(parse_time("+9"), None);
+        assert_eq!(parse_time(""), None);
+    }
+
+    #[test]
+    fn check_parse_range() {
+        assert_eq!(parse_range("9-17"), Some((9 * 3600, 17 * 3600)));
+        assert_eq!(parse_range("9:30am - 5pm"), Some((9 * 3600 + 30 * 60, 17 * 3600)));
+        assert_eq!(parse_range("09:00:00-17:00:00"), Some((9 * 3600, 17 * 3600)));
+        assert_eq!(parse_range("9"), None);
+        assert_eq!(parse_range("9-17-18"), None);
+    }
+
+    #[test]
+    fn check_format_duration() {
+        assert_eq!(format_duration(9 * 3600, 17 * 3600 + 30 * 60), "8h 30m");
+        assert_eq!(format_duration(0, 45), "0h 00m 45s");
+        // past midnight
+        assert_eq!(format_duration(22 * 3600, 2 * 3600), "4h 00m");
+    }
+
+    #[test]
+    fn check_picker() {
+        let mut picker = TimeRangePicker::default();
+        assert_eq!(picker.text(), "09:00:00-17:00:00");
+
+        // the hours go round without touching the minutes
+        picker.change(-10);
+        assert_eq!(picker.text(), "23:00:00-17:00:00");
+        picker.next_part();
+        picker.change(75);
+        assert_eq!(picker.text(), "23:15:00-17:00:00");
+
+        picker.other_time();
+        picker.change(1);
+        assert_eq!(picker.text(), "23:15:00-18:00:00");
+        picker.previous_part();
+        picker.change(-1);
+        assert_eq!(picker.text(), "23:15:59-18:00:00");
+
+        for c in "9:30pm-11xpm".chars() {
+            picker.type_char(c);
+        }
+        assert_eq!(picker.text(), "21:30:00-23:00:00");
+        picker.delete_char();
+        assert_eq!(picker.text(), "9:30pm-11p");
+        // shorthand that cannot be read is dropped by the arrow keys
+        picker.change(0);
+        assert_eq!(picker.text(), "23:15:59-18:00:00");
+
+        // and shorthand that can be read is adjusted by them
+        for c in "9-17".chars() {
+            picker.type_char(c);
+        }
+        picker.change(1);
+        assert_eq!(picker.text(), "09:00:01-17:00:00");
+        for c in "9-17".chars() {
+            picker.type_char(c);
+        }
+        picker.other_time();
+        picker.change(1);
+        assert_eq!(picker.text(), "09:00:00-18:00:00");
+    }
+}
@@ -1,9 +1,9 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
-    layout::{Constraint, Layout, Rect, Position},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
+    layout::{Constraint, Layout, Rect},
     style::{
-        Color, Stylize, Style, Modifier
+        Stylize, Style, Modifier
     },
     symbols,
     text::{Line},
@@ -20,29 +20,50 @@ use std::{
         Path
     },
     error::Error,
+    time::Duration
 };
 use crate::Timings;
 use crate::Weekday as CommonWeekday;
 use crate::Schedule as CommonSchedule;
 use crate::Timings as CommonTimings;
 use crate::areas;
+use crate::mouse::{
+    Click,
+    Footer,
+    Targets,
+    explorer_rows,
+    explorer_state,
+    is_click
+};
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::mount::Drive;
+use crate::hotplug::{
+    DriveMonitor,
+    Toast,
+    browsed_drive,
+    update_explorer
+};
+use crate::drives::DrivesWidget;
+use crate::wizard::{
+    Nav,
+    Step
+};
 use regex::Regex;
 use strum::Display;
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    ALT_ROW_BG_COLOR,
-    SELECTED_STYLE,
-    TEXT_FG_COLOR,
-    TEXT_DIR_COLOR,
-    FOOTER_STYLE
-};
+use crate::styles::theme;
+use crate::tr;
+use crate::i18n::weekday_name;
 
 use crate::schedule::{
     export,
     import,
 };
+use crate::schedule::picker::TimeRangePicker;
 
 type FileSelect = PathBuf;
 use ratatui_explorer::{FileExplorer, Theme};
@@ -128,7 +149,7 @@ impl Timing {
 
 impl From<&Timing> for ListItem<'_> {
     fn from(value: &Timing) -> Self {
-        let line = Line::styled(value.clone().format().to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(value.clone().format().to_string(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -206,13 +227,13 @@ enum TimingOp {
 impl TimingOp {
     fn as_vec_of_str(&self) -> Vec<TimingOpItem> {
         vec![
-            TimingOpItem::from("Add"), 
-            TimingOpItem::from("Delete"), 
-            TimingOpItem::from("Edit"),
-            TimingOpItem::from("Copy"),
-            TimingOpItem::from("Import"),
-            TimingOpItem::from("Export"),
-            TimingOpItem::from("Exit")
+            TimingOpItem::from(tr!("schedule.op_add")),
+            TimingOpItem::from(tr!("schedule.op_delete")),
+            TimingOpItem::from(tr!("schedule.op_edit")),
+            TimingOpItem::from(tr!("schedule.op_copy")),
+            TimingOpItem::from(tr!("schedule.op_import")),
+            TimingOpItem::from(tr!("schedule.op_export")),
+            TimingOpItem::from(tr!("schedule.op_exit"))
         ]
     }
 }
@@ -249,7 +270,7 @@ impl TimingOpItem {
 
 impl From<&TimingOpItem> for ListItem<'_> {
     fn from(value: &TimingOpItem) -> Self {
-        let line = Line::styled(value.op_item.clone(), TEXT_FG_COLOR);
+        let line = Line::styled(value.op_item.clone(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -265,9 +286,9 @@ enum DuplicateOpItem {
 impl DuplicateOpItem {
     fn as_desc(&self) -> &'static str {
         match self {
-            DuplicateOpItem::Day => "Copy this day's schedule to another day",
-            DuplicateOpItem::Weekdays => "Copy this day's schedule to all weekdays",
-            DuplicateOpItem::All => "Copy this day's schedule to all days"
+            DuplicateOpItem::Day => tr!("schedule.copy_day"),
+            DuplicateOpItem::Weekdays => tr!("schedule.copy_weekdays"),
+            DuplicateOpItem::All => tr!("schedule.copy_all")
         }
     }
 }
@@ -293,7 +314,7 @@ impl DuplicateOpList {
 }
 impl From<&DuplicateOpItem> for ListItem<'_> {
     fn from(value: &DuplicateOpItem) -> Self {
-        let line = Line::styled(value.as_desc().to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(value.as_desc().to_string(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -312,15 +333,7 @@ enum DuplicateDayOpItem {
 
 impl DuplicateDayOpItem {
     fn as_desc(&self) -> &'static str {
-        match self {
-            DuplicateDayOpItem::Monday => "Monday",
-            DuplicateDayOpItem::Tuesday => "Tuesday",
-            DuplicateDayOpItem::Wednesday => "Wednesday",
-            DuplicateDayOpItem::Thursday => "Thursday",
-            DuplicateDayOpItem::Friday => "Friday",
-            DuplicateDayOpItem::Saturday => "Saturday",
-            DuplicateDayOpItem::Sunday => "Sunday",
-        }
+        weekday_name(self.as_int())
     }
     fn as_int(&self) -> usize {
         match self {
@@ -361,7 +374,7 @@ impl DuplicateDayOpList {
 
 impl From<&DuplicateDayOpItem> for ListItem<'_> {
     fn from(value: &DuplicateDayOpItem) -> Self {
-        let line = Line::styled(value.as_desc().to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(value.as_desc().to_string(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -386,7 +399,7 @@ impl DelOpList {
         let mut state = ListState::default();
         state.select_first();
         DelOpList {
-            del_list: vec![DelOpItem::new("Yes"), DelOpItem::new("No")],
+            del_list: vec![DelOpItem::new(tr!("common.yes")), DelOpItem::new(tr!("common.no"))],
             state
         }
     }
@@ -394,7 +407,7 @@ impl DelOpList {
 
 impl From<&DelOpItem> for ListItem<'_> {
     fn from(value: &DelOpItem) -> Self {
-        let line = Line::styled(value.item.to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(value.item.to_string(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -417,7 +430,7 @@ impl ExitList {
         let mut state = ListState::default();
         state.select_first();
         ExitList {
-            exit_list_items: vec![ExitItem::new("Yes"), ExitItem::new("No")],
+            exit_list_items: vec![ExitItem::new(tr!("common.yes")), ExitItem::new(tr!("common.no"))],
             state
         }
     }
@@ -425,7 +438,7 @@ impl ExitList {
 
 impl From<&ExitItem> for ListItem<'_> {
     fn from(value: &ExitItem) -> Self {
-        let line = Line::styled(value.item.to_string(), TEXT_FG_COLOR);
+        let line = Line::styled(value.item.to_string(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -441,6 +454,7 @@ pub struct TimingsWidget {
     file_explorer: FileExplorer,
     selected_file: FileSelect,
     should_exit: bool,
+    nav: Nav,
     current_screen: CurrentScreen,
     previous_screen: CurrentScreen,
     // selected weekdays and timings are indexes starting at 0
@@ -448,8 +462,9 @@ pub struct TimingsWidget {
     timing_selected: usize,
     operation_selected: TimingOp,
     timing_op_list: TimingOpList,
+    // the timing being checked before it is saved, as 09:00:00-17:00:00
     input: String,
-    character_index: usize,
+    picker: TimeRangePicker,
     input_area: Rect,
     del_op_list: DelOpList,
     duplicate_op_list: DuplicateOpList,
@@ -458,7 +473,12 @@ pub struct TimingsWidget {
     error_type: ErrorType,
     list_element_entries: TimingsList,
     schedule: CommonTimings,
-    mounted_drives: Vec<(PathBuf, String)>
+    mounted_drives: Vec<Drive>,
+    monitor: Option<DriveMonitor>,
+    toast: Option<Toast>,
+    drives_requested: bool,
+    targets: Targets,
+    help: bool
 }
 impl Default for TimingsWidget {
     fn default() -> Self {
@@ -467,6 +487,7 @@ impl Default for TimingsWidget {
             file_explorer: FileExplorer::new().unwrap(),
             selected_file: PathBuf::new(),
             should_exit: false,
+            nav: Nav::Next,
             current_screen: CurrentScreen::Weekdays,
             previous_screen: CurrentScreen::Weekdays,
             weekday_selected: 0,
@@ -474,7 +495,7 @@ impl Default for TimingsWidget {
             operation_selected: TimingOp::Add,
             timing_op_list: TimingOpList::default(),
             input: String::new(),
-            character_index: 0,
+            picker: TimeRangePicker::default(),
             input_area: Rect::new(0,0,0,0),
             del_op_list: DelOpList::default(),
             duplicate_op_list: DuplicateOpList::default(),
@@ -491,7 +512,12 @@ impl Default for TimingsWidget {
                 (Weekday::Sunday(TimingCollection::default())),
             ]),
             schedule: Vec::with_capacity(7),
-            mounted_drives: Vec::new()
+            mounted_drives: Vec::new(),
+            monitor: None,
+            toast: None,
+            drives_requested: false,
+            targets: Targets::default(),
+            help: false
         }
     }
 }
@@ -528,13 +554,6 @@ pub struct TimingsEntry {
     timings: TimingCollection,
 }
 
-impl From<&TimingsEntry> for ListItem<'_> {
-    fn from(value: &TimingsEntry) -> Self {
-        let line = Line::styled(value.list_element.to_string(), TEXT_FG_COLOR);
-        ListItem::new(line)
-    }
-}
-
 impl TimingsEntry {
     fn new(weekday: Weekday) -> Self {
         Self {
@@ -544,6 +563,61 @@ impl TimingsEntry {
     }
 }
 
+// this needs to extract the whole string and parse to two u32s that are the full times 
+// combined into one u32
+fn extract_timings(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
+    let two_timings = input.split("-").collect::<Vec<&str>>();
+
+    let t_start = two_timings[0].split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
+    let t_end = two_timings[1].split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
+
+    Ok((t_start, t_end))
+}
+
+/// Whether `input` is a timing such as 09:00:00-17:00:00 on the 24 hour clock.
+pub fn timing_format_correct(input: &str) -> Result<bool, Box<dyn Error>> {
+    let re = Regex::new(r"^(?<h>[0-2][0-9]):[0-5][0-9]:[0-5][0-9]-(?<h2>[0-2][0-9]):[0-5][0-9]:[0-5][0-9]$")?;
+    if re.is_match(input) { 
+        if let Some(output) = re.captures(input) {
+            let (_, [start, end]) = output.extract();
+            let hour_1 = start.parse::<u32>()?;
+            let hour_2 = end.parse::<u32>()?;
+            
+            // This checks if the hour is less than 24
+            // The minutes and seconds are already checked by the regex
+            if hour_1 < 24 && hour_2 < 24 {
+                return Ok(true);
+            } 
+        }
+    }
+    Ok(false)
+}
+
+/// Whether the timing in `input` overlaps none of the `existing` timings for the same day.
+pub fn timing_no_clash<'a>(input: &str, existing: impl IntoIterator<Item = &'a (String, String)>) -> Result<bool, Box<dyn Error>> {
+    // iterate through each timing for the current day
+    // if input start is after any other start but before the end it will clash
+    let (start, end) = extract_timings(input)?;
+    for timing in existing {
+        let t_start = timing.0.split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
+        
+        let t_end = timing.1.split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
+
+        // verbose for clarity
+        #[allow(clippy::if_same_then_else)]
+        if start <= t_start && end >= t_start {
+            return Ok(false);
+        } else if start <= t_start && end >= t_end {
+            return Ok(false);
+        } else if start <= t_end && end >= t_end {
+            return Ok(false);
+        } else if start >= t_start &&  end <= t_end {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 fn parse_common_timings(c_timings: CommonTimings) -> Result<TimingsList, Box<dyn Error>> {
 
     let days_with_timings_collection: Vec<_> = c_timings.iter()
@@ -580,7 +654,7 @@ fn parse_common_timings(c_timings: CommonTimings) -> Result<TimingsList, Box<dyn
 }
 
 impl TimingsWidget {
-    pub fn new (preset_timings: CommonTimings, mounted_drives: Vec<(PathBuf, String)>) -> Result<Self, Box<dyn Error>> {
+    pub fn new (preset_timings: CommonTimings, mounted_drives: Vec<Drive>) -> Result<Self, Box<dyn Error>> {
 
         // convert the common-timings to timings
         let parsed_timings: TimingsList = parse_common_timings(preset_timings)?;
@@ -592,6 +666,7 @@ impl TimingsWidget {
                 file_explorer,
                 selected_file: Path::new("./").to_path_buf(),
                 should_exit: false,
+                nav: Nav::Next,
                 current_screen: CurrentScreen::Weekdays,
                 previous_screen: CurrentScreen::Weekdays,
                 weekday_selected: 0,
@@ -599,7 +674,7 @@ impl TimingsWidget {
                 operation_selected: TimingOp::Add,
                 timing_op_list: TimingOpList::default(),
                 input: String::new(),
-                character_index: 0,
+                picker: TimeRangePicker::default(),
                 input_area: Rect::new(0,0,0,0),
                 del_op_list: DelOpList::default(),
                 duplicate_op_list: DuplicateOpList::default(),
@@ -609,10 +684,15 @@ impl TimingsWidget {
                 list_element_entries: parsed_timings,
                 schedule: Vec::with_capacity(7),
                 mounted_drives,
+                monitor: Some(DriveMonitor::start()),
+                toast: None,
+                drives_requested: false,
+                targets: Targets::default(),
+                help: false
             }
         )
     }
-    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Timings, Box< dyn Error>> {
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<Timings>, Box< dyn Error>> {
 
         let _file_explorer_init = self.setup_file_explorer()?;
         let _file_explorer_style_init = self.style_file_explorer();
@@ -622,48 +702,85 @@ impl TimingsWidget {
                 f.render_widget(&mut self, f.area());
                 match self.current_screen {
                     CurrentScreen::Add | CurrentScreen::Edit => {
-                        f.set_cursor_position(Position::new(
-                                self.input_area.x + self.character_index as u16 + 1,
-                                // move one line down, from the border to the input lin
-                                self.input_area.y + 1,
-                        ))
+                        if let Some(position) = self.picker.cursor(self.input_area) {
+                            f.set_cursor_position(position);
+                        }
                     },
                     _ => {}
                 }
             })?;
 
-            let event = event::read()?;
-            if self.current_screen == CurrentScreen::Import {
-                let _ = self.file_explorer.handle(&event);
+            self.update_drives()?;
+            // wake up now and then to notice drives being plugged in
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
             }
+            let event = match event::read()? {
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                event => Some(event)
+            };
+            if let Some(event) = event {
+                if self.current_screen == CurrentScreen::Import && !self.help {
+                    let _ = self.file_explorer.handle(&event);
+                }
 
-            if let Event::Key(key) = event {
-                let _handle_result = self.handle_key(key)?;
-                //self.text_area.input(key);
+                if let Event::Key(key) = event {
+                    let _handle_result = self.handle_key(key)?;
+                    //self.text_area.input(key);
+                }
+            }
+            if self.drives_requested {
+                self.drives_requested = false;
+                self.show_drives(terminal)?;
             }
         }
-        Ok(self.schedule)
+        Ok(self.nav.with(self.schedule))
     }
     fn style_file_explorer(&mut self) {
         let theme = Theme::default()
             .add_default_title()
             .with_block(Block::default()
-                .title(Line::raw("Select File").centered())
+                .title(Line::raw(tr!("fileselect.explorer_title")).centered())
                 .borders(Borders::TOP)
                 .border_set(symbols::border::EMPTY)
-                .border_style(ITEM_HEADER_STYLE)
-                .bg(NORMAL_ROW_BG)
+                .border_style(theme().item_header)
+                .bg(theme().normal_row_bg)
                 .padding(Padding::horizontal(1))
                 )
-            .with_highlight_item_style(SELECTED_STYLE)
+            .with_highlight_item_style(theme().selected)
             .with_highlight_symbol("> ")
             .with_highlight_spacing(HighlightSpacing::Always)
-            .with_dir_style(Style::default().fg(TEXT_DIR_COLOR).add_modifier(Modifier::BOLD))
-            .with_highlight_dir_style(SELECTED_STYLE)
-            .with_item_style(Style::default().fg(TEXT_FG_COLOR));
+            .with_dir_style(Style::default().fg(theme().text_dir).add_modifier(Modifier::BOLD))
+            .with_highlight_dir_style(theme().selected)
+            .with_item_style(Style::default().fg(theme().text_fg));
         
         self.file_explorer.set_theme(theme)
     }
+    // pick up drives plugged in or pulled out since the last check
+    fn update_drives(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.monitor.as_ref().and_then(DriveMonitor::poll) {
+            Some(drives) => self.apply_drives(drives),
+            None => Ok(())
+        }
+    }
+
+    // open the drives screen on the drive being browsed
+    fn show_drives(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        let browsed = browsed_drive(&self.file_explorer, &self.mounted_drives);
+        let drives = DrivesWidget::new(self.mounted_drives.clone(), browsed).run(terminal)?;
+        self.apply_drives(drives)
+    }
+
+    fn apply_drives(&mut self, drives: Vec<Drive>) -> Result<(), Box<dyn Error>> {
+        let update = update_explorer(&mut self.file_explorer, &mut self.mounted_drives, drives)?;
+        if update.folder_gone {
+            self.setup_file_explorer()?;
+        }
+        if update.toast.is_some() {
+            self.toast = update.toast;
+        }
+        Ok(())
+    }
     fn setup_file_explorer(&mut self) -> Result<(), Box<dyn Error>> {
         let username = whoami::username();
 
@@ -683,8 +800,8 @@ impl TimingsWidget {
         } else if self.mounted_drives.len() > 1 && !username.is_empty() {
             let path_buf: PathBuf = ["/media/", &username].iter().collect();
             self.file_explorer.set_cwd(&path_buf)?;
-        } else if self.mounted_drives.len() == 1 {
-            self.file_explorer.set_cwd(self.mounted_drives[0].0.clone())?;
+        } else if let [drive] = self.mounted_drives.as_slice() && let Some(mountpoint) = &drive.mountpoint {
+            self.file_explorer.set_cwd(mountpoint)?;
         } else if !username.is_empty() {
             let username = whoami::username();
             let path_buf: PathBuf = ["/home/", &username].iter().collect();
@@ -723,21 +840,94 @@ impl TimingsWidget {
         }
     }
 
+    // the keys of the current screen
+    fn keymap(&self) -> Keymap {
+        let back = &[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace, KeyCode::Esc];
+        let choose = &[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter];
+        match self.current_screen {
+            CurrentScreen::Weekdays => Keymap::list()
+                .bind(choose, Action::Choose)
+                .bind(&[KeyCode::Left, KeyCode::Backspace], Action::Finish)
+                .bind(&[KeyCode::Char('m')], Action::Menu)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::Char('q')], Action::Quit),
+            CurrentScreen::Day => Keymap::list()
+                .bind(choose, Action::Choose)
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace], Action::Back)
+                .bind(&[KeyCode::Char('m')], Action::Menu)
+                .bind(&[KeyCode::Char('q'), KeyCode::Esc], Action::Finish),
+            CurrentScreen::TimingOptions => Keymap::list()
+                .bind(choose, Action::Choose)
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace, KeyCode::Esc, KeyCode::Char('q')], Action::Back),
+            // other keys type a timing in shorthand, such as 9-17
+            CurrentScreen::Add | CurrentScreen::Edit => Keymap::default()
+                .bind(&[KeyCode::Up], Action::Increase)
+                .bind(&[KeyCode::Down], Action::Decrease)
+                .bind(&[KeyCode::Left], Action::PreviousPart)
+                .bind(&[KeyCode::Right], Action::NextPart)
+                .bind(&[KeyCode::Tab, KeyCode::BackTab], Action::OtherTime)
+                .bind(&[KeyCode::Backspace], Action::DeleteChar)
+                .bind(&[KeyCode::Enter], Action::Save)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(HELP_KEYS, Action::Help),
+            CurrentScreen::Delete | CurrentScreen::Duplicate | CurrentScreen::DuplicateDay | CurrentScreen::Exit => Keymap::list()
+                .bind(choose, Action::Choose)
+                .bind(back, Action::Back)
+                .bind(&[KeyCode::Char('m')], Action::Menu),
+            CurrentScreen::Import => Keymap::list()
+                .bind(&[KeyCode::Char('l'), KeyCode::Right], Action::Open)
+                .bind(&[KeyCode::Enter], Action::OpenOrChoose)
+                .bind(&[KeyCode::Char('e')], Action::Drives)
+                .bind(back, Action::Back),
+            // any other key closes these
+            CurrentScreen::Error | CurrentScreen::Message | CurrentScreen::Export => Keymap::default()
+                .bind(HELP_KEYS, Action::Help)
+        }
+    }
+
+    // moves the highlight of the list on the current screen, or returns false for other actions
+    fn move_highlight(&mut self, action: Action) -> bool {
+        match action {
+            Action::Next => self.select_next(),
+            Action::Previous => self.select_previous(),
+            Action::First => self.select_first(),
+            Action::Last => self.select_last(),
+            _ => return false
+        }
+        true
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
         if key.kind != KeyEventKind::Press {
             return Ok(());
         }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return Ok(());
+        }
+        let action = self.keymap().action(key.code);
+        match action {
+            Some(Action::Help) => {
+                self.help = true;
+                return Ok(());
+            },
+            Some(action) if self.move_highlight(action) => return Ok(()),
+            _ => {}
+        }
         match self.current_screen {
             CurrentScreen::Weekdays => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Char('q') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.current_screen = CurrentScreen::Exit,
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Finish) => self.current_screen = CurrentScreen::Exit,
+                    Some(Action::Back) => {
+                        // keep the edits made so far for when this screen is shown again
+                        self.compile_schedule();
+                        self.leave(Nav::Back);
+                    },
+                    Some(Action::Quit) => self.leave(Nav::Cancel),
                     //KeyCode::Char('h') | KeyCode::Left => self.select_none(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         // check the current selection
@@ -753,15 +943,11 @@ impl TimingsWidget {
                 }
             },
             CurrentScreen::Day => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Char('q') | KeyCode::Esc => self.current_screen = CurrentScreen::Exit,
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.reverse_state(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Finish) => self.current_screen = CurrentScreen::Exit,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.list_element_entries.list[self.weekday_selected].timings.state.selected() {
@@ -775,15 +961,9 @@ impl TimingsWidget {
                 }
             },
             CurrentScreen::TimingOptions => {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.reverse_state(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.timing_op_list.state.selected() {
@@ -803,13 +983,15 @@ impl TimingsWidget {
                             self.operation_selected = op.clone();
 
                             match op {
-                                TimingOp::Add => self.current_screen = CurrentScreen::Add,
+                                TimingOp::Add => {
+                                    self.picker = TimeRangePicker::default();
+                                    self.current_screen = CurrentScreen::Add;
+                                },
                                 TimingOp::Del => self.current_screen = CurrentScreen::Delete,
                                 TimingOp::Edit => {
                                     if !self.list_element_entries.list[self.weekday_selected].timings.timing_collection.is_empty() {
-                                        self.input.clear();
-                                        self.input.push_str(&self.list_element_entries.list[self.weekday_selected].timings.timing_collection[self.timing_selected].clone().format()); 
-                                        self.character_index = self.input.chars().count();
+                                        let (start, end) = &self.list_element_entries.list[self.weekday_selected].timings.timing_collection[self.timing_selected].timing;
+                                        self.picker = TimeRangePicker::from_timing(start, end);
                                         self.current_screen = CurrentScreen::Edit
                                     } else {
                                         self.reverse_state();
@@ -833,15 +1015,17 @@ impl TimingsWidget {
                 }
             },
             CurrentScreen::Add => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Left => self.move_cursor_left(),
-                    KeyCode::Right => self.move_cursor_right(),
-                    KeyCode::Backspace => self.delete_char(),
-                    KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                    KeyCode::Enter => {
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Increase) => self.picker.change(1),
+                    Some(Action::Decrease) => self.picker.change(-1),
+                    Some(Action::NextPart) => self.picker.next_part(),
+                    Some(Action::PreviousPart) => self.picker.previous_part(),
+                    Some(Action::OtherTime) => self.picker.other_time(),
+                    Some(Action::DeleteChar) => self.picker.delete_char(),
+                    Some(Action::Save) => {
                         self.previous_screen = CurrentScreen::Add;
+                        self.input = self.picker.text();
                         let timing_format_correct = self.timing_format_correct()?;
                         let timing_format_no_clash = self.timing_format_no_clash()?;
 
@@ -857,22 +1041,27 @@ impl TimingsWidget {
                             self.list_element_entries.list[self.weekday_selected].timings.timing_collection.push(t);
                             self.reverse_state();
                             self.input.clear();
-                            self.character_index = 0;
                         }
                     }
-                    _ => {}
+                    _ => {
+                        if let KeyCode::Char(to_insert) = key.code {
+                            self.picker.type_char(to_insert);
+                        }
+                    }
                 }
             },
             CurrentScreen::Edit => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Left => self.move_cursor_left(),
-                    KeyCode::Right => self.move_cursor_right(),
-                    KeyCode::Backspace => self.delete_char(),
-                    KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                    KeyCode::Enter => {
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Increase) => self.picker.change(1),
+                    Some(Action::Decrease) => self.picker.change(-1),
+                    Some(Action::NextPart) => self.picker.next_part(),
+                    Some(Action::PreviousPart) => self.picker.previous_part(),
+                    Some(Action::OtherTime) => self.picker.other_time(),
+                    Some(Action::DeleteChar) => self.picker.delete_char(),
+                    Some(Action::Save) => {
                         self.previous_screen = CurrentScreen::Edit;
+                        self.input = self.picker.text();
                         let timing_format_correct = self.timing_format_correct()?;
                         let timing_format_no_clash = self.timing_format_no_clash()?;
                         // check, then parse the timing
@@ -887,26 +1076,25 @@ impl TimingsWidget {
                             let _removed = std::mem::replace(&mut self.list_element_entries.list[self.weekday_selected].timings.timing_collection[self.timing_selected], t);
                             self.reverse_state();
                             self.input.clear();
-                            self.character_index = 0;
                         }
                     }
-                    _ => {}
+                    _ => {
+                        if let KeyCode::Char(to_insert) = key.code {
+                            self.picker.type_char(to_insert);
+                        }
+                    }
                 }
             },
 
             CurrentScreen::Delete => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.del_op_list.state.selected() &&
-                            self.del_op_list.del_list[i].item.as_str() == "Yes" && 
+                            self.del_op_list.del_list[i].item.as_str() == tr!("common.yes") && 
                                 !self.list_element_entries.list[self.weekday_selected].timings.timing_collection.is_empty() {
                                         self.list_element_entries.list[self.weekday_selected].timings.timing_collection.remove(self.timing_selected);
                         }
@@ -916,14 +1104,10 @@ impl TimingsWidget {
                 }
             },
             CurrentScreen::Duplicate => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         if let Some(i) = self.duplicate_op_list.state.selected() {
                             // match the duplicate submenu emnum options
                             let op = match i {
@@ -942,12 +1126,12 @@ impl TimingsWidget {
                                 DuplicateOpItem::Day => self.current_screen = CurrentScreen::DuplicateDay,
                                 DuplicateOpItem::Weekdays => {
                                     self.duplicate_schedule_to_weekdays();
-                                    self.message_text = String::from("Schedule has been copied to all weekdays.");
+                                    self.message_text = String::from(tr!("schedule.copied_weekdays"));
                                     self.current_screen = CurrentScreen::Message;
                                 },
                                 DuplicateOpItem::All => {
                                     self.duplicate_schedule_to_all_days();
-                                    self.message_text = String::from("Schedule has been copied to all days.");
+                                    self.message_text = String::from(tr!("schedule.copied_all"));
                                     self.current_screen = CurrentScreen::Message;
                                 }
                             }
@@ -958,14 +1142,10 @@ impl TimingsWidget {
                 }
             },
             CurrentScreen::DuplicateDay => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         if let Some(i) = self.duplicate_day_op_list.state.selected() {
                             let op = match i {
                                 0 => DuplicateDayOpItem::Monday,
@@ -989,7 +1169,7 @@ impl TimingsWidget {
                             }
                 
                         }
-                        self.message_text = String::from("Day schedule has been copied.");
+                        self.message_text = String::from(tr!("schedule.copied_day"));
                         self.current_screen = CurrentScreen::Message;
                     },
                     _ => {}
@@ -1020,14 +1200,15 @@ impl TimingsWidget {
             CurrentScreen::Export => self.reverse_state(),
             CurrentScreen::Import => {
                 let is_dir = self.file_explorer.current().is_dir();
-                match key.code {
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Enter if !is_dir => {
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Drives) => self.drives_requested = true,
+                    Some(Action::OpenOrChoose) if !is_dir => {
                         let current_path_buf = self.file_explorer.current().path().to_path_buf();
                         self.selected_file = current_path_buf;
                         let schedule = import::import_schedule(self.selected_file.clone());
                         self.list_element_entries = parse_common_timings(schedule?)?;
-                        self.message_text = String::from("Schedule import successful");
+                        self.message_text = String::from(tr!("schedule.imported"));
                         self.current_screen = CurrentScreen::Message;
                         },
                     _ => {}
@@ -1035,21 +1216,15 @@ impl TimingsWidget {
 
             },
             CurrentScreen::Exit => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::TimingOptions,
-                    KeyCode::Esc | KeyCode::Backspace => self.reverse_state(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-
-                    KeyCode::Char('h') | KeyCode::Left => self.reverse_state(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::TimingOptions,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.exit_list.state.selected() {
                             match self.exit_list.exit_list_items[i].item.as_str() {
-                                "Yes" => {
+                                item if item == tr!("common.yes") => {
                                     // compile the schedule here
                                     self.compile_schedule();                         
                                     self.should_exit = true;
@@ -1065,59 +1240,71 @@ impl TimingsWidget {
         }
         Ok(())
     }
-    // this needs to extract the whole string and parse to two u32s that are the full times 
-    // combined into one u32
-    fn extract_timings_from_input(&self, input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-        let two_timings = input.split("-").collect::<Vec<&str>>();
-
-        let t_start = two_timings[0].split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
-        let t_end = two_timings[1].split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
+    // turns a click into the key it stands for, or highlights the row that was clicked
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Event> {
+        if self.help {
+            self.help = !is_click(&mouse);
+            return None;
+        }
+        let targets = self.targets;
+        let footer = self.footer();
+        match self.current_screen {
+            // any key closes these, so any click does too
+            CurrentScreen::Message | CurrentScreen::Error | CurrentScreen::Export => {
+                is_click(&mouse).then(|| Event::Key(KeyEvent::from(KeyCode::Enter)))
+            },
+            // the footer keys are typed into the timing here
+            CurrentScreen::Add | CurrentScreen::Edit => None,
+            CurrentScreen::Import => {
+                let state = explorer_state(&self.file_explorer, targets.list.height);
+                match targets.click(mouse, &state, self.file_explorer.files().len(), footer.as_ref())? {
+                    Click::Key(code) => Some(Event::Key(KeyEvent::from(code))),
+                    Click::Select(i) => {
+                        self.file_explorer.set_selected_idx(i);
+                        None
+                    }
+                }
+            },
+            _ => {
+                let (state, rows) = self.current_list()?;
+                match targets.click(mouse, state, rows, footer.as_ref())? {
+                    Click::Key(code) => Some(Event::Key(KeyEvent::from(code))),
+                    Click::Select(i) => {
+                        state.select(Some(i));
+                        None
+                    }
+                }
+            }
+        }
+    }
 
-        Ok((t_start, t_end))
+    // the list shown on the current screen and how many rows it has
+    fn current_list(&mut self) -> Option<(&mut ListState, usize)> {
+        match self.current_screen {
+            CurrentScreen::Weekdays => Some((&mut self.list_element_entries.state, self.list_element_entries.list.len())),
+            CurrentScreen::Day => {
+                let timings = &mut self.list_element_entries.list[self.weekday_selected].timings;
+                Some((&mut timings.state, timings.timing_collection.len()))
+            },
+            CurrentScreen::TimingOptions => Some((&mut self.timing_op_list.state, self.timing_op_list.timing_ops.len())),
+            CurrentScreen::Delete => Some((&mut self.del_op_list.state, self.del_op_list.del_list.len())),
+            CurrentScreen::Duplicate => Some((&mut self.duplicate_op_list.state, self.duplicate_op_list.duplicate_list.len())),
+            CurrentScreen::DuplicateDay => Some((&mut self.duplicate_day_op_list.state, self.duplicate_day_op_list.duplicate_day_list.len())),
+            CurrentScreen::Exit => Some((&mut self.exit_list.state, self.exit_list.exit_list_items.len())),
+            _ => None
+        }
     }
 
     fn timing_format_correct(&self) -> Result<bool, Box<dyn Error>> {
-        let re = Regex::new(r"^(?<h>[0-2][0-9]):[0-5][0-9]:[0-5][0-9]-(?<h2>[0-2][0-9]):[0-5][0-9]:[0-5][0-9]$")?;
-        if re.is_match(&self.input) { 
-            if let Some(output) = re.captures(&self.input) {
-                let (_, [start, end]) = output.extract();
-                let hour_1 = start.parse::<u32>()?;
-                let hour_2 = end.parse::<u32>()?;
-                
-                // This checks if the hour is less than 24
-                // The minutes and seconds are already checked by the regex
-                if hour_1 < 24 && hour_2 < 24 {
-                    return Ok(true);
-                } 
-            }
-        }
-        Ok(false)
+        timing_format_correct(&self.input)
     }
 
     fn timing_format_no_clash(&self) -> Result<bool, Box<dyn Error>> {
-        // iterate through each timing for the current day
-        // if input start is after any other start but before the end it will clash
-        // Must not include current timing being edited
-        let (start, end) = self.extract_timings_from_input(&self.input)?;
-        for (i, t) in self.list_element_entries.list[self.weekday_selected].timings.timing_collection.iter().enumerate() {
-            if self.current_screen == CurrentScreen::Edit && i == self.timing_selected { return Ok(true) }
-            let t_start = t.timing.0.split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
-            
-            let t_end = t.timing.1.split(":").collect::<Vec<&str>>().join("").parse::<u32>()?;
-    
-            // verbose for clarity
-            #[allow(clippy::if_same_then_else)]
-            if start <= t_start && end >= t_start {
-                return Ok(false);
-            } else if start <= t_start && end >= t_end {
-                return Ok(false);
-            } else if start <= t_end && end >= t_end {
-                return Ok(false);
-            } else if start >= t_start &&  end <= t_end {
-                return Ok(false);
-            }
-        }
-        Ok(true)
+        // Must not include current timing being edited, or the ones after it
+        let existing = self.list_element_entries.list[self.weekday_selected].timings.timing_collection.iter()
+            .take(if self.current_screen == CurrentScreen::Edit { self.timing_selected } else { usize::MAX })
+            .map(|t| &t.timing);
+        timing_no_clash(&self.input, existing)
     }
 
     fn parse_timing_from_input(&self) -> Timing {
@@ -1128,6 +1315,11 @@ impl TimingsWidget {
         Timing::new(&new_t[0], &new_t[1])
     }
     
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
     fn reverse_state(&mut self) {
         match self.current_screen {
             CurrentScreen::Weekdays => self.current_screen = CurrentScreen::Weekdays,
@@ -1181,6 +1373,8 @@ impl TimingsWidget {
             CurrentScreen::DuplicateDay => self.duplicate_day_op_list.state.select_first(),
             CurrentScreen::Delete => self.del_op_list.state.select_first(),
             CurrentScreen::Exit => self.exit_list.state.select_first(),
+            // the file explorer moves its own highlight up and down, but has no keys for the ends
+            CurrentScreen::Import => self.file_explorer.set_selected_idx(0),
             _ => {}
 
         }
@@ -1195,6 +1389,10 @@ impl TimingsWidget {
             CurrentScreen::DuplicateDay => self.duplicate_day_op_list.state.select_last(),
             CurrentScreen::Delete => self.del_op_list.state.select_last(),
             CurrentScreen::Exit => self.exit_list.state.select_last(),
+            CurrentScreen::Import => {
+                let last = self.file_explorer.files().len().saturating_sub(1);
+                self.file_explorer.set_selected_idx(last);
+            },
             _ => {}
         }
     }
@@ -1221,73 +1419,66 @@ impl TimingsWidget {
         }
     }
 
-    fn move_cursor_left(&mut self) {
-        let cursor_moved_left = self.character_index.saturating_sub(1);
-        self.character_index = self.clamp_cursor(cursor_moved_left);
-    }
-
-    fn move_cursor_right(&mut self) {
-        let cursor_moved_right = self.character_index.saturating_add(1);
-        self.character_index = self.clamp_cursor(cursor_moved_right);
-    }
-
-    fn enter_char(&mut self, new_char: char) {
-        let index = self.byte_index();
-        self.input.insert(index, new_char);
-        self.move_cursor_right();
-    }
-
-    fn byte_index(&self) -> usize {
-        self.input
-            .char_indices()
-            .map(|(i, _)| i)
-            .nth(self.character_index)
-            .unwrap_or(self.input.len())
-    }
-
-    fn delete_char(&mut self) {
-        let is_not_cursor_leftmost = self.character_index != 0;
-        if is_not_cursor_leftmost {
-
-            let current_index = self.character_index;
-            let from_left_to_current_index = current_index -1;
-            // get all characters before the selected character
-            let before_char_to_delete = self.input.chars().take(from_left_to_current_index);
-            // get all characters after selected character
-            let after_char_to_delete = self.input.chars().skip(current_index);
-
-            //put all the chars together except the selected one (which is "deleted")
-            self.input = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_cursor_left();
-        }
-    }
-
-    fn clamp_cursor(&self, new_cursor_pos: usize) -> usize {
-        new_cursor_pos.clamp(0, self.input.chars().count())
-    }
-
     // rendering logic
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Schedule Timings")
+        Paragraph::new(tr!("schedule.header"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
-    fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.")
-            .style(FOOTER_STYLE)
+    // the key hints of the current screen, each checked against its keymap
+    fn footer(&self) -> Option<Footer> {
+        let keymap = self.keymap();
+        let list = || Footer::default()
+            .hint(tr!("footer.move"))
+            .bound(&keymap, tr!("footer.change"), KeyCode::Right, Action::Choose)
+            .hint(tr!("footer.top_bottom"));
+        let help = |footer: Footer| footer.bound(&keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help);
+        let footer = match self.current_screen {
+            CurrentScreen::Weekdays => list()
+                .bound(&keymap, tr!("footer.finish"), KeyCode::Left, Action::Finish)
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+                .bound(&keymap, tr!("footer.quit"), KeyCode::Char('q'), Action::Quit),
+            CurrentScreen::Day => list()
+                .bound(&keymap, tr!("footer.left_back"), KeyCode::Left, Action::Back)
+                .bound(&keymap, tr!("footer.esc_finish"), KeyCode::Esc, Action::Finish),
+            CurrentScreen::TimingOptions | CurrentScreen::Delete | CurrentScreen::Duplicate
+                | CurrentScreen::DuplicateDay | CurrentScreen::Exit => list()
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back),
+            // the picker explains its own keys, and ? is typed
+            CurrentScreen::Add | CurrentScreen::Edit => return Some(Footer::default()
+                .bound(&keymap, tr!("footer.save"), KeyCode::Enter, Action::Save)
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+                .bound(&keymap, tr!("footer.f1_help"), KeyCode::F(1), Action::Help)),
+            CurrentScreen::Import => Footer::default()
+                .hint(tr!("footer.move"))
+                .bound(&keymap, tr!("footer.open"), KeyCode::Right, Action::Open)
+                .hint(tr!("footer.top_bottom"))
+                .bound(&keymap, tr!("footer.drives"), KeyCode::Char('e'), Action::Drives)
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back),
+            // any key closes these
+            CurrentScreen::Error | CurrentScreen::Message | CurrentScreen::Export => return None
+        };
+        Some(help(footer))
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let text = self.footer().map(|footer| footer.line()).unwrap_or_default();
+        Paragraph::new(text)
+            .style(theme().footer)
             .centered()
             .render(area, buf);
     }
 
     fn render_weekdays_list(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw("Select Day").centered())
+            .title(Line::raw(tr!("schedule.select_day")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the elements in the 'items' and stylise them
         let items: Vec<ListItem> = self 
@@ -1295,16 +1486,16 @@ impl TimingsWidget {
             .list
             .iter()
             .enumerate()
-            .map(|(i, list_item)| {
-                let color = alternate_colors(i);
-                ListItem::from(list_item).bg(color)
+            .map(|(i, _)| {
+                let color = theme().row_bg(i);
+                ListItem::new(Line::styled(weekday_name(i), theme().text_fg)).bg(color)
             })
             .collect();
 
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1315,12 +1506,13 @@ impl TimingsWidget {
             
         if !self.list_element_entries.list[self.weekday_selected].timings.timing_collection.is_empty() {
             let block = Block::new()
-                .title(Line::raw("Edit Timings").centered())
+                .title(Line::raw(tr!("schedule.edit_timings")).centered())
                 .borders(Borders::TOP | Borders::LEFT)
                 .border_set(symbols::border::EMPTY)
-                .border_style(ITEM_HEADER_STYLE)
-                .bg(NORMAL_ROW_BG)
-                .fg(TEXT_FG_COLOR);
+                .border_style(theme().item_header)
+                .bg(theme().normal_row_bg)
+                .fg(theme().text_fg);
+            self.targets.list = block.inner(area);
             
             // sorting the Timing struct
             self.list_element_entries.list[self.weekday_selected]
@@ -1335,7 +1527,7 @@ impl TimingsWidget {
                 .iter()
                 .enumerate()
                 .map(|(i, timings)| {
-                    let color = alternate_colors(i);
+                    let color = theme().row_bg(i);
                     let mut timings_joined = String::from(&timings.timing.0);
                     timings_joined.push('-');
                     timings_joined.push_str(&timings.timing.1);
@@ -1347,24 +1539,25 @@ impl TimingsWidget {
             // create a list from all the items and highlight the currently selected one
             let list = List::new(items)
                 .block(block)
-                .highlight_style(SELECTED_STYLE)
+                .highlight_style(theme().selected)
                 .highlight_symbol("> ")
                 .highlight_spacing(HighlightSpacing::Always);
 
             // we have to diferentiate this "render" from the render fn on self
             StatefulWidget::render(list, area, buf, &mut self.list_element_entries.list[self.weekday_selected].timings.state);
         } else {
-            Paragraph::new(Line::raw("No schedule set, press Enter to add a new one."))
-               .style(SELECTED_STYLE)
-               .bg(NORMAL_ROW_BG)
-               .fg(TEXT_FG_COLOR)
+            self.targets.list = Rect::default();
+            Paragraph::new(Line::raw(tr!("schedule.empty")))
+               .style(theme().selected)
+               .bg(theme().normal_row_bg)
+               .fg(theme().text_fg)
                .wrap(Wrap { trim:true })
                .block(
                    Block::new()
                    .borders(Borders::TOP | Borders::LEFT)
                    .border_set(symbols::border::EMPTY)
-                   .style(ITEM_HEADER_STYLE)
-                   .title(Line::raw("Add Timing").centered())
+                   .style(theme().item_header)
+                   .title(Line::raw(tr!("schedule.add_timing")).centered())
                )
                .render(area, buf);
 
@@ -1373,11 +1566,12 @@ impl TimingsWidget {
 
     fn render_op_list(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw("Select Task").centered())
+            .title(Line::raw(tr!("schedule.select_task")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -1386,7 +1580,7 @@ impl TimingsWidget {
             .iter()
             .enumerate()
             .map(|(i, ops)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                     ListItem::from(ops).bg(color)
             })
             .collect();
@@ -1394,7 +1588,7 @@ impl TimingsWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1402,40 +1596,34 @@ impl TimingsWidget {
 
     }
     fn render_add(&self, area: Rect, buf: &mut Buffer) {
-       Paragraph::new(self.input.as_str()) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
-           .block(
-               Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("Add Timing").centered())
-           )
-           .render(area, buf);
+        self.render_picker(tr!("schedule.add_timing"), area, buf);
     }
 
     fn render_edit(&mut self, area: Rect, buf: &mut Buffer) {
-        // set the current input as the entry selected.
+        // the picker starts from the timing being edited
+        self.render_picker(tr!("schedule.edit_timing"), area, buf);
+    }
 
-        Paragraph::new(self.input.as_str()) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
-           .block(
-               Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("Edit Timing").centered())
-           )
-           .render(area, buf);
+    fn render_picker(&self, title: &str, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .style(theme().item_header)
+            .title(Line::raw(title.to_string()).centered());
+        let inner = block.inner(area);
 
+        buf.set_style(area, Style::default().fg(theme().text_fg).bg(theme().normal_row_bg));
+        block.render(area, buf);
+        self.picker.render(inner, buf);
     }
 
     fn render_delete(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let block = Block::new()
-            .title(Line::raw("Are you sure?").centered())
+            .title(Line::raw(tr!("schedule.confirm_delete")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -1444,7 +1632,7 @@ impl TimingsWidget {
             .iter()
             .enumerate()
             .map(|(i, option)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(option).bg(color)
             })
             .collect();
@@ -1452,7 +1640,7 @@ impl TimingsWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1461,11 +1649,12 @@ impl TimingsWidget {
     fn render_duplicate(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let block = Block::new()
-            .title(Line::raw("Select a function").centered())
+            .title(Line::raw(tr!("schedule.select_function")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -1474,7 +1663,7 @@ impl TimingsWidget {
             .iter()
             .enumerate()
             .map(|(i, option)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(option).bg(color)
             })
             .collect();
@@ -1482,7 +1671,7 @@ impl TimingsWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1491,11 +1680,12 @@ impl TimingsWidget {
     fn render_duplicate_day(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let block = Block::new()
-            .title(Line::raw("Select a day").centered())
+            .title(Line::raw(tr!("schedule.select_copy_day")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -1504,7 +1694,7 @@ impl TimingsWidget {
             .iter()
             .enumerate()
             .map(|(i, option)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(option).bg(color)
             })
             .collect();
@@ -1512,7 +1702,7 @@ impl TimingsWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1520,48 +1710,50 @@ impl TimingsWidget {
     }
 
     fn render_file_explorer(&mut self, area: Rect, buf: &mut Buffer) {
+        self.targets.list = explorer_rows(area);
         self.file_explorer.widget().render(area, buf);
     }
     fn render_file_explorer_selected_item(&self, area: Rect, buf: &mut Buffer) {
 
-        let text = vec![ 
-                Line::from("Select a file using our file explorer or press 'm' to open the menu."),
-                Line::from("Use the arrow keys ⇅ to find the file you want to use."),
-                Line::from("Press ENTER to select the file."),
-                Line::from("To ascend a directory navigate to \"↑ Parent Folder ↑\" and press Enter"),
-                Line::from("USB sticks will show up automatically. Manually find them in the directory '/media'."),
+        let mut text = vec![ 
+                Line::from(tr!("schedule.import_instructions_1")),
+                Line::from(tr!("fileselect.file_instructions_2")),
+                Line::from(tr!("fileselect.file_instructions_3")),
+                Line::from(tr!("fileselect.parent_instructions")),
+                Line::from(tr!("schedule.usb_instructions")),
 
             ];
+        text.extend(self.mounted_drives.iter().map(|drive| Line::from(drive.summary())));
+        if !self.mounted_drives.is_empty() {
+            text.push(Line::from(tr!("fileselect.eject_hint")));
+        }
 
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INSTRUCTIONS").centered())
+            .title(Line::raw(tr!("common.instructions")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(text)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
 
     fn render_file_explorer_header(&mut self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Select a schedule for import")
+        Paragraph::new(tr!("schedule.import_header"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
     fn render_file_explorer_footer(&mut self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .style(FOOTER_STYLE)
-            .centered()
-            .render(area, buf);
+        self.render_footer(area, buf);
     }
 
 
@@ -1570,16 +1762,16 @@ impl TimingsWidget {
         // set the current input as the entry selected.
 
         let username = whoami::username();
-        let message = format!("Schedule has been exported to /home/{}/schedule.mt", username);
+        let message = tr!("schedule.exported", user = username);
 
         Paragraph::new(Line::raw(message)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("EXPORT").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("schedule.export_title")).centered())
            )
            .render(area, buf);
 
@@ -1588,13 +1780,13 @@ impl TimingsWidget {
         // set the current input as the entry selected.
 
         Paragraph::new(Line::raw(&self.message_text)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("MESSAGE").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("common.message")).centered())
            )
            .render(area, buf);
 
@@ -1602,18 +1794,18 @@ impl TimingsWidget {
     fn render_error(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let message = match self.error_type {
-            ErrorType::Format => "Formating Error! Please check the timing format you have entered. Schedule timings must use the 24 hour clock and must follow the format 00:00:00-00:00:00",
-            ErrorType::Clash => "Clash Error! Please check that the timing does not clash with another existing timing."
+            ErrorType::Format => tr!("schedule.format_error"),
+            ErrorType::Clash => tr!("schedule.clash_error")
         };
 
         Paragraph::new(Line::raw(message)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("ERROR").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("common.error")).centered())
            )
            .render(area, buf);
 
@@ -1621,11 +1813,12 @@ impl TimingsWidget {
     fn render_exit(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let block = Block::new()
-            .title(Line::raw("Ready to exit the schedule?").centered())
+            .title(Line::raw(tr!("schedule.exit_question")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -1634,7 +1827,7 @@ impl TimingsWidget {
             .iter()
             .enumerate()
             .map(|(i, option)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(option).bg(color)
             })
             .collect();
@@ -1642,7 +1835,7 @@ impl TimingsWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -1652,44 +1845,37 @@ impl TimingsWidget {
 
     fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
         let info = vec![ 
-            Line::from("Use the arrow keys ⇅ to select a day or press 'm' to open the menu."),
-            Line::from("Use ENTER or → to display the schedule."),
-            Line::from("Select a timing and press ENTER to Add, Edit or Delete."),
-            Line::from("Enter the start and end timings for each new schedule entry."),
-            Line::from("Use ESC or ← to exit."),
-            Line::from("Schedule timings must use the 24 hour clock and must follow the format 00:00:00-00:00:00"),
-            Line::from("Example: 12:20:00-13:15:00"),
+            Line::from(tr!("schedule.instructions_1")),
+            Line::from(tr!("schedule.instructions_2")),
+            Line::from(tr!("schedule.instructions_3")),
+            Line::from(tr!("schedule.instructions_4")),
+            Line::from(tr!("schedule.instructions_5")),
+            Line::from(tr!("schedule.instructions_6")),
+            Line::from(tr!("schedule.instructions_7")),
         ];
 
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INSTRUCTIONS").centered())
+            .title(Line::raw(tr!("common.instructions")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(info)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
 
 }
 
-const fn alternate_colors(i: usize) -> Color {
-    if i.is_multiple_of(2) {
-        NORMAL_ROW_BG
-    } else {
-        ALT_ROW_BG_COLOR
-    }
-}
-
 impl Widget for &mut TimingsWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        self.targets.screen = area;
 
         let popup_area: Rect = areas::popup_area(area);
 
@@ -1711,7 +1897,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(main_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 self.render_weekdays_list(list_area, buf);
                 self.render_selected_item(item_area, buf);
 
@@ -1737,7 +1923,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
                 self.render_selected_item(item_area, buf);
@@ -1764,7 +1950,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -1794,7 +1980,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
                 self.render_selected_item(item_area, buf);
@@ -1825,7 +2011,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
                 self.render_selected_item(item_area, buf);
@@ -1855,7 +2041,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -1884,7 +2070,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -1913,7 +2099,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -1962,7 +2148,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -1991,7 +2177,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -2021,7 +2207,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(list_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
 
                 self.render_weekdays_list(weekdays_area, buf);
                 self.render_day_list(day_area, buf);
@@ -2045,7 +2231,7 @@ impl Widget for &mut TimingsWidget {
                 .areas(main_area);
 
                 TimingsWidget::render_header(header_area, buf);
-                TimingsWidget::render_footer(footer_area, buf);
+                self.render_footer(footer_area, buf);
                 self.render_weekdays_list(list_area, buf);
                 self.render_selected_item(item_area, buf);
 
@@ -2055,6 +2241,20 @@ impl Widget for &mut TimingsWidget {
 
             },
         }
+
+        if let Some(toast) = self.toast.as_ref().filter(|toast| !toast.expired()) {
+            let toast_area = Toast::area(area);
+            Clear.render(toast_area, buf);
+            toast.render(toast_area, buf);
+        }
+
+        if self.help {
+            let about = match self.current_screen {
+                CurrentScreen::Add | CurrentScreen::Edit => tr!("help.schedule_entry"),
+                _ => tr!("help.schedule")
+            };
+            self.keymap().render_help(about, area, buf);
+        }
     }
 }
 
@@ -2222,12 +2422,12 @@ mod tests {
     fn check_extract_timings_from_input() {
         let mut t_widget = TimingsWidget::default();
         t_widget.input = String::from("10:00:00-11:00:00");
-        let extracted_timings = t_widget.extract_timings_from_input(&t_widget.input);
+        let extracted_timings = extract_timings(&t_widget.input);
         assert_eq!(extracted_timings.0, 100000 as u32);
         assert_eq!(extracted_timings.1, 110000 as u32);
 
         let input = String::from("12:12:12-16:00:00");
-        let extracted_timings = t_widget.extract_timings_from_input(&input);
+        let extracted_timings = extract_timings(&input);
         assert_eq!(extracted_timings.0, 121212 as u32);
         assert_eq!(extracted_timings.1, 160000 as u32);
     }
@@ -2295,5 +2495,56 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn check_keymaps() {
+        let mut t_widget = TimingsWidget::default();
+        // building each keymap checks that no key is bound twice
+        for screen in [
+            CurrentScreen::Weekdays, CurrentScreen::Day, CurrentScreen::TimingOptions, CurrentScreen::Add,
+            CurrentScreen::Edit, CurrentScreen::Delete, CurrentScreen::Duplicate, CurrentScreen::DuplicateDay,
+            CurrentScreen::Import, CurrentScreen::Message, CurrentScreen::Export, CurrentScreen::Error,
+            CurrentScreen::Exit
+        ] {
+            t_widget.current_screen = screen;
+            assert_eq!(t_widget.keymap().action(KeyCode::F(1)), Some(Action::Help));
+            // and building each footer checks its hints against the keymap
+            let _ = t_widget.footer();
+        }
+        t_widget.current_screen = CurrentScreen::Add;
+        assert!(!t_widget.footer().unwrap().line().to_string().contains(tr!("footer.quit")));
+
+        t_widget.current_screen = CurrentScreen::Weekdays;
+        t_widget.handle_key(KeyEvent::from(KeyCode::Char('?'))).unwrap();
+        assert!(t_widget.help);
+        // the key that closes the help does nothing else
+        t_widget.handle_key(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert!(!t_widget.help);
+        assert_eq!(t_widget.list_element_entries.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn check_picker_entry() {
+        let mut t_widget = TimingsWidget::default();
+        t_widget.current_screen = CurrentScreen::Add;
+        // shorthand is still accepted
+        for c in "6pm-11pm".chars() {
+            t_widget.handle_key(KeyEvent::from(KeyCode::Char(c))).unwrap();
+        }
+        t_widget.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        assert!(t_widget.current_screen == CurrentScreen::TimingOptions);
+        let timing = &t_widget.list_element_entries.list[0].timings.timing_collection[1].timing;
+        assert_eq!(timing, &(String::from("18:00:00"), String::from("23:00:00")));
+
+        // the picker starts from the default 09:00:00-17:00:00, so moving the end an hour later clashes
+        t_widget.current_screen = CurrentScreen::Add;
+        t_widget.picker = TimeRangePicker::default();
+        for code in [KeyCode::Tab, KeyCode::Up] {
+            t_widget.handle_key(KeyEvent::from(code)).unwrap();
+        }
+        t_widget.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        assert!(t_widget.current_screen == CurrentScreen::Error);
+        assert_eq!(t_widget.input, "09:00:00-18:00:00");
+    }
     
 }
@@ -0,0 +1,596 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{
+        Stylize,
+    },
+    symbols,
+    text::Line,
+    widgets::{
+        Block, Borders, ListState, Padding, Paragraph,
+        StatefulWidget, Widget, Wrap, ListItem, List,
+        HighlightSpacing
+    },
+    DefaultTerminal,
+};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf
+    },
+    process::Command,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            Ordering
+        },
+        mpsc::{
+            self,
+            Receiver,
+            TryRecvError
+        }
+    },
+    thread
+};
+
+use crate::styles::theme;
+
+use crate::{
+    Model,
+    SlideOrder,
+    SlideshowOptions
+};
+use crate::runner::{
+    CommandRunner,
+    SystemRunner
+};
+use crate::tr;
+use crate::keymap::{
+    Action,
+    Keymap
+};
+use crate::wizard::{
+    Nav,
+    Step
+};
+
+pub const MIN_SLIDE_DELAY: u32 = 1;
+pub const MAX_SLIDE_DELAY: u32 = 3600;
+
+// files feh can show, compared case-insensitively
+pub const IMAGE_EXTENSIONS: [&str; 11] = [
+    "jpg", "jpeg", "png", "gif", "webp", "bmp", "tif", "tiff", "heic", "avif", "svg"
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SlideshowSetting {
+    Delay,
+    Order,
+    Recursive,
+    SkipNonImages
+}
+
+pub struct SlideshowWidget {
+    should_exit: bool,
+    nav: Nav,
+    slide_delay: u32,
+    options: SlideshowOptions,
+    state: ListState,
+    help: bool
+}
+
+fn settings() -> [(SlideshowSetting, &'static str); 4] {
+    [
+        (SlideshowSetting::Delay, tr!("slideshow.delay_info")),
+        (SlideshowSetting::Order, tr!("slideshow.order_info")),
+        (SlideshowSetting::Recursive, tr!("slideshow.recursive_info")),
+        (SlideshowSetting::SkipNonImages, tr!("slideshow.skip_info"))
+    ]
+}
+
+/// The name of `order` as shown on screen.
+pub fn slide_order_label(order: &SlideOrder) -> &'static str {
+    match order {
+        SlideOrder::Name => tr!("slideshow.order_name"),
+        SlideOrder::Modified => tr!("slideshow.order_modified"),
+        SlideOrder::Shuffle => tr!("slideshow.order_shuffle")
+    }
+}
+
+impl SlideshowWidget {
+    pub fn new(slide_delay: u32, options: SlideshowOptions) -> Self {
+        let mut state = ListState::default();
+        state.select_first();
+        Self {
+            should_exit: false,
+            nav: Nav::Next,
+            slide_delay: slide_delay.clamp(MIN_SLIDE_DELAY, MAX_SLIDE_DELAY),
+            options,
+            state,
+            help: false
+        }
+    }
+
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<(u32, SlideshowOptions)>, Box< dyn Error>> {
+        while !self.should_exit {
+            terminal.draw(|f| f.render_widget(&mut self, f.area()))?;
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            };
+        }
+        Ok(self.nav.with((self.slide_delay, self.options)))
+    }
+
+    fn keymap() -> Keymap {
+        Keymap::list()
+            .bind(&[KeyCode::Char('h'), KeyCode::Left], Action::Decrease)
+            .bind(&[KeyCode::Char('l'), KeyCode::Right], Action::Increase)
+            .bind(&[KeyCode::PageDown], Action::DecreaseMore)
+            .bind(&[KeyCode::PageUp], Action::IncreaseMore)
+            .bind(&[KeyCode::Enter], Action::Continue)
+            .bind(&[KeyCode::Esc], Action::Back)
+            .bind(&[KeyCode::Char('q')], Action::Quit)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return;
+        }
+        match SlideshowWidget::keymap().action(key.code) {
+            Some(Action::Continue) => self.leave(Nav::Next),
+            Some(Action::Back) => self.leave(Nav::Back),
+            Some(Action::Quit) => self.leave(Nav::Cancel),
+            Some(Action::Next) => self.state.select_next(),
+            Some(Action::Previous) => self.state.select_previous(),
+            Some(Action::First) => self.state.select_first(),
+            Some(Action::Last) => self.state.select_last(),
+            Some(Action::Decrease) => self.change_setting(-1),
+            Some(Action::Increase) => self.change_setting(1),
+            Some(Action::DecreaseMore) => self.change_setting(-10),
+            Some(Action::IncreaseMore) => self.change_setting(10),
+            Some(Action::Help) => self.help = true,
+            _ => {}
+        }
+    }
+
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
+    fn selected_setting(&self) -> Option<SlideshowSetting> {
+        self.state.selected()
+            .and_then(|i| settings().into_iter().nth(i))
+            .map(|(setting, _)| setting)
+    }
+
+    fn change_setting(&mut self, step: i64) {
+        match self.selected_setting() {
+            Some(SlideshowSetting::Delay) => {
+                let delay = (self.slide_delay as i64 + step).clamp(MIN_SLIDE_DELAY as i64, MAX_SLIDE_DELAY as i64);
+                self.slide_delay = delay as u32;
+            },
+            Some(SlideshowSetting::Order) => {
+                self.options.order = match (&self.options.order, step > 0) {
+                    (SlideOrder::Name, true) | (SlideOrder::Shuffle, false) => SlideOrder::Modified,
+                    (SlideOrder::Modified, true) | (SlideOrder::Name, false) => SlideOrder::Shuffle,
+                    (SlideOrder::Shuffle, true) | (SlideOrder::Modified, false) => SlideOrder::Name
+                };
+            },
+            Some(SlideshowSetting::Recursive) => self.options.recursive = !self.options.recursive,
+            Some(SlideshowSetting::SkipNonImages) => self.options.skip_non_images = !self.options.skip_non_images,
+            None => {}
+        }
+    }
+
+    fn setting_line(&self, setting: SlideshowSetting) -> String {
+        let yes_no = |value: bool| if value { tr!("common.yes") } else { tr!("common.no") };
+        match setting {
+            SlideshowSetting::Delay => tr!("slideshow.delay", seconds = self.slide_delay),
+            SlideshowSetting::Order => tr!("slideshow.order", order = slide_order_label(&self.options.order)),
+            SlideshowSetting::Recursive => tr!("slideshow.recursive", value = yes_no(self.options.recursive)),
+            SlideshowSetting::SkipNonImages => tr!("slideshow.skip", value = yes_no(self.options.skip_non_images))
+        }
+    }
+
+    // rendering logic
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("slideshow.header"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(tr!("slideshow.footer"))
+            .style(theme().footer)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(tr!("slideshow.question")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+
+        let items: Vec<ListItem> = settings()
+            .iter()
+            .enumerate()
+            .map(|(i, (setting, _))| {
+                let color = theme().row_bg(i);
+                ListItem::new(Line::styled(self.setting_line(*setting), theme().text_fg)).bg(color)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme().selected)
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+
+    fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
+        let info = self.state.selected()
+            .and_then(|i| settings().into_iter().nth(i))
+            .map_or(tr!("common.nothing_selected"), |(_, info)| info);
+
+        let block = Block::new()
+            .title(Line::raw(tr!("common.information")).centered())
+            .borders(Borders::TOP)
+            .border_set(symbols::border::EMPTY)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
+            .padding(Padding::horizontal(1));
+
+        Paragraph::new(info)
+            .block(block)
+            .fg(theme().text_fg)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut SlideshowWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [list_area, item_area] = Layout::vertical([
+            Constraint::Fill(2),
+            Constraint::Fill(1)
+        ])
+        .areas(main_area);
+
+        SlideshowWidget::render_header(header_area, buf);
+        SlideshowWidget::render_footer(footer_area, buf);
+        self.render_list(list_area, buf);
+        self.render_selected_item(item_area, buf);
+
+        if self.help {
+            SlideshowWidget::keymap().render_help(tr!("help.slideshow"), area, buf);
+        }
+    }
+}
+
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+/// Lists the files in a slideshow folder, sorted by path. Hidden files are left out, as they
+/// are usually thumbnails or metadata written by other systems.
+pub fn slideshow_files(dir: &Path, recursive: bool) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    collect_files(dir, recursive, &mut visited, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+// `visited` holds the folders already listed, so a symlink back up the tree is only followed once
+fn collect_files(dir: &Path, recursive: bool, visited: &mut HashSet<PathBuf>, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !visited.insert(fs::canonicalize(dir)?) {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            if recursive {
+                collect_files(&path, recursive, visited, files)?;
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// What a slideshow folder holds. Images in subfolders are only counted, as they are not
+/// shown unless subfolders are included.
+#[derive(Debug, Default, PartialEq)]
+pub struct ImageInventory {
+    pub images: usize,
+    pub subfolder_images: usize,
+    pub unsupported: usize,
+    pub empty: usize,
+    pub unreadable: usize,
+    // images bigger than the model's display
+    pub oversized: usize,
+    pub largest: Option<(u32, u32)>
+}
+
+enum InventoryUpdate {
+    Checked(usize, usize),
+    Finished(ImageInventory),
+    Failed(String)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InventoryState {
+    // fraction of the files checked
+    Running(f64),
+    Finished(ImageInventory),
+    Failed(String)
+}
+
+/// Counts what is in a slideshow folder on a separate thread, as probing every image in a large
+/// folder takes a while.
+pub struct InventoryJob {
+    updates: Receiver<InventoryUpdate>,
+    cancel: Arc<AtomicBool>,
+    folder: PathBuf,
+    progress: f64
+}
+
+impl InventoryJob {
+    pub fn start(folder: PathBuf, model: &Model) -> Self {
+        let (sender, updates) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = Arc::clone(&cancel);
+        let thread_folder = folder.clone();
+        let limit = model.max_resolution();
+        thread::spawn(move || {
+            let checked = |done, total| {
+                let _ = sender.send(InventoryUpdate::Checked(done, total));
+                !thread_cancel.load(Ordering::Relaxed)
+            };
+            let update = match build_inventory(&thread_folder, limit, |file| image_size(&SystemRunner, file), checked) {
+                Ok(inventory) => InventoryUpdate::Finished(inventory),
+                Err(e) => InventoryUpdate::Failed(e.to_string())
+            };
+            let _ = sender.send(update);
+        });
+
+        Self {
+            updates,
+            cancel,
+            folder,
+            progress: 0.0
+        }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    pub fn poll(&mut self) -> InventoryState {
+        loop {
+            match self.updates.try_recv() {
+                Ok(InventoryUpdate::Checked(done, total)) => self.progress = done as f64 / total.max(1) as f64,
+                Ok(InventoryUpdate::Finished(inventory)) => return InventoryState::Finished(inventory),
+                Ok(InventoryUpdate::Failed(reason)) => return InventoryState::Failed(reason),
+                Err(TryRecvError::Empty) => return InventoryState::Running(self.progress),
+                Err(TryRecvError::Disconnected) => return InventoryState::Failed(tr!("library.stopped").to_string())
+            }
+        }
+    }
+
+    /// Stops checking the folder after the image being probed.
+    pub fn cancel(self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Counts the images in `dir`, calling `checked` with the files done so far and the total after
+/// each one. Counting stops early when `checked` returns false. Files that vanish or cannot be
+/// read while counting are counted as unreadable.
+fn build_inventory<F, C>(dir: &Path, limit: (u32, u32), probe: F, mut checked: C) -> io::Result<ImageInventory>
+where
+    F: Fn(&Path) -> Option<(u32, u32)>,
+    C: FnMut(usize, usize) -> bool
+{
+    let mut inventory = ImageInventory::default();
+    let files = slideshow_files(dir, true)?;
+    for (i, file) in files.iter().enumerate() {
+        if !checked(i, files.len()) {
+            break;
+        }
+        let in_subfolder = file.parent() != Some(dir);
+        if !is_image(file) {
+            if !in_subfolder {
+                inventory.unsupported += 1;
+            }
+            continue;
+        }
+        if in_subfolder {
+            inventory.subfolder_images += 1;
+            continue;
+        }
+        match fs::metadata(file) {
+            Ok(metadata) if metadata.len() == 0 => {
+                inventory.empty += 1;
+                continue;
+            },
+            Ok(_) => {},
+            Err(_) => {
+                inventory.unreadable += 1;
+                continue;
+            }
+        }
+        match probe(file) {
+            Some((width, height)) => {
+                inventory.images += 1;
+                // compare the long and short sides so portrait images are judged fairly
+                if width.max(height) > limit.0 || width.min(height) > limit.1 {
+                    inventory.oversized += 1;
+                }
+                let pixels = width as u64 * height as u64;
+                if inventory.largest.is_none_or(|(w, h)| pixels > w as u64 * h as u64) {
+                    inventory.largest = Some((width, height));
+                }
+            },
+            None => inventory.unreadable += 1
+        }
+    }
+    Ok(inventory)
+}
+
+// ffprobe reads the dimensions of every format feh is likely to be given
+fn image_size(runner: &dyn CommandRunner, file: &Path) -> Option<(u32, u32)> {
+    let output = runner.output(Command::new("ffprobe")
+        .args(["-loglevel", "error", "-select_streams", "v:0", "-show_entries", "stream=width,height", "-of", "csv=p=0:s=x"])
+        .arg(file))
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_size(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_size(output: &str) -> Option<(u32, u32)> {
+    let (width, height) = output.lines().next()?.trim().split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn check_change_setting() {
+        let mut widget = SlideshowWidget::new(0, SlideshowOptions::default());
+        // stored delays below the minimum are raised
+        assert_eq!(widget.slide_delay, MIN_SLIDE_DELAY);
+        widget.change_setting(-1);
+        assert_eq!(widget.slide_delay, MIN_SLIDE_DELAY);
+        widget.change_setting(10);
+        assert_eq!(widget.slide_delay, 11);
+
+        widget.state.select(Some(1));
+        widget.change_setting(1);
+        assert_eq!(widget.options.order, SlideOrder::Modified);
+        widget.change_setting(-1);
+        widget.change_setting(-1);
+        assert_eq!(widget.options.order, SlideOrder::Shuffle);
+
+        widget.state.select(Some(2));
+        widget.change_setting(1);
+        assert!(widget.options.recursive);
+    }
+
+    #[test]
+    fn check_slideshow_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("b.JPG"), "").unwrap();
+        fs::write(dir.path().join("a.png"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+        fs::write(dir.path().join(".thumb.png"), "").unwrap();
+        fs::create_dir(dir.path().join("more")).unwrap();
+        fs::write(dir.path().join("more/c.webp"), "").unwrap();
+
+        let files = slideshow_files(dir.path(), false).unwrap();
+        assert_eq!(files, vec![dir.path().join("a.png"), dir.path().join("b.JPG"), dir.path().join("notes.txt")]);
+        let images: Vec<PathBuf> = files.into_iter().filter(|f| is_image(f)).collect();
+        assert_eq!(images.len(), 2);
+
+        let files = slideshow_files(dir.path(), true).unwrap();
+        assert!(files.contains(&dir.path().join("more/c.webp")));
+    }
+
+    #[test]
+    fn check_slideshow_files_symlink_loop() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("more")).unwrap();
+        fs::write(dir.path().join("more/c.webp"), "").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("more/back")).unwrap();
+
+        let files = slideshow_files(dir.path(), true).unwrap();
+        assert_eq!(files, vec![dir.path().join("more/c.webp")]);
+    }
+
+    #[test]
+    fn check_parse_size() {
+        assert_eq!(parse_size("1920x1080\n"), Some((1920, 1080)));
+        assert_eq!(parse_size(""), None);
+        assert_eq!(parse_size("N/AxN/A"), None);
+    }
+
+    #[test]
+    fn check_image_inventory() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("small.png"), "x").unwrap();
+        fs::write(dir.path().join("huge.jpg"), "x").unwrap();
+        fs::write(dir.path().join("tall.jpg"), "x").unwrap();
+        fs::write(dir.path().join("broken.jpg"), "x").unwrap();
+        fs::write(dir.path().join("empty.png"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        fs::create_dir(dir.path().join("more")).unwrap();
+        fs::write(dir.path().join("more/c.webp"), "x").unwrap();
+        // pulled out from under the scan
+        std::os::unix::fs::symlink(dir.path().join("missing.jpg"), dir.path().join("gone.jpg")).unwrap();
+
+        let probe = |file: &Path| match file.file_name()?.to_str()? {
+            "small.png" => Some((800, 600)),
+            "huge.jpg" => Some((6000, 4000)),
+            "tall.jpg" => Some((1080, 1920)),
+            _ => None
+        };
+        let mut checked = Vec::new();
+        let inventory = build_inventory(dir.path(), Model::Eco.max_resolution(), probe, |done, total| {
+            checked.push((done, total));
+            true
+        }).unwrap();
+        assert_eq!(checked.first(), Some(&(0, 8)));
+        assert_eq!(checked.len(), 8);
+        assert_eq!(inventory, ImageInventory {
+            images: 3,
+            subfolder_images: 1,
+            unsupported: 1,
+            empty: 1,
+            unreadable: 2,
+            oversized: 1,
+            largest: Some((6000, 4000))
+        });
+
+        // stopped after the first file
+        let inventory = build_inventory(dir.path(), Model::Eco.max_resolution(), probe, |done, _| done == 0).unwrap();
+        assert_eq!(inventory.images + inventory.unsupported + inventory.empty + inventory.unreadable, 1);
+    }
+}
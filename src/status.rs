@@ -0,0 +1,58 @@
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf
+};
+use serde::{
+    Deserialize,
+    Serialize
+};
+use strum::Display;
+
+use crate::env_file_path;
+
+#[derive(Debug, Clone, PartialEq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaybackState {
+    // outside of the scheduled times
+    Idle,
+    Playing,
+    // the player finished and will not play again until the next window
+    Finished,
+    // waiting to restart a player that crashed or hung
+    Restarting
+}
+
+/// Written by `mediatimer run` so other tools can see what is playing. `updated` doubles as a
+/// heartbeat for the supervisor itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    pub updated: String,
+    pub state: PlaybackState,
+    pub proc_type: String,
+    pub player: String,
+    pub source: String,
+    pub pid: Option<u32>,
+    pub running_secs: Option<u64>,
+    pub restarts: u32,
+    pub last_problem: Option<String>
+}
+
+pub fn status_file_path() -> PathBuf {
+    env_file_path().with_file_name("status.json")
+}
+
+/// The last status written by `mediatimer run`, if it has written one.
+pub fn read_status() -> Option<Status> {
+    let contents = fs::read_to_string(status_file_path()).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Replaces the status file in one step so readers never see a partly written file.
+pub fn write_status(status: &Status) -> Result<(), Box<dyn Error>> {
+    let path = status_file_path();
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string_pretty(status)?)?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
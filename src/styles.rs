@@ -1,9 +1,34 @@
 use ratatui::{
     style::{
         palette::tailwind::{WHITE},
-        Color, Modifier, Style, 
+        Color, Modifier, Style,
     },
 };
+use std::{
+    fs,
+    error::Error,
+    path::PathBuf,
+    sync::atomic::{
+        AtomicUsize,
+        Ordering
+    }
+};
+use strum::{
+    Display,
+    EnumString,
+    EnumIter,
+    IntoEnumIterator
+};
+use log::{
+    info,
+    warn
+};
+use crate::{
+    logi,
+    logw
+};
+
+use crate::env_file_path;
 
 // #ffd6ff
 const PINK: Color = Color::Rgb(255, 214, 255);
@@ -19,12 +44,162 @@ const BLUE: Color = Color::Rgb(15, 40, 48);
 //const BLUE_LIGHTEST: Color = Color::Rgb(23, 55, 66);
 //const BLUE_ALT: Color = Color::Rgb(32, 61, 71);
 //const GREEN: Color = Color::Rgb(4, 211, 126);
+const BLACK: Color = Color::Rgb(0, 0, 0);
+// #1c1c1c
+const GREY_DARK: Color = Color::Rgb(28, 28, 28);
+const YELLOW: Color = Color::Rgb(255, 255, 0);
+const PURE_WHITE: Color = Color::Rgb(255, 255, 255);
+// #ebebeb
+const GREY_LIGHT: Color = Color::Rgb(235, 235, 235);
+// #141414
+const INK: Color = Color::Rgb(20, 20, 20);
+// #003366
+const NAVY: Color = Color::Rgb(0, 51, 102);
+
+/// The colours every screen is drawn with.
+#[derive(Debug, PartialEq)]
+pub struct Theme {
+    pub item_header: Style,
+    pub normal_row_bg: Color,
+    pub alt_row_bg: Color,
+    pub selected: Style,
+    pub text_fg: Color,
+    pub text_dir: Color,
+    pub footer: Style
+}
+
+impl Theme {
+    /// The background of row `i` of a list. Rows alternate so they are easy to tell apart.
+    pub const fn row_bg(&self, i: usize) -> Color {
+        if i.is_multiple_of(2) {
+            self.normal_row_bg
+        } else {
+            self.alt_row_bg
+        }
+    }
+}
+
+/// The built in themes, as named in the theme file.
+#[derive(Debug, Display, EnumString, EnumIter, PartialEq, Clone, Copy, Default)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ThemeName {
+    #[default]
+    Default,
+    /// White and yellow on black, for operators with low vision.
+    HighContrast,
+    /// Dark text on white, for projectors that wash out the default colours.
+    Light
+}
+
+impl ThemeName {
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            ThemeName::Default => &DEFAULT,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+            ThemeName::Light => &LIGHT
+        }
+    }
+
+    /// The theme after this one, wrapping round to the first.
+    pub fn next(self) -> ThemeName {
+        let themes: Vec<ThemeName> = ThemeName::iter().collect();
+        let i = themes.iter().position(|name| *name == self).unwrap_or(0);
+        themes[(i + 1) % themes.len()]
+    }
+}
+
+const DEFAULT: Theme = Theme {
+    item_header: Style::new().fg(WHITE).bg(BLUE),
+    normal_row_bg: PINK,
+    alt_row_bg: PINK_LIGHT,
+    selected: Style::new().bg(BLUE).fg(WHITE).add_modifier(Modifier::BOLD),
+    text_fg: BLUE,
+    text_dir: BLUE,
+    footer: Style::new().bg(BLUE).fg(WHITE)
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    item_header: Style::new().fg(BLACK).bg(YELLOW).add_modifier(Modifier::BOLD),
+    normal_row_bg: BLACK,
+    alt_row_bg: GREY_DARK,
+    selected: Style::new().bg(YELLOW).fg(BLACK).add_modifier(Modifier::BOLD),
+    text_fg: PURE_WHITE,
+    text_dir: YELLOW,
+    footer: Style::new().bg(PURE_WHITE).fg(BLACK).add_modifier(Modifier::BOLD)
+};
+
+const LIGHT: Theme = Theme {
+    item_header: Style::new().fg(PURE_WHITE).bg(NAVY),
+    normal_row_bg: PURE_WHITE,
+    alt_row_bg: GREY_LIGHT,
+    selected: Style::new().bg(NAVY).fg(PURE_WHITE).add_modifier(Modifier::BOLD),
+    text_fg: INK,
+    text_dir: NAVY,
+    footer: Style::new().bg(NAVY).fg(PURE_WHITE)
+};
+
+// the index of the current theme in ThemeName::iter()
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// The theme the screens are drawn with now.
+pub fn theme() -> &'static Theme {
+    theme_name().theme()
+}
+
+pub fn theme_name() -> ThemeName {
+    ThemeName::iter().nth(CURRENT.load(Ordering::Relaxed)).unwrap_or_default()
+}
+
+pub fn set_theme(name: ThemeName) {
+    let i = ThemeName::iter().position(|theme| theme == name).unwrap_or(0);
+    CURRENT.store(i, Ordering::Relaxed);
+}
+
+/// The file holding the name of the chosen theme, next to the config.
+fn theme_file_path() -> PathBuf {
+    env_file_path().with_file_name("theme")
+}
+
+/// Reads the chosen theme from the config folder. The default theme is kept when none has been
+/// chosen or the name is not recognised.
+pub fn load_theme() {
+    let Ok(name) = fs::read_to_string(theme_file_path()) else {
+        return;
+    };
+    match name.trim().parse::<ThemeName>() {
+        Ok(name) => {
+            logi!("Theme: {}", name);
+            set_theme(name);
+        },
+        Err(_) => logw!("Unknown theme {}, the default theme will be used", name.trim())
+    }
+}
+
+/// Switches to `name` and remembers it for next time.
+pub fn save_theme(name: ThemeName) -> Result<(), Box<dyn Error>> {
+    set_theme(name);
+    let path = theme_file_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, format!("{}\n", name))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub const ITEM_HEADER_STYLE: Style = Style::new().fg(WHITE).bg(BLUE);
-pub const NORMAL_ROW_BG: Color = PINK;
-pub const ALT_ROW_BG_COLOR: Color = PINK_LIGHT;
-pub const SELECTED_STYLE: Style = Style::new().bg(BLUE).fg(WHITE).add_modifier(Modifier::BOLD);
-pub const TEXT_FG_COLOR: Color = BLUE;
-pub const TEXT_DIR_COLOR: Color = BLUE;
-pub const FOOTER_STYLE: Style = Style::new().bg(BLUE).fg(WHITE);
+    #[test]
+    fn check_theme_names() {
+        assert_eq!("high-contrast".parse::<ThemeName>(), Ok(ThemeName::HighContrast));
+        assert_eq!("Light".parse::<ThemeName>(), Ok(ThemeName::Light));
+        assert!("neon".parse::<ThemeName>().is_err());
+        assert_eq!(ThemeName::HighContrast.to_string(), "high-contrast");
 
+        assert_eq!(ThemeName::Default.next(), ThemeName::HighContrast);
+        assert_eq!(ThemeName::Light.next(), ThemeName::Default);
+        assert_eq!(HIGH_CONTRAST.row_bg(0), BLACK);
+        assert_eq!(HIGH_CONTRAST.row_bg(1), GREY_DARK);
+    }
+}
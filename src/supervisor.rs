@@ -0,0 +1,488 @@
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    thread,
+    time::{
+        Duration,
+        Instant,
+        SystemTime
+    }
+};
+use chrono::{
+    Datelike,
+    Local,
+    Timelike
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::{
+    Task,
+    ProcType,
+    Autoloop,
+    AdvancedSchedule,
+    Model,
+    load_task,
+    read_model,
+    env_file_path,
+    media_root
+};
+use crate::runner::SystemRunner;
+use crate::mount::{
+    Resolution,
+    identify_mounted_drives,
+    resolve_media
+};
+use crate::schedule::active::{
+    active_window,
+    Window
+};
+use crate::player::backend::{
+    PlayerBackend,
+    PlayerStatus,
+    backend_for
+};
+use crate::watchdog::{
+    Watchdog,
+    cpu_ticks
+};
+use crate::status::{
+    Status,
+    PlaybackState,
+    write_status
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+// a player that stays up this long is considered healthy again
+const STABLE_RUN: Duration = Duration::from_secs(30);
+// the status file is rewritten at least this often so readers can tell the supervisor is alive
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Exponential delay between restarts of a player that keeps exiting.
+struct Backoff {
+    failures: u32,
+    retry_at: Option<Instant>
+}
+
+impl Backoff {
+    fn new() -> Self {
+        Self {
+            failures: 0,
+            retry_at: None
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        BACKOFF_BASE
+            .saturating_mul(2u32.saturating_pow(self.failures))
+            .min(BACKOFF_MAX)
+    }
+
+    // returns how long to wait before the next attempt
+    fn record_exit(&mut self, ran_for: Duration) -> Duration {
+        if ran_for >= STABLE_RUN {
+            self.failures = 0;
+        }
+        let delay = self.delay();
+        self.retry_at = Some(Instant::now() + delay);
+        self.failures = self.failures.saturating_add(1);
+        delay
+    }
+
+    fn ready(&self) -> bool {
+        self.retry_at.is_none_or(|retry_at| Instant::now() >= retry_at)
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+        self.retry_at = None;
+    }
+}
+
+// the player that was started, the backend owns the actual process
+struct Player {
+    started: Instant,
+    window: Option<Window>
+}
+
+/// Keeps the player for the configured Task running while the schedule says it should be, and
+/// stops it outside of the scheduled times.
+pub struct Supervisor {
+    task: Task,
+    backend: Box<dyn PlayerBackend>,
+    player: Option<Player>,
+    backoff: Backoff,
+    watchdog: Option<Watchdog>,
+    restarts: u32,
+    last_problem: Option<String>,
+    // set when the player finished on its own and should not be replayed in this window
+    finished: bool,
+    finished_window: Option<Window>
+}
+
+impl Supervisor {
+    pub fn new(task: Task, model: Model) -> Self {
+        let backend = backend_for(&task, &model);
+        Self::with_backend(task, backend)
+    }
+
+    pub fn with_backend(task: Task, backend: Box<dyn PlayerBackend>) -> Self {
+        let watchdog = Watchdog::for_task(&task);
+        Self {
+            task,
+            backend,
+            player: None,
+            backoff: Backoff::new(),
+            watchdog,
+            restarts: 0,
+            last_problem: None,
+            finished: false,
+            finished_window: None
+        }
+    }
+
+    /// A clean exit only ends playback for media that is not looped and for executables, which
+    /// may launch other software and exit straight away.
+    fn finishes_cleanly(&self) -> bool {
+        match self.task.proc_type {
+            ProcType::Video | ProcType::Audio => {
+                self.task.advanced_schedule == AdvancedSchedule::Yes && self.task.auto_loop == Autoloop::No
+            },
+            ProcType::Executable => true,
+            _ => false
+        }
+    }
+
+    /// Returns whether content should be showing, and the window it belongs to when an advanced
+    /// schedule is in use.
+    fn scheduled(&self, day: usize, seconds: u32) -> (bool, Option<Window>) {
+        match self.task.advanced_schedule {
+            AdvancedSchedule::Yes => {
+                let window = active_window(&self.task.timings, day, seconds);
+                (window.is_some(), window)
+            },
+            AdvancedSchedule::No => (true, None)
+        }
+    }
+
+    /// Brings the player in line with the schedule at `day` (0 = Monday) and `seconds` since
+    /// midnight.
+    pub fn tick(&mut self, day: usize, seconds: u32) {
+        let (should_play, window) = self.scheduled(day, seconds);
+
+        // a new window means a finished player may play again
+        if self.finished && self.finished_window != window {
+            self.finished = false;
+        }
+
+        // reap a player that has exited on its own
+        if let Some(player) = self.player.as_ref() {
+            let code = match self.backend.status() {
+                PlayerStatus::Running => None,
+                PlayerStatus::Exited(code) => Some(code),
+                // the backend lost track of the player
+                PlayerStatus::Stopped => Some(None)
+            };
+            if let Some(code) = code {
+                let ran_for = player.started.elapsed();
+                let player_window = player.window;
+                self.player = None;
+
+                if code == Some(0) && self.finishes_cleanly() {
+                    logi!("Player finished");
+                    self.finished = true;
+                    self.finished_window = player_window;
+                    self.backoff.reset();
+                } else {
+                    let problem = code.map_or(String::from("killed by a signal"), |c| format!("exited with code {}", c));
+                    self.restart_later(ran_for, problem);
+                }
+            }
+        }
+
+        // stop a player that is still running but no longer playing
+        if let Some(player) = self.player.as_ref() && let Some(watchdog) = self.watchdog.as_mut() {
+            let cpu = self.backend.pid().and_then(cpu_ticks);
+            let position = self.backend.position();
+            if let Some(hang) = watchdog.observe(Instant::now(), cpu, position) {
+                let ran_for = player.started.elapsed();
+                self.stop();
+                self.restart_later(ran_for, format!("hung ({})", hang));
+            }
+        }
+
+        if !should_play {
+            self.stop();
+            self.backoff.reset();
+            self.finished = false;
+        } else if self.player.is_none() && !self.finished && self.backoff.ready() {
+            self.start(window);
+        }
+    }
+
+    fn restart_later(&mut self, ran_for: Duration, problem: String) {
+        let delay = self.backoff.record_exit(ran_for);
+        logw!("Player {}, restarting in {}s", problem, delay.as_secs());
+        self.restarts = self.restarts.saturating_add(1);
+        self.last_problem = Some(problem);
+    }
+
+    fn start(&mut self, window: Option<Window>) {
+        logi!("Starting {} for {}", self.backend.name(), self.task.proc_type);
+        match self.backend.start(&self.task) {
+            Ok(()) => {
+                if let Some(watchdog) = self.watchdog.as_mut() {
+                    watchdog.reset();
+                }
+                self.player = Some(Player {
+                    started: Instant::now(),
+                    window
+                });
+            },
+            Err(e) => {
+                self.backoff.record_exit(Duration::ZERO);
+                loge!("Could not start player: {}", e);
+                self.last_problem = Some(format!("could not start: {}", e));
+            }
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if self.player.take().is_some() {
+            self.backend.stop();
+        }
+    }
+
+    pub fn status(&mut self) -> Status {
+        let state = if self.player.is_some() {
+            PlaybackState::Playing
+        } else if self.finished {
+            PlaybackState::Finished
+        } else if !self.backoff.ready() {
+            PlaybackState::Restarting
+        } else {
+            PlaybackState::Idle
+        };
+        let source = match self.task.proc_type {
+            ProcType::Web => self.task.url.clone(),
+            _ => self.task.file.display().to_string()
+        };
+        Status {
+            updated: Local::now().to_rfc3339(),
+            state,
+            proc_type: self.task.proc_type.to_string(),
+            player: self.backend.name().to_string(),
+            source,
+            pid: self.player.as_ref().and(self.backend.pid()),
+            running_secs: self.player.as_ref().map(|player| player.started.elapsed().as_secs()),
+            restarts: self.restarts,
+            last_problem: self.last_problem.clone()
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Loads the saved task, following the media to wherever its drive is mounted now.
+fn load_resolved(env_dir_path: &Path) -> Result<Task, Box<dyn Error>> {
+    let mut task = load_task(env_dir_path)?;
+    if task.file.as_os_str().is_empty() || task.proc_type == ProcType::Web {
+        return Ok(task);
+    }
+    let mounted_drives = identify_mounted_drives(&SystemRunner).unwrap_or_else(|e| {
+        logw!("No storage devices identified, Error: {}", e);
+        Vec::new()
+    });
+    match resolve_media(&task.file, &task.uuid, &media_root(), &mounted_drives) {
+        Resolution::Found(_) => {},
+        Resolution::Relocated { from, to } => {
+            logi!("Media moved from {} to {}", from.display(), to.display());
+            task.file = to;
+        },
+        Resolution::Missing(path) => logw!("Media not found: {}", path.display())
+    }
+    Ok(task)
+}
+
+/// Runs the saved configuration until the process is stopped. The config is reloaded whenever
+/// the env file changes.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let env_dir_path = env_file_path();
+    let mut config_modified = modified(&env_dir_path);
+    let mut supervisor = Supervisor::new(load_resolved(&env_dir_path)?, read_model());
+    let mut last_state = None;
+    let mut last_heartbeat: Option<Instant> = None;
+
+    loop {
+        let current_modified = modified(&env_dir_path);
+        if current_modified != config_modified {
+            logi!("Configuration changed, reloading");
+            config_modified = current_modified;
+            // a config that cannot be read leaves the previous one playing
+            match load_resolved(&env_dir_path) {
+                Ok(task) => {
+                    supervisor.stop();
+                    supervisor = Supervisor::new(task, read_model());
+                },
+                Err(e) => loge!("Could not reload the configuration, keeping the previous one: {}", e)
+            }
+        }
+
+        let now = Local::now();
+        supervisor.tick(now.weekday().num_days_from_monday() as usize, now.num_seconds_from_midnight());
+
+        let status = supervisor.status();
+        let due = last_heartbeat.is_none_or(|heartbeat| heartbeat.elapsed() >= HEARTBEAT_INTERVAL);
+        if due || last_state.as_ref() != Some(&status.state) {
+            if let Err(e) = write_status(&status) {
+                loge!("Could not write status file: {}", e);
+            }
+            last_state = Some(status.state);
+            last_heartbeat = Some(Instant::now());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::mock::{
+        MockPlayer,
+        MockCall
+    };
+    use crate::player::backend::tests::task;
+    use crate::Weekday;
+
+    const TEN: u32 = 10 * 3600;
+
+    // plays on Monday from 10:00 to 11:00
+    fn scheduled_task(proc_type: ProcType, auto_loop: Autoloop) -> Task {
+        let mut task = task(proc_type, auto_loop, AdvancedSchedule::Yes);
+        task.timings = vec![Weekday::Monday(vec![(String::from("10:00:00"), String::from("11:00:00"))])];
+        task
+    }
+
+    fn supervisor(task: Task) -> (Supervisor, MockPlayer) {
+        let mock = MockPlayer::new();
+        (Supervisor::with_backend(task, Box::new(mock.clone())), mock)
+    }
+
+    #[test]
+    fn check_backoff_delay() {
+        let mut backoff = Backoff::new();
+        assert_eq!(backoff.delay(), Duration::from_secs(1));
+        assert_eq!(backoff.record_exit(Duration::ZERO), Duration::from_secs(1));
+        assert_eq!(backoff.record_exit(Duration::ZERO), Duration::from_secs(2));
+        assert_eq!(backoff.delay(), Duration::from_secs(4));
+        for _ in 0..10 {
+            backoff.record_exit(Duration::ZERO);
+        }
+        assert_eq!(backoff.delay(), BACKOFF_MAX);
+
+        // a long run resets the delay
+        assert_eq!(backoff.record_exit(STABLE_RUN), Duration::from_secs(1));
+        assert!(!backoff.ready());
+    }
+
+    #[test]
+    fn check_finishes_cleanly() {
+        let supervisor = Supervisor::new(task(ProcType::Video, Autoloop::No, AdvancedSchedule::Yes), Model::Pro);
+        assert!(supervisor.finishes_cleanly());
+        let supervisor = Supervisor::new(task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::Yes), Model::Pro);
+        assert!(!supervisor.finishes_cleanly());
+        let supervisor = Supervisor::new(task(ProcType::Image, Autoloop::No, AdvancedSchedule::Yes), Model::Pro);
+        assert!(!supervisor.finishes_cleanly());
+        let supervisor = Supervisor::new(task(ProcType::Executable, Autoloop::Yes, AdvancedSchedule::No), Model::Pro);
+        assert!(supervisor.finishes_cleanly());
+    }
+
+    #[test]
+    fn check_scheduled_start_stop() {
+        let (mut supervisor, mock) = supervisor(scheduled_task(ProcType::Video, Autoloop::Yes));
+
+        // nothing plays before the window or on other days
+        supervisor.tick(0, TEN - 1);
+        supervisor.tick(1, TEN);
+        assert_eq!(mock.calls(), vec![]);
+
+        supervisor.tick(0, TEN);
+        supervisor.tick(0, TEN + 60);
+        assert_eq!(mock.calls(), vec![MockCall::Start(ProcType::Video)]);
+
+        // leaving the window stops the player once
+        supervisor.tick(0, TEN + 3600);
+        supervisor.tick(0, TEN + 3660);
+        assert_eq!(mock.calls(), vec![MockCall::Start(ProcType::Video), MockCall::Stop]);
+    }
+
+    #[test]
+    fn check_crash_backoff() {
+        let (mut supervisor, mock) = supervisor(task(ProcType::Image, Autoloop::No, AdvancedSchedule::No));
+        supervisor.tick(0, TEN);
+        mock.exit(Some(1));
+        mock.clear();
+
+        // the crash is noticed but the restart waits for the backoff
+        supervisor.tick(0, TEN);
+        assert_eq!(mock.calls(), vec![]);
+        supervisor.backoff.retry_at = Some(Instant::now());
+        supervisor.tick(0, TEN);
+        assert_eq!(mock.calls(), vec![MockCall::Start(ProcType::Image)]);
+    }
+
+    #[test]
+    fn check_clean_finish() {
+        let (mut supervisor, mock) = supervisor(scheduled_task(ProcType::Audio, Autoloop::No));
+        supervisor.tick(0, TEN);
+        mock.exit(Some(0));
+        mock.clear();
+
+        // unlooped media is not replayed in the same window
+        supervisor.tick(0, TEN + 60);
+        supervisor.tick(0, TEN + 120);
+        assert_eq!(mock.calls(), vec![]);
+        assert_eq!(supervisor.status().state, PlaybackState::Finished);
+    }
+
+    #[test]
+    fn check_frozen_output() {
+        let (mut supervisor, mock) = supervisor(task(ProcType::Video, Autoloop::Yes, AdvancedSchedule::No));
+        supervisor.watchdog = Some(Watchdog::new(Duration::ZERO));
+        mock.set_position(Some(3.0));
+        supervisor.tick(0, TEN);
+        supervisor.tick(0, TEN);
+        assert_eq!(supervisor.status().state, PlaybackState::Playing);
+
+        // the position has not moved since the last tick
+        supervisor.tick(0, TEN);
+        assert_eq!(mock.calls(), vec![MockCall::Start(ProcType::Video), MockCall::Stop]);
+        let status = supervisor.status();
+        assert_eq!(status.state, PlaybackState::Restarting);
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.last_problem, Some(String::from("hung (output frozen)")));
+    }
+
+    #[test]
+    fn check_failed_start() {
+        let (mut supervisor, mock) = supervisor(task(ProcType::Web, Autoloop::No, AdvancedSchedule::No));
+        mock.set_fail_start(true);
+        supervisor.tick(0, TEN);
+        supervisor.tick(0, TEN);
+        assert_eq!(mock.calls(), vec![MockCall::Start(ProcType::Web)]);
+        assert!(!supervisor.backoff.ready());
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    error::Error,
+    fs,
+    io::{
+        BufRead,
+        BufReader,
+        Read
+    },
+    path::{
+        Path,
+        PathBuf
+    },
+    process::{
+        Child,
+        Command,
+        Stdio
+    },
+    sync::mpsc::{
+        self,
+        Receiver,
+        TryRecvError
+    },
+    thread::{
+        self,
+        JoinHandle
+    }
+};
+use log::{
+    info,
+    warn,
+    error
+};
+use crate::{
+    logi,
+    logw,
+    loge
+};
+
+use crate::{
+    Model,
+    ProcType,
+    local_media_dir
+};
+use crate::tr;
+use crate::library::unused_name;
+use crate::probe::MediaInfo;
+use crate::capabilities::display_size;
+
+// leave headroom under the model's limit for bitrate peaks
+const BIT_RATE_SHARE: f64 = 0.8;
+const AUDIO_BIT_RATE: &str = "192k";
+
+/// The ffmpeg arguments that convert a file into something the model can play.
+#[derive(Debug, PartialEq)]
+pub struct TranscodePlan {
+    pub output: PathBuf,
+    // what ffmpeg writes to, which is renamed to the output once it has finished
+    pub partial: PathBuf,
+    pub args: Vec<String>,
+    pub duration: Option<f64>
+}
+
+fn output_name(source: &Path, model: &Model, extension: &str) -> String {
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("converted");
+    format!("{}.{}.{}", stem, model.as_ref().to_lowercase(), extension)
+}
+
+/// Converted files go next to the source so they travel with it. Read-only drives fall back to
+/// local storage. A file that is already there, such as an earlier conversion, is never replaced:
+/// the output gets a number added instead.
+pub fn output_path(source: &Path, model: &Model, extension: &str) -> PathBuf {
+    let name = output_name(source, model, extension);
+    if let Some(dir) = source.parent() {
+        // try writing a file of our own, as a file with the output's name may already be there
+        let probe = dir.join(format!(".mediatimer-write-test-{}", std::process::id()));
+        if fs::OpenOptions::new().write(true).create_new(true).open(&probe).is_ok() {
+            let _ = fs::remove_file(&probe);
+            return dir.join(unused_name(dir, &name, false));
+        }
+    }
+    let dir = local_media_dir();
+    dir.join(unused_name(&dir, &name, false))
+}
+
+// hidden, so the player and file browsers never pick up a half written file, and keeping the
+// extension that ffmpeg chooses the format by
+fn partial_path(output: &Path) -> PathBuf {
+    let stem = output.file_stem().and_then(|s| s.to_str()).unwrap_or("converted");
+    let name = match output.extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!(".{}.partial.{}", stem, extension),
+        None => format!(".{}.partial", stem)
+    };
+    output.with_file_name(name)
+}
+
+pub fn plan(source: &Path, output: PathBuf, info: &MediaInfo, proc_type: &ProcType, model: &Model) -> TranscodePlan {
+    let capabilities = model.capabilities();
+    let mut args: Vec<String> = ["-y", "-nostdin", "-loglevel", "error", "-progress", "pipe:1", "-nostats", "-i"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    args.push(source.display().to_string());
+
+    if *proc_type == ProcType::Video {
+        let (width, height) = info.video.as_ref().map_or((0, 0), display_size);
+        // ffmpeg rotates the picture before filtering, so fit the box to the shown orientation
+        let (box_width, box_height) = if height > width {
+            (capabilities.max_height, capabilities.max_width)
+        } else {
+            (capabilities.max_width, capabilities.max_height)
+        };
+        let mut filter = format!("scale=w={}:h={}:force_original_aspect_ratio=decrease:force_divisible_by=2", box_width, box_height);
+
+        let short_side = width.min(height).min(capabilities.max_height);
+        let max_fps = capabilities.frame_rates.iter()
+            .find(|(max_height, _)| short_side <= *max_height)
+            .map(|(_, max_fps)| *max_fps);
+        let frame_rate = info.video.as_ref().and_then(|v| v.frame_rate);
+        if let (Some(max_fps), Some(frame_rate)) = (max_fps, frame_rate) && frame_rate > max_fps + 0.01 {
+            filter.push_str(&format!(",fps={}", max_fps));
+        }
+
+        let max_rate = (capabilities.max_bit_rate as f64 * BIT_RATE_SHARE) as u64;
+        args.extend([
+            "-vf", &filter,
+            "-c:v", "libx264", "-profile:v", "high", "-pix_fmt", "yuv420p",
+            "-preset", "veryfast", "-crf", "20",
+            "-maxrate", &max_rate.to_string(), "-bufsize", &(max_rate * 2).to_string(),
+            "-movflags", "+faststart"
+        ].iter().map(|a| a.to_string()));
+    } else {
+        args.push(String::from("-vn"));
+    }
+
+    if let Some(audio) = info.audio.as_ref() {
+        let channels = audio.channels.clamp(1, capabilities.max_audio_channels);
+        args.extend(["-c:a", "aac", "-b:a", AUDIO_BIT_RATE, "-ac", &channels.to_string()].iter().map(|a| a.to_string()));
+    } else {
+        args.push(String::from("-an"));
+    }
+
+    let partial = partial_path(&output);
+    // -y only ever replaces a partial file left by an interrupted conversion
+    args.push(partial.display().to_string());
+    TranscodePlan {
+        output,
+        partial,
+        args,
+        duration: info.duration
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ProgressLine {
+    // seconds of output written so far
+    Time(f64),
+    End
+}
+
+fn parse_progress_line(line: &str) -> Option<ProgressLine> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        // out_time_ms is also in microseconds
+        "out_time_us" | "out_time_ms" => value.parse::<u64>().ok().map(|us| ProgressLine::Time(us as f64 / 1_000_000.0)),
+        "progress" if value == "end" => Some(ProgressLine::End),
+        _ => None
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TranscodeState {
+    // fraction complete, when the length of the source is known
+    Running(Option<f64>),
+    Finished(PathBuf),
+    Failed(String)
+}
+
+/// An ffmpeg conversion running in the background.
+pub struct TranscodeJob {
+    child: Child,
+    updates: Receiver<ProgressLine>,
+    // reads ffmpeg's progress until it closes its output
+    reader: Option<JoinHandle<()>>,
+    // collects ffmpeg's errors, so a long error output cannot fill the pipe and stall it
+    errors: Option<JoinHandle<String>>,
+    output: PathBuf,
+    partial: PathBuf,
+    duration: Option<f64>,
+    fraction: Option<f64>
+}
+
+impl TranscodeJob {
+    pub fn start(plan: TranscodePlan) -> Result<Self, Box<dyn Error>> {
+        if let Some(dir) = plan.output.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        logi!("Converting to {}", plan.output.display());
+        let mut child = Command::new("ffmpeg")
+            .args(&plan.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = child.stdout.take().ok_or("ffmpeg output unavailable")?;
+        let mut stderr = child.stderr.take().ok_or("ffmpeg output unavailable")?;
+        let (sender, updates) = mpsc::channel();
+        let reader = thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if let Some(update) = parse_progress_line(&line) && sender.send(update).is_err() {
+                    break;
+                }
+            }
+        });
+        let errors = thread::spawn(move || {
+            let mut errors = String::new();
+            let _ = stderr.read_to_string(&mut errors);
+            errors
+        });
+
+        Ok(Self {
+            child,
+            updates,
+            reader: Some(reader),
+            errors: Some(errors),
+            output: plan.output,
+            partial: plan.partial,
+            duration: plan.duration.filter(|d| *d > 0.0),
+            fraction: None
+        })
+    }
+
+    fn read_progress(&mut self) {
+        loop {
+            match self.updates.try_recv() {
+                Ok(ProgressLine::Time(seconds)) => {
+                    self.fraction = self.duration.map(|duration| (seconds / duration).clamp(0.0, 1.0));
+                },
+                Ok(ProgressLine::End) => self.fraction = Some(1.0),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break
+            }
+        }
+    }
+
+    pub fn poll(&mut self) -> TranscodeState {
+        self.read_progress();
+        match self.child.try_wait() {
+            Ok(None) => TranscodeState::Running(self.fraction),
+            Ok(Some(status)) => {
+                // ffmpeg may have written its last lines after they were read above
+                if let Some(reader) = self.reader.take() {
+                    let _ = reader.join();
+                }
+                self.read_progress();
+                let reason = self.errors.take()
+                    .and_then(|errors| errors.join().ok())
+                    .unwrap_or_default();
+                if status.success() {
+                    return self.finish();
+                }
+                let _ = fs::remove_file(&self.partial);
+                loge!("Conversion failed ({}): {}", status, reason.trim());
+                TranscodeState::Failed(reason.lines().last().unwrap_or("ffmpeg stopped unexpectedly").to_string())
+            },
+            Err(e) => TranscodeState::Failed(e.to_string())
+        }
+    }
+
+    // moves the finished file into place, unless something has taken its name in the meantime
+    fn finish(&self) -> TranscodeState {
+        let moved = if self.output.exists() {
+            Err(tr!("library.exists", path = self.output.display()))
+        } else {
+            fs::rename(&self.partial, &self.output).map_err(|e| e.to_string())
+        };
+        match moved {
+            Ok(()) => {
+                logi!("Conversion finished");
+                TranscodeState::Finished(self.output.clone())
+            },
+            Err(e) => {
+                let _ = fs::remove_file(&self.partial);
+                loge!("Could not move the converted file into place: {}", e);
+                TranscodeState::Failed(e)
+            }
+        }
+    }
+
+    /// Stops ffmpeg and removes the partly written file.
+    pub fn cancel(mut self) {
+        logw!("Conversion cancelled");
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_file(&self.partial);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::{
+        VideoStream,
+        AudioStream
+    };
+
+    fn info(width: u32, height: u32, frame_rate: f64, channels: u32) -> MediaInfo {
+        MediaInfo {
+            container: String::from("mov,mp4,m4a,3gp,3g2,mj2"),
+            duration: Some(120.0),
+            bit_rate: Some(50_000_000),
+            video: Some(VideoStream {
+                codec: String::from("hevc"),
+                profile: Some(String::from("Main 10")),
+                pix_fmt: Some(String::from("yuv420p10le")),
+                width,
+                height,
+                frame_rate: Some(frame_rate),
+                rotation: 0
+            }),
+            audio: Some(AudioStream {
+                codec: String::from("eac3"),
+                channels,
+                sample_rate: Some(48000)
+            })
+        }
+    }
+
+    fn arg_after<'a>(plan: &'a TranscodePlan, flag: &str) -> Option<&'a str> {
+        let index = plan.args.iter().position(|a| a == flag)?;
+        plan.args.get(index + 1).map(|a| a.as_str())
+    }
+
+    #[test]
+    fn check_video_plan() {
+        let source = Path::new("/media/user/USB/clip.mov");
+        let output = PathBuf::from("/media/user/USB/clip.eco.mp4");
+        let plan = plan(source, output.clone(), &info(3840, 2160, 59.94, 6), &ProcType::Video, &Model::Eco);
+        assert_eq!(plan.partial, PathBuf::from("/media/user/USB/.clip.eco.partial.mp4"));
+        assert_eq!(plan.args.last(), Some(&plan.partial.display().to_string()));
+        assert_eq!(arg_after(&plan, "-i"), Some("/media/user/USB/clip.mov"));
+        assert_eq!(arg_after(&plan, "-vf"), Some("scale=w=1920:h=1080:force_original_aspect_ratio=decrease:force_divisible_by=2,fps=30"));
+        assert_eq!(arg_after(&plan, "-c:v"), Some("libx264"));
+        assert_eq!(arg_after(&plan, "-maxrate"), Some("16000000"));
+        assert_eq!(arg_after(&plan, "-ac"), Some("2"));
+        assert_eq!(plan.duration, Some(120.0));
+
+        // portrait video keeps its orientation and 720p60 is allowed
+        let plan = super::plan(source, output, &info(720, 1280, 60.0, 2), &ProcType::Video, &Model::Eco);
+        assert_eq!(arg_after(&plan, "-vf"), Some("scale=w=1080:h=1920:force_original_aspect_ratio=decrease:force_divisible_by=2"));
+    }
+
+    #[test]
+    fn check_audio_plan() {
+        let mut audio = info(0, 0, 0.0, 8);
+        audio.video = None;
+        let plan = plan(Path::new("/tmp/song.wma"), PathBuf::from("/tmp/song.standard.m4a"), &audio, &ProcType::Audio, &Model::Standard);
+        assert!(plan.args.contains(&String::from("-vn")));
+        assert!(!plan.args.contains(&String::from("-c:v")));
+        assert_eq!(arg_after(&plan, "-ac"), Some("6"));
+    }
+
+    #[test]
+    fn check_output_name() {
+        assert_eq!(output_name(Path::new("/media/user/USB/clip.mov"), &Model::Pro, "mp4"), "clip.pro.mp4");
+    }
+
+    #[test]
+    fn check_output_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("clip.mov");
+        assert_eq!(output_path(&source, &Model::Pro, "mp4"), dir.path().join("clip.pro.mp4"));
+        // an earlier conversion is kept
+        fs::write(dir.path().join("clip.pro.mp4"), "earlier").unwrap();
+        assert_eq!(output_path(&source, &Model::Pro, "mp4"), dir.path().join("clip.pro (2).mp4"));
+        assert_eq!(fs::read_to_string(dir.path().join("clip.pro.mp4")).unwrap(), "earlier");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(partial_path(Path::new("/tmp/song.standard.m4a")), PathBuf::from("/tmp/.song.standard.partial.m4a"));
+    }
+
+    #[test]
+    fn check_parse_progress_line() {
+        assert_eq!(parse_progress_line("out_time_us=1500000"), Some(ProgressLine::Time(1.5)));
+        assert_eq!(parse_progress_line("out_time_ms=3000000"), Some(ProgressLine::Time(3.0)));
+        assert_eq!(parse_progress_line("progress=end"), Some(ProgressLine::End));
+        assert_eq!(parse_progress_line("progress=continue"), None);
+        assert_eq!(parse_progress_line("out_time_us=N/A"), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    time::{
+        Duration,
+        Instant
+    }
+};
+use strum::Display;
+
+use crate::{
+    Task,
+    ProcType
+};
+
+// how long a player can go without any sign of progress
+pub const HANG_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Display, PartialEq)]
+pub enum Hang {
+    #[strum(to_string = "no CPU activity")]
+    NoActivity,
+    #[strum(to_string = "output frozen")]
+    Frozen
+}
+
+/// Notices a player that is still running but no longer playing anything. It is fed samples of
+/// the player's CPU time and, where the backend can report it, the playback position; a sample
+/// that does not change for longer than the timeout is treated as a hang.
+pub struct Watchdog {
+    timeout: Duration,
+    cpu: Option<(u64, Instant)>,
+    position: Option<(f64, Instant)>
+}
+
+impl Watchdog {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            cpu: None,
+            position: None
+        }
+    }
+
+    /// Returns a watchdog for tasks that keep the player busy. Still images, web pages and
+    /// executables can legitimately sit idle, so they are only watched for exits.
+    pub fn for_task(task: &Task) -> Option<Self> {
+        match task.proc_type {
+            ProcType::Video | ProcType::Audio => Some(Self::new(HANG_TIMEOUT)),
+            ProcType::Slideshow => {
+                let delay = Duration::from_secs(task.slide_delay as u64).saturating_mul(3);
+                Some(Self::new(HANG_TIMEOUT.max(delay)))
+            },
+            _ => None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.cpu = None;
+        self.position = None;
+    }
+
+    pub fn observe(&mut self, now: Instant, cpu: Option<u64>, position: Option<f64>) -> Option<Hang> {
+        if let Some(ticks) = cpu {
+            match self.cpu {
+                Some((last, since)) if last == ticks => {
+                    if now.duration_since(since) >= self.timeout {
+                        return Some(Hang::NoActivity);
+                    }
+                },
+                _ => self.cpu = Some((ticks, now))
+            }
+        }
+        if let Some(seconds) = position {
+            match self.position {
+                Some((last, since)) if last == seconds => {
+                    if now.duration_since(since) >= self.timeout {
+                        return Some(Hang::Frozen);
+                    }
+                },
+                _ => self.position = Some((seconds, now))
+            }
+        }
+        None
+    }
+}
+
+/// Total user and system CPU time of a process, in clock ticks.
+pub fn cpu_ticks(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    parse_cpu_ticks(&stat)
+}
+
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    // the command name can contain spaces, so count fields from the closing bracket
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_cpu_ticks() {
+        let stat = "1234 (ff play) S 1 1234 1234 0 -1 4194304 500 0 0 0 150 25 0 0 20 0 4 0 100 0 0";
+        assert_eq!(parse_cpu_ticks(stat), Some(175));
+        assert_eq!(parse_cpu_ticks("1234 (ffplay) S 1"), None);
+    }
+
+    #[test]
+    fn check_watchdog() {
+        let start = Instant::now();
+        let mut watchdog = Watchdog::new(Duration::from_secs(30));
+        assert_eq!(watchdog.observe(start, Some(10), None), None);
+        assert_eq!(watchdog.observe(start + Duration::from_secs(20), Some(10), None), None);
+        // activity restarts the timeout
+        assert_eq!(watchdog.observe(start + Duration::from_secs(25), Some(11), None), None);
+        assert_eq!(watchdog.observe(start + Duration::from_secs(50), Some(11), None), None);
+        assert_eq!(watchdog.observe(start + Duration::from_secs(55), Some(11), None), Some(Hang::NoActivity));
+
+        // busy but the position is stuck
+        watchdog.reset();
+        assert_eq!(watchdog.observe(start, Some(1), Some(4.0)), None);
+        assert_eq!(watchdog.observe(start + Duration::from_secs(40), Some(2), Some(4.0)), Some(Hang::Frozen));
+    }
+}
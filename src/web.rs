@@ -1,9 +1,9 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
     layout::{Constraint, Layout, Rect, Position},
     style::{
-        Color, Stylize, Style, Modifier
+        Stylize, Style, Modifier
     },
     symbols,
     text::{Line},
@@ -21,7 +21,8 @@ use std::{
     },
     error::Error,
     io::{BufRead, BufReader},
-    fs
+    fs,
+    time::Duration
 };
 
 use log::{
@@ -31,6 +32,7 @@ use crate::{
     logi,
     loge,
     logw,
+    tr
 };
 
 /*
@@ -46,17 +48,34 @@ use wl_clipboard_rs::{
 */
 
 use crate::areas;
+use crate::mouse::{
+    Click,
+    Footer,
+    Targets,
+    explorer_rows,
+    explorer_state,
+    is_click
+};
+use crate::keymap::{
+    Action,
+    Keymap,
+    HELP_KEYS
+};
+use crate::mount::Drive;
+use crate::hotplug::{
+    DriveMonitor,
+    Toast,
+    browsed_drive,
+    update_explorer
+};
+use crate::drives::DrivesWidget;
+use crate::wizard::{
+    Nav,
+    Step
+};
 use regex::Regex;
 
-use crate::styles::{
-    ITEM_HEADER_STYLE,
-    NORMAL_ROW_BG,
-    ALT_ROW_BG_COLOR,
-    SELECTED_STYLE,
-    TEXT_FG_COLOR,
-    TEXT_DIR_COLOR,
-    FOOTER_STYLE
-};
+use crate::styles::theme;
 
 type FileSelect = PathBuf;
 use ratatui_explorer::{FileExplorer, Theme};
@@ -83,9 +102,9 @@ enum Menu {
 impl Menu {
     fn as_vec_of_str(&self) -> Vec<MenuItem> {
         vec![
-            MenuItem::from("Add"), 
-            MenuItem::from("Import"),
-            MenuItem::from("Exit")
+            MenuItem::from(tr!("web.menu_add")), 
+            MenuItem::from(tr!("web.menu_import")),
+            MenuItem::from(tr!("web.menu_exit"))
         ]
     }
 }
@@ -120,7 +139,7 @@ impl MenuItem {
 
 impl From<&MenuItem> for ListItem<'_> {
     fn from(value: &MenuItem) -> Self {
-        let line = Line::styled(value.op_item.clone(), TEXT_FG_COLOR);
+        let line = Line::styled(value.op_item.clone(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -143,7 +162,7 @@ impl ExitList {
         let mut state = ListState::default();
         state.select_first();
         ExitList {
-            exit_list_items: vec![ExitItem::new("Yes"), ExitItem::new("No")],
+            exit_list_items: vec![ExitItem::new(tr!("common.yes")), ExitItem::new(tr!("common.no"))],
             state
         }
     }
@@ -151,7 +170,7 @@ impl ExitList {
 
 impl From<&ExitItem> for ListItem<'_> {
     fn from(value: &ExitItem) -> Self {
-        let line = Line::styled(value.item.clone(), TEXT_FG_COLOR);
+        let line = Line::styled(value.item.clone(), theme().text_fg);
         ListItem::new(line)
     }
 }
@@ -165,6 +184,7 @@ pub struct WebWidget {
     file_explorer: FileExplorer,
     selected_file: FileSelect,
     should_exit: bool,
+    nav: Nav,
     current_screen: CurrentScreen,
     previous_screen: CurrentScreen,
     // selected weekdays and timings are indexes starting at 0
@@ -176,7 +196,12 @@ pub struct WebWidget {
     input_area: Rect,
     exit_list: ExitList,
     error_type: ErrorType,
-    mounted_drives: Vec<(PathBuf, String)>
+    mounted_drives: Vec<Drive>,
+    monitor: Option<DriveMonitor>,
+    toast: Option<Toast>,
+    drives_requested: bool,
+    targets: Targets,
+    help: bool
 }
 
 impl Default for WebWidget {
@@ -187,6 +212,7 @@ impl Default for WebWidget {
             file_explorer: FileExplorer::new().unwrap(),
             selected_file: PathBuf::new(),
             should_exit: false,
+            nav: Nav::Next,
             current_screen: CurrentScreen::Menu,
             previous_screen: CurrentScreen::Menu,
             url: String::new(),
@@ -197,12 +223,23 @@ impl Default for WebWidget {
             input_area: Rect::new(0,0,0,0),
             exit_list: ExitList::default(),
             error_type: ErrorType::Format,
-            mounted_drives: Vec::new()
+            mounted_drives: Vec::new(),
+            monitor: None,
+            toast: None,
+            drives_requested: false,
+            targets: Targets::default(),
+            help: false
         }
     }
 }
+/// Whether `input` looks like a web address, with or without the http(s):// prefix.
+pub fn url_format_correct(input: &str) -> Result<bool, Box<dyn Error>> {
+    let re = Regex::new(r"^(https?://)?([\da-z\.-]+)\.([a-z\.]{2,6})([\/\w \.-]*)*\/?$")?;
+    Ok(re.is_match(input))
+}
+
 impl WebWidget {
-    pub fn new (new_url: String, mounted_drives: Vec<(PathBuf, String)>) -> Result<Self, Box<dyn Error>> {
+    pub fn new (new_url: String, mounted_drives: Vec<Drive>) -> Result<Self, Box<dyn Error>> {
         let file_explorer = FileExplorer::new()?;
         Ok(
             Self {
@@ -210,6 +247,7 @@ impl WebWidget {
                 file_explorer,
                 selected_file: Path::new("./").to_path_buf(),
                 should_exit: false,
+                nav: Nav::Next,
                 current_screen: CurrentScreen::Menu,
                 previous_screen: CurrentScreen::Menu,
                 url: new_url.clone(),
@@ -220,11 +258,16 @@ impl WebWidget {
                 input_area: Rect::new(0,0,0,0),
                 exit_list: ExitList::default(),
                 error_type: ErrorType::Format,
-                mounted_drives
+                mounted_drives,
+                monitor: Some(DriveMonitor::start()),
+                toast: None,
+                drives_requested: false,
+                targets: Targets::default(),
+                help: false
             }
         )
     }
-    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<String, Box< dyn Error>> {
+    pub fn run (mut self, terminal: &mut DefaultTerminal) -> Result<Step<String>, Box< dyn Error>> {
 
         let _file_explorer_init = self.setup_file_explorer()?;
         let _file_explorer_style_init = self.style_file_explorer();
@@ -241,37 +284,76 @@ impl WebWidget {
                 }
             })?;
 
-            let event = event::read()?;
-                if self.current_screen == CurrentScreen::Import {
+            self.update_drives()?;
+            // wake up now and then to notice drives being plugged in
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let event = match event::read()? {
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                event => Some(event)
+            };
+            if let Some(event) = event {
+                if self.current_screen == CurrentScreen::Import && !self.help {
                         let _ = self.file_explorer.handle(&event);
                 }
                 if let Event::Key(key) = event {
                     let _handle = self.handle_key(key)?;
                     //self.text_area.input(key);
                 }
+            }
+            if self.drives_requested {
+                self.drives_requested = false;
+                self.show_drives(terminal)?;
+            }
         }
-        Ok(self.url)
+        Ok(self.nav.with(self.url))
     }
     fn style_file_explorer(&mut self) {
         let theme = Theme::default()
             .add_default_title()
             .with_block(Block::default()
-                .title(Line::raw("Select File").centered())
+                .title(Line::raw(tr!("fileselect.explorer_title")).centered())
                 .borders(Borders::TOP)
                 .border_set(symbols::border::EMPTY)
-                .border_style(ITEM_HEADER_STYLE)
-                .bg(NORMAL_ROW_BG)
+                .border_style(theme().item_header)
+                .bg(theme().normal_row_bg)
                 .padding(Padding::horizontal(1))
                 )
-            .with_highlight_item_style(SELECTED_STYLE)
+            .with_highlight_item_style(theme().selected)
             .with_highlight_symbol("> ")
             .with_highlight_spacing(HighlightSpacing::Always)
-            .with_dir_style(Style::default().fg(TEXT_DIR_COLOR).add_modifier(Modifier::BOLD))
-            .with_highlight_dir_style(SELECTED_STYLE)
-            .with_item_style(Style::default().fg(TEXT_FG_COLOR));
+            .with_dir_style(Style::default().fg(theme().text_dir).add_modifier(Modifier::BOLD))
+            .with_highlight_dir_style(theme().selected)
+            .with_item_style(Style::default().fg(theme().text_fg));
         
         self.file_explorer.set_theme(theme)
     }
+    // pick up drives plugged in or pulled out since the last check
+    fn update_drives(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.monitor.as_ref().and_then(DriveMonitor::poll) {
+            Some(drives) => self.apply_drives(drives),
+            None => Ok(())
+        }
+    }
+
+    // open the drives screen on the drive being browsed
+    fn show_drives(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        let browsed = browsed_drive(&self.file_explorer, &self.mounted_drives);
+        let drives = DrivesWidget::new(self.mounted_drives.clone(), browsed).run(terminal)?;
+        self.apply_drives(drives)
+    }
+
+    fn apply_drives(&mut self, drives: Vec<Drive>) -> Result<(), Box<dyn Error>> {
+        let update = update_explorer(&mut self.file_explorer, &mut self.mounted_drives, drives)?;
+        if update.folder_gone {
+            self.setup_file_explorer()?;
+        }
+        if update.toast.is_some() {
+            self.toast = update.toast;
+        }
+        Ok(())
+    }
     fn setup_file_explorer(&mut self) -> Result<(), Box<dyn Error>> {
         let username = whoami::username();
 
@@ -291,8 +373,8 @@ impl WebWidget {
         } else if self.mounted_drives.len() > 1 && !username.is_empty() {
             let path_buf: PathBuf = ["/media/", &username].iter().collect();
             self.file_explorer.set_cwd(&path_buf)?;
-        } else if self.mounted_drives.len() == 1 {
-            self.file_explorer.set_cwd(self.mounted_drives[0].0.clone())?;
+        } else if let [drive] = self.mounted_drives.as_slice() && let Some(mountpoint) = &drive.mountpoint {
+            self.file_explorer.set_cwd(mountpoint)?;
         } else if !username.is_empty() {
             let username = whoami::username();
             let path_buf: PathBuf = ["/home/", &username].iter().collect();
@@ -302,21 +384,63 @@ impl WebWidget {
         }
         Ok(())
     }
+    // the keys of the current screen
+    fn keymap(&self) -> Keymap {
+        match self.current_screen {
+            CurrentScreen::Menu => Keymap::list()
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace], Action::Finish)
+                .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::Char('q')], Action::Quit),
+            // every other key is typed into the address, so only F1 opens the help
+            CurrentScreen::Add => Keymap::default()
+                .bind(&[KeyCode::Left], Action::CursorLeft)
+                .bind(&[KeyCode::Right], Action::CursorRight)
+                .bind(&[KeyCode::Backspace], Action::DeleteChar)
+                .bind(&[KeyCode::Enter], Action::Save)
+                .bind(&[KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::F(1)], Action::Help),
+            // any other key closes these
+            CurrentScreen::Message | CurrentScreen::Error => Keymap::default()
+                .bind(HELP_KEYS, Action::Help),
+            CurrentScreen::Import => Keymap::list()
+                .bind(&[KeyCode::Char('l'), KeyCode::Right], Action::Open)
+                .bind(&[KeyCode::Enter], Action::OpenOrChoose)
+                .bind(&[KeyCode::Char('e')], Action::Drives)
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace, KeyCode::Esc], Action::Back),
+            CurrentScreen::Exit => Keymap::list()
+                .bind(&[KeyCode::Char('l'), KeyCode::Right, KeyCode::Enter], Action::Choose)
+                .bind(&[KeyCode::Char('h'), KeyCode::Left, KeyCode::Backspace, KeyCode::Esc], Action::Back)
+                .bind(&[KeyCode::Char('m')], Action::Menu)
+        }
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
         if key.kind != KeyEventKind::Press {
             return Ok(());
         }
+        // any key closes the help
+        if self.help {
+            self.help = false;
+            return Ok(());
+        }
+        let action = self.keymap().action(key.code);
+        if action == Some(Action::Help) {
+            self.help = true;
+            return Ok(());
+        }
         match self.current_screen {
             CurrentScreen::Menu => {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.reverse_state(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Back) => self.leave(Nav::Back),
+                    Some(Action::Quit) => self.leave(Nav::Cancel),
+                    Some(Action::Finish) => self.reverse_state(),
+                    Some(Action::Next) => self.select_next(),
+                    Some(Action::Previous) => self.select_previous(),
+                    Some(Action::First) => self.select_first(),
+                    Some(Action::Last) => self.select_last(),
+
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.menu_op_list.state.selected() {
@@ -345,11 +469,11 @@ impl WebWidget {
                 }
             },
             CurrentScreen::Add => {
-                match key.code {
-                    KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Left => self.move_cursor_left(),
-                    KeyCode::Right => self.move_cursor_right(),
-                    KeyCode::Backspace => self.delete_char(),
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::CursorLeft) => self.move_cursor_left(),
+                    Some(Action::CursorRight) => self.move_cursor_right(),
+                    Some(Action::DeleteChar) => self.delete_char(),
                     /* TODO clipboard not working
                     KeyCode::Char('v') => {
                         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -370,8 +494,7 @@ impl WebWidget {
                         }
                     },
                     */
-                    KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                    KeyCode::Enter => {
+                    Some(Action::Save) => {
                         self.previous_screen = CurrentScreen::Add;
                         self.clean_input();
                         let url_format = self.url_format_correct()?;
@@ -381,12 +504,16 @@ impl WebWidget {
                             logi!("Url format incorrect");
                         } else {
                             self.url = self.input.clone();
-                            self.message_text = format!("Url selected: {}", &self.url);
+                            self.message_text = tr!("web.selected", url = &self.url);
                             self.current_screen = CurrentScreen::Message;
                             self.character_index = 0;
                         }
                     }
-                    _ => {}
+                    _ => {
+                        if let KeyCode::Char(to_insert) = key.code {
+                            self.enter_char(to_insert);
+                        }
+                    }
                 }
             },
            CurrentScreen::Error => {
@@ -407,9 +534,16 @@ impl WebWidget {
             },
             CurrentScreen::Import => {
                 let is_dir = self.file_explorer.current().is_dir();
-                match key.code {
-                    KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace | KeyCode::Esc => self.reverse_state(),
-                    KeyCode::Enter if !is_dir => {
+                match action {
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Drives) => self.drives_requested = true,
+                    // the file explorer moves the highlight and opens folders, these are only missing from it
+                    Some(Action::First) => self.file_explorer.set_selected_idx(0),
+                    Some(Action::Last) => {
+                        let last = self.file_explorer.files().len().saturating_sub(1);
+                        self.file_explorer.set_selected_idx(last);
+                    },
+                    Some(Action::OpenOrChoose) if !is_dir => {
                         let current_path_buf = self.file_explorer.current().path().to_path_buf();
                         self.selected_file = current_path_buf;
                         let file = fs::File::open(&self.selected_file)?;
@@ -431,7 +565,7 @@ impl WebWidget {
                             self.reverse_state();
                             self.character_index = 0;
                         }
-                        self.message_text = format!("Url imported: {}", &self.url);
+                        self.message_text = tr!("web.imported", url = &self.url);
                         self.current_screen = CurrentScreen::Message;
                         },
                     _ => {}
@@ -439,21 +573,19 @@ impl WebWidget {
 
             },
             CurrentScreen::Exit => {
-                match key.code {
-                    KeyCode::Char('m') => self.current_screen = CurrentScreen::Menu,
-                    KeyCode::Esc | KeyCode::Backspace => self.reverse_state(),
-                    KeyCode::Char('j') | KeyCode::Down => self.select_next(),
-                    KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
-                    KeyCode::Char('g') | KeyCode::Home => self.select_first(),
-                    KeyCode::Char('G') | KeyCode::End => self.select_last(),
-
-                    KeyCode::Char('h') | KeyCode::Left => self.reverse_state(),
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                match action {
+                    Some(Action::Menu) => self.current_screen = CurrentScreen::Menu,
+                    Some(Action::Back) => self.reverse_state(),
+                    Some(Action::Next) => self.select_next(),
+                    Some(Action::Previous) => self.select_previous(),
+                    Some(Action::First) => self.select_first(),
+                    Some(Action::Last) => self.select_last(),
+                    Some(Action::Choose) => {
                         // add code to select the list item
                         // render popup now using current selection
                         if let Some(i) = self.exit_list.state.selected() {
                             match self.exit_list.exit_list_items[i].item.as_str() {
-                                "Yes" => {
+                                item if item == tr!("common.yes") => {
                                     // compile the schedule here
                                     self.should_exit = true;
                                 },
@@ -468,15 +600,52 @@ impl WebWidget {
         }
         Ok(())
     }
+    // turns a click into the key it stands for, or highlights the row that was clicked
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Event> {
+        if self.help {
+            self.help = !is_click(&mouse);
+            return None;
+        }
+        let (state, rows) = match self.current_screen {
+            // any key closes these, so any click does too
+            CurrentScreen::Message | CurrentScreen::Error => {
+                return is_click(&mouse).then(|| Event::Key(KeyEvent::from(KeyCode::Enter)));
+            },
+            // the footer keys are typed into the address here
+            CurrentScreen::Add => return None,
+            CurrentScreen::Menu => (self.menu_op_list.state.clone(), self.menu_op_list.menu_ops.len()),
+            CurrentScreen::Exit => (self.exit_list.state.clone(), self.exit_list.exit_list_items.len()),
+            CurrentScreen::Import => (
+                explorer_state(&self.file_explorer, self.targets.list.height),
+                self.file_explorer.files().len()
+            )
+        };
+        match self.targets.click(mouse, &state, rows, self.footer().as_ref())? {
+            Click::Key(code) => Some(Event::Key(KeyEvent::from(code))),
+            Click::Select(i) => {
+                match self.current_screen {
+                    CurrentScreen::Menu => self.menu_op_list.state.select(Some(i)),
+                    CurrentScreen::Exit => self.exit_list.state.select(Some(i)),
+                    _ => self.file_explorer.set_selected_idx(i)
+                }
+                None
+            }
+        }
+    }
+
     fn clean_input(&mut self) {
         self.input = String::from(self.input.trim());
     }
     fn url_format_correct(&self) -> Result<bool, Box<dyn Error>> {
-        let re = Regex::new(r"^(https?://)?([\da-z\.-]+)\.([a-z\.]{2,6})([\/\w \.-]*)*\/?$")?;
-        Ok(re.is_match(&self.input))
+        url_format_correct(&self.input)
     }
 
     
+    fn leave(&mut self, nav: Nav) {
+        self.nav = nav;
+        self.should_exit = true;
+    }
+
     fn reverse_state(&mut self) {
         match self.current_screen {
             CurrentScreen::Menu => self.current_screen = CurrentScreen::Exit,
@@ -566,7 +735,7 @@ impl WebWidget {
 
     // rendering logic
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Web")
+        Paragraph::new(tr!("proctype.web"))
             .bold()
             .centered()
             .render(area, buf);
@@ -578,77 +747,94 @@ impl WebWidget {
         match self.current_screen {
             CurrentScreen::Menu => {
                 text = vec![ 
-                    Line::from("Select \"Add\" to type or paste a URL from the browser."),
-                    Line::from("Select \"Import\" to import a URL directly from a text file."),
-                    Line::from("Select \"Exit\" to leave this screen."),
+                    Line::from(tr!("web.menu_add_info")),
+                    Line::from(tr!("web.menu_import_info")),
+                    Line::from(tr!("web.menu_exit_info")),
                 ];
 
             },
             CurrentScreen::Add => {
                 text = vec![
-                    Line::from("Type the web URL or use Shift+Ctl+v to paste a URL from the browser. The URL must match the format provided in your browser and start with https://")
+                    Line::from(tr!("web.add_info"))
                 ];
             },
             CurrentScreen::Import => {
                 text = vec![
-                    Line::from("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.")
+                    Line::from(tr!("web.list_footer"))
                 ];
             },
             CurrentScreen::Exit => {
                 text = vec![
-                    Line::from("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.")
+                    Line::from(tr!("web.list_footer"))
                 ];
             },
             _ => {}
         }
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INFO").centered().style(FOOTER_STYLE))
+            .title(Line::raw(tr!("common.info")).centered().style(theme().footer))
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(text)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
-    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
-        let mut text = String::with_capacity(20);
-
+    // the key hints along the bottom of the screen
+    fn footer(&self) -> Option<Footer> {
+        let keymap = self.keymap();
         match self.current_screen {
-            CurrentScreen::Menu => {
-                text = String::from("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.");
-            },
-            CurrentScreen::Add => {
-                text = String::from("Type the web URL or use Shift+Ctl+v to paste a URL from the browser.");
-            },
-            CurrentScreen::Import => {
-                text = String::from("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.");
-            },
-            CurrentScreen::Exit => {
-                text = String::from("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, and ESC to exit.");
-            },
-            _ => {}
+            CurrentScreen::Menu => Some(Footer::default()
+                .hint(tr!("footer.move"))
+                .bound(&keymap, tr!("footer.finish"), KeyCode::Left, Action::Finish)
+                .bound(&keymap, tr!("footer.change"), KeyCode::Right, Action::Choose)
+                .hint(tr!("footer.top_bottom"))
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+                .bound(&keymap, tr!("footer.quit"), KeyCode::Char('q'), Action::Quit)
+                .bound(&keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help)),
+            CurrentScreen::Import => Some(Footer::default()
+                .hint(tr!("footer.move"))
+                .bound(&keymap, tr!("footer.open"), KeyCode::Right, Action::Open)
+                .hint(tr!("footer.top_bottom"))
+                .bound(&keymap, tr!("footer.back"), KeyCode::Esc, Action::Back)
+                .bound(&keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help)),
+            CurrentScreen::Exit => Some(Footer::default()
+                .hint(tr!("footer.move"))
+                .bound(&keymap, tr!("footer.change"), KeyCode::Right, Action::Choose)
+                .hint(tr!("footer.top_bottom"))
+                .bound(&keymap, tr!("footer.exit"), KeyCode::Esc, Action::Back)
+                .bound(&keymap, tr!("footer.help"), KeyCode::Char('?'), Action::Help)),
+            _ => None
         }
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let text = match (&self.current_screen, self.footer()) {
+            (CurrentScreen::Add, _) => Line::from(tr!("web.add_footer")),
+            (_, Some(footer)) => footer.line(),
+            (_, None) => Line::default()
+        };
 
-        Paragraph::new(text.as_str())
-            .style(FOOTER_STYLE)
+        Paragraph::new(text)
+            .style(theme().footer)
             .centered()
             .render(area, buf);
     }
 
     fn render_menu_op_list(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw("Select Task").centered())
+            .title(Line::raw(tr!("web.menu_title")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -657,7 +843,7 @@ impl WebWidget {
             .iter()
             .enumerate()
             .map(|(i, ops)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                     ListItem::from(ops).bg(color)
             })
             .collect();
@@ -665,7 +851,7 @@ impl WebWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -674,57 +860,59 @@ impl WebWidget {
     }
     fn render_add(&self, area: Rect, buf: &mut Buffer) {
        Paragraph::new(self.input.as_str()) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("Add Url").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("web.add_title")).centered())
            )
            .render(area, buf);
     }
 
    fn render_file_explorer(&mut self, area: Rect, buf: &mut Buffer) {
+        self.targets.list = explorer_rows(area);
         self.file_explorer.widget().render(area, buf);
     }
     fn render_file_explorer_selected_item(&self, area: Rect, buf: &mut Buffer) {
-            let text = vec![ 
-                Line::from("Select a text file containing a URL using our file explorer."),
-                Line::from("Use the arrow keys ⇅ to find the file you want to use."),
-                Line::from("Press ENTER to select the file."),
-                Line::from("To ascend a directory navigate to \"↑ Parent Folder ↑\" and press Enter"),
-                Line::from("USB sticks will show up automatically. Manually find them in the directory '/media'."),
+            let mut text = vec![ 
+                Line::from(tr!("web.import_instructions")),
+                Line::from(tr!("fileselect.file_instructions_2")),
+                Line::from(tr!("fileselect.file_instructions_3")),
+                Line::from(tr!("fileselect.parent_instructions")),
+                Line::from(tr!("fileselect.usb_instructions")),
             ];
+        text.extend(self.mounted_drives.iter().map(|drive| Line::from(drive.summary())));
+        if !self.mounted_drives.is_empty() {
+            text.push(Line::from(tr!("fileselect.eject_hint")));
+        }
 
         // show the list item's info under the list
         let block = Block::new()
-            .title(Line::raw("INSTRUCTIONS").centered())
+            .title(Line::raw(tr!("common.instructions")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(text)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
 
     fn render_file_explorer_header(&mut self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Select a file containing a URL")
+        Paragraph::new(tr!("web.import_header"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
     fn render_file_explorer_footer(&mut self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .style(FOOTER_STYLE)
-            .centered()
-            .render(area, buf);
+        WebWidget::render_footer(self, area, buf);
     }
 
 
@@ -732,13 +920,13 @@ impl WebWidget {
         // set the current input as the entry selected.
 
         Paragraph::new(Line::raw(&self.message_text)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("MESSAGE").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("common.message")).centered())
            )
            .render(area, buf);
 
@@ -746,17 +934,17 @@ impl WebWidget {
     fn render_error(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let message = match self.error_type {
-            ErrorType::Format => "Formating Error! Please check the URL format. Note that URLs must start with \"https://\".",
+            ErrorType::Format => tr!("web.format_error"),
         };
 
         Paragraph::new(Line::raw(message)) 
-           .fg(TEXT_FG_COLOR)
-           .bg(NORMAL_ROW_BG)
+           .fg(theme().text_fg)
+           .bg(theme().normal_row_bg)
            .wrap(Wrap {trim:false})
            .block(
                Block::bordered()
-               .style(ITEM_HEADER_STYLE)
-               .title(Line::raw("ERROR").centered())
+               .style(theme().item_header)
+               .title(Line::raw(tr!("common.error")).centered())
            )
            .render(area, buf);
 
@@ -764,11 +952,12 @@ impl WebWidget {
     fn render_exit(&mut self, area: Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         let block = Block::new()
-            .title(Line::raw("Ready to exit this stage?").centered())
+            .title(Line::raw(tr!("common.exit_question")).centered())
             .borders(Borders::ALL)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG);
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg);
+        self.targets.list = block.inner(area);
 
         // Iterate through all the timings in the weekday selected and stylise them
         let items: Vec<ListItem> = self 
@@ -777,7 +966,7 @@ impl WebWidget {
             .iter()
             .enumerate()
             .map(|(i, option)| {
-                let color = alternate_colors(i);
+                let color = theme().row_bg(i);
                 ListItem::from(option).bg(color)
             })
             .collect();
@@ -785,7 +974,7 @@ impl WebWidget {
         // create a list from all the items and highlight the currently selected one
         let list = List::new(items)
             .block(block)
-            .highlight_style(SELECTED_STYLE)
+            .highlight_style(theme().selected)
             .highlight_symbol("> ")
             .highlight_spacing(HighlightSpacing::Always);
         // we have to diferentiate this "render" from the render fn on self
@@ -796,11 +985,11 @@ impl WebWidget {
     fn render_background(&self, area:Rect, buf: &mut Buffer) {
         // set the current input as the entry selected.
         Block::new()
-            .title(Line::raw("URL setup").centered())
+            .title(Line::raw(tr!("web.background_title")).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .render(area, buf);
     }
     /*
@@ -820,14 +1009,14 @@ impl WebWidget {
             .title(Line::raw("INSTRUCTIONS").centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
-            .border_style(ITEM_HEADER_STYLE)
-            .bg(NORMAL_ROW_BG)
+            .border_style(theme().item_header)
+            .bg(theme().normal_row_bg)
             .padding(Padding::horizontal(1));
 
         // now render the item info
         Paragraph::new(info)
             .block(block)
-            .fg(TEXT_FG_COLOR)
+            .fg(theme().text_fg)
             .wrap(Wrap { trim: false })
             .render(area, buf);
     }
@@ -835,16 +1024,9 @@ impl WebWidget {
 
 }
 
-const fn alternate_colors(i: usize) -> Color {
-    if i.is_multiple_of(2) {
-        NORMAL_ROW_BG
-    } else {
-        ALT_ROW_BG_COLOR
-    }
-}
-
 impl Widget for &mut WebWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        self.targets.screen = area;
 
         let popup_area: Rect = areas::popup_area(area);
 
@@ -958,6 +1140,39 @@ impl Widget for &mut WebWidget {
 
             },
         }
+
+        if let Some(toast) = self.toast.as_ref().filter(|toast| !toast.expired()) {
+            let toast_area = Toast::area(area);
+            Clear.render(toast_area, buf);
+            toast.render(toast_area, buf);
+        }
+
+        if self.help {
+            let about = match self.current_screen {
+                CurrentScreen::Add => tr!("help.web_add"),
+                _ => tr!("help.web")
+            };
+            self.keymap().render_help(about, area, buf);
+        }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_footers() {
+        let mut widget = WebWidget::default();
+        for screen in [CurrentScreen::Menu, CurrentScreen::Import, CurrentScreen::Exit] {
+            widget.current_screen = screen;
+            // building the footer checks each hint against the keymap
+            assert!(widget.footer().is_some());
+        }
+        widget.current_screen = CurrentScreen::Import;
+        let line = widget.footer().unwrap().line().to_string();
+        assert!(line.contains(tr!("footer.open")));
+        assert!(!line.contains(tr!("footer.unselect")));
+    }
+}
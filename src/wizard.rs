@@ -0,0 +1,252 @@
+use ratatui::DefaultTerminal;
+use std::error::Error;
+
+use crate::{
+    Task,
+    Model,
+    ProcType,
+    Autoloop,
+    AdvancedSchedule,
+    env_file_path,
+    fresh_uuid,
+    load_task
+};
+use crate::mount::Drive;
+use crate::landing::LandingWidget;
+use crate::proctype::ProcTypeWidget;
+use crate::web::WebWidget;
+use crate::fileselect::FileSelectWidget;
+use crate::slideshow::SlideshowWidget;
+use crate::advanced_schedule::AdvancedScheduleWidget;
+use crate::schedule::timings::TimingsWidget;
+use crate::autoloop::AutoloopWidget;
+use crate::tr;
+use crate::review::{
+    Review,
+    ReviewWidget
+};
+
+/// How a wizard step was left.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Nav {
+    #[default]
+    Next,
+    Back,
+    Cancel
+}
+
+impl Nav {
+    pub fn with<T>(self, value: T) -> Step<T> {
+        match self {
+            Nav::Next => Step::Next(value),
+            Nav::Back => Step::Back(value),
+            Nav::Cancel => Step::Cancel
+        }
+    }
+}
+
+/// What a wizard step returns. Going back still hands over the value, so a step that is
+/// revisited shows what was chosen on it last time.
+#[derive(Debug, PartialEq)]
+pub enum Step<T> {
+    Next(T),
+    Back(T),
+    Cancel
+}
+
+/// The setup screens, in the order they are normally shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Screen {
+    ProcType,
+    Media,
+    Slideshow,
+    AdvancedSchedule,
+    Timings,
+    Autoloop
+}
+
+impl Screen {
+    fn title(self) -> &'static str {
+        match self {
+            Screen::ProcType => tr!("wizard.proc_type"),
+            Screen::Media => tr!("wizard.media"),
+            Screen::Slideshow => tr!("wizard.slideshow"),
+            Screen::AdvancedSchedule => tr!("wizard.advanced_schedule"),
+            Screen::Timings => tr!("wizard.timings"),
+            Screen::Autoloop => tr!("wizard.autoloop")
+        }
+    }
+}
+
+/// Where the wizard goes after `screen`, or None when it is the last one for `task`.
+fn next_screen(screen: Screen, task: &Task) -> Option<Screen> {
+    match screen {
+        Screen::ProcType => Some(Screen::Media),
+        Screen::Media if task.proc_type == ProcType::Slideshow => Some(Screen::Slideshow),
+        Screen::Media | Screen::Slideshow => Some(Screen::AdvancedSchedule),
+        Screen::AdvancedSchedule if task.advanced_schedule == AdvancedSchedule::Yes => Some(Screen::Timings),
+        Screen::AdvancedSchedule => None,
+        // looping only applies to media that plays for a set time
+        Screen::Timings if matches!(task.proc_type, ProcType::Video | ProcType::Audio) => Some(Screen::Autoloop),
+        Screen::Timings | Screen::Autoloop => None
+    }
+}
+
+/// Runs the setup screens one after another, starting from the saved configuration. ESC on a
+/// screen goes back to the previous one with the choices made so far kept. The last screen
+/// leads to a review of everything chosen before it is saved.
+pub struct Wizard {
+    model: Model,
+    username: String,
+    mounted_drives: Vec<Drive>,
+    task: Task,
+    // the screens that led to the current one
+    history: Vec<Screen>
+}
+
+impl Wizard {
+    pub(crate) fn new(model: Model, username: String, mounted_drives: Vec<Drive>, task: Task) -> Self {
+        Self {
+            model,
+            username,
+            mounted_drives,
+            task,
+            history: Vec::new()
+        }
+    }
+
+    /// Returns the configured task, or None when setup was quit without saving.
+    pub(crate) fn run(mut self, terminal: &mut DefaultTerminal) -> Result<Option<Task>, Box<dyn Error>> {
+        let mut screen = Screen::ProcType;
+        loop {
+            let nav = self.show(screen, terminal)?;
+            match nav {
+                Nav::Cancel => return Ok(None),
+                Nav::Back => match self.history.pop() {
+                    Some(previous) => screen = previous,
+                    None => LandingWidget::new(self.model.clone()).run(terminal)?
+                },
+                Nav::Next => match next_screen(screen, &self.task) {
+                    Some(next) => {
+                        self.history.push(screen);
+                        screen = next;
+                    },
+                    None => {
+                        self.history.push(screen);
+                        match self.review(terminal)? {
+                            Review::Apply => return Ok(Some(self.task)),
+                            Review::Cancel => return Ok(None),
+                            Review::Edit(index) => {
+                                screen = self.history[index];
+                                self.history.truncate(index);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn review(&self, terminal: &mut DefaultTerminal) -> Result<Review, Box<dyn Error>> {
+        let saved = saved_task();
+        let steps = self.history.iter().map(|screen| screen.title()).collect();
+        ReviewWidget::new(&self.task, saved.as_ref(), steps).run(terminal)
+    }
+
+    fn show(&mut self, screen: Screen, terminal: &mut DefaultTerminal) -> Result<Nav, Box<dyn Error>> {
+        let task = &mut self.task;
+        let nav = match screen {
+            Screen::ProcType => keep(ProcTypeWidget::new(self.model.clone(), task.proc_type.clone()).run(terminal)?, &mut task.proc_type),
+            Screen::Media if task.proc_type == ProcType::Web => {
+                keep(WebWidget::new(task.url.clone(), self.mounted_drives.clone())?.run(terminal)?, &mut task.url)
+            },
+            Screen::Media => {
+                let can_be_dir = task.proc_type == ProcType::Slideshow;
+                let step = FileSelectWidget::new(self.model.clone(), task.file.clone(), can_be_dir, task.proc_type.clone(), self.mounted_drives.clone())?.run(terminal)?;
+                let nav = keep(step, &mut task.file);
+                if nav == Nav::Next {
+                    task.uuid = fresh_uuid(std::mem::take(&mut task.uuid), &self.username, &task.file)?;
+                }
+                nav
+            },
+            Screen::Slideshow => {
+                let mut settings = (task.slide_delay, task.slideshow.clone());
+                let nav = keep(SlideshowWidget::new(settings.0, settings.1.clone()).run(terminal)?, &mut settings);
+                (task.slide_delay, task.slideshow) = settings;
+                nav
+            },
+            Screen::AdvancedSchedule => {
+                let preset = std::mem::replace(&mut task.advanced_schedule, AdvancedSchedule::No);
+                keep(AdvancedScheduleWidget::new(preset).run(terminal)?, &mut task.advanced_schedule)
+            },
+            Screen::Timings => keep(TimingsWidget::new(task.timings.clone(), self.mounted_drives.clone())?.run(terminal)?, &mut task.timings),
+            Screen::Autoloop => {
+                let preset = std::mem::replace(&mut task.auto_loop, Autoloop::No);
+                keep(AutoloopWidget::new(preset).run(terminal)?, &mut task.auto_loop)
+            }
+        };
+        Ok(nav)
+    }
+}
+
+/// The configuration saved now, if there is one.
+fn saved_task() -> Option<Task> {
+    let env_dir_path = env_file_path();
+    if !env_dir_path.exists() {
+        return None;
+    }
+    load_task(&env_dir_path).ok()
+}
+
+/// Stores the value a step returned and reports how it was left. A cancelled step leaves the
+/// value as it was.
+fn keep<T>(step: Step<T>, value: &mut T) -> Nav {
+    match step {
+        Step::Next(new) => {
+            *value = new;
+            Nav::Next
+        },
+        Step::Back(new) => {
+            *value = new;
+            Nav::Back
+        },
+        Step::Cancel => Nav::Cancel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::task;
+    use crate::parse_task;
+
+    #[test]
+    fn check_next_screen() {
+        let video = task(&[("MT_PROCTYPE", "video"), ("MT_SCHEDULE", "true")]);
+        assert_eq!(next_screen(Screen::ProcType, &video), Some(Screen::Media));
+        assert_eq!(next_screen(Screen::Media, &video), Some(Screen::AdvancedSchedule));
+        assert_eq!(next_screen(Screen::AdvancedSchedule, &video), Some(Screen::Timings));
+        assert_eq!(next_screen(Screen::Timings, &video), Some(Screen::Autoloop));
+        assert_eq!(next_screen(Screen::Autoloop, &video), None);
+
+        let slideshow = task(&[("MT_PROCTYPE", "slideshow"), ("MT_SCHEDULE", "true")]);
+        assert_eq!(next_screen(Screen::Media, &slideshow), Some(Screen::Slideshow));
+        assert_eq!(next_screen(Screen::Slideshow, &slideshow), Some(Screen::AdvancedSchedule));
+        assert_eq!(next_screen(Screen::Timings, &slideshow), None);
+
+        let unscheduled = task(&[("MT_PROCTYPE", "web"), ("MT_SCHEDULE", "false")]);
+        assert_eq!(next_screen(Screen::Media, &unscheduled), Some(Screen::AdvancedSchedule));
+        assert_eq!(next_screen(Screen::AdvancedSchedule, &unscheduled), None);
+    }
+
+    #[test]
+    fn check_keep() {
+        let mut value = 1;
+        assert_eq!(keep(Nav::Back.with(2), &mut value), Nav::Back);
+        assert_eq!(value, 2);
+        assert_eq!(keep(Nav::Cancel.with(3), &mut value), Nav::Cancel);
+        assert_eq!(value, 2);
+        assert_eq!(keep(Nav::default().with(4), &mut value), Nav::Next);
+        assert_eq!(value, 4);
+    }
+}